@@ -0,0 +1,10 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        // SAFETY: the build script is single-threaded
+        unsafe {
+            std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().unwrap());
+        }
+        tonic_build::compile_protos("proto/wcd.proto").unwrap();
+    }
+}
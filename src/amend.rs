@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Local};
+use uuid::Uuid;
+
+use crate::audit::{self, Action};
+use crate::store::{Annotation, Interval, MemoryStore, Query, Result, Store};
+
+pub const AMEND_KIND: &str = "amend";
+pub const PRIVATE_TITLE: &str = "[비공개]";
+
+#[derive(Debug, Clone)]
+pub enum Correction {
+    Tag(Option<String>),
+    Private,
+}
+
+impl Correction {
+    fn describe(&self, interval: &Interval) -> String {
+        let span = format!(
+            "{}~{}",
+            interval.start.format("%H:%M:%S"),
+            interval.end.format("%H:%M:%S")
+        );
+        match self {
+            Correction::Tag(tag) => format!(
+                "{} 태그 변경: {} -> {} ({})",
+                span,
+                interval.tag.as_deref().unwrap_or("-"),
+                tag.as_deref().unwrap_or("-"),
+                interval.title
+            ),
+            Correction::Private => format!("{} 비공개 처리", span),
+        }
+    }
+
+    fn apply(&self, interval: &mut Interval) {
+        match self {
+            Correction::Tag(tag) => interval.tag = tag.clone(),
+            Correction::Private => interval.title = PRIVATE_TITLE.to_string(),
+        }
+    }
+}
+
+pub fn amend(
+    store: &mut dyn Store,
+    target: &Interval,
+    correction: &Correction,
+) -> Result<Interval> {
+    let query = Query {
+        from: Some(target.start),
+        to: Some(target.end),
+    };
+    let mut intervals = store.query(&query)?;
+    let Some(interval) = intervals
+        .iter_mut()
+        .find(|interval| interval.id == target.id)
+    else {
+        return Err("저장소에서 수정할 구간을 찾을 수 없습니다".into());
+    };
+    let text = correction.describe(interval);
+    let original = interval.clone();
+    correction.apply(interval);
+    let amended = interval.clone();
+    store.replace_intervals(&query, &intervals)?;
+    let keep = !matches!(correction, Correction::Private);
+    audit::record_amend(&text, &original, &amended, keep)?;
+    store.append_annotation(&Annotation {
+        time: amended.start,
+        kind: AMEND_KIND.to_string(),
+        text,
+    })?;
+    Ok(amended)
+}
+
+pub fn amended_ids() -> Result<HashSet<Uuid>> {
+    Ok(audit::entries()?
+        .into_iter()
+        .filter(|entry| entry.action == Action::Amend)
+        .filter_map(|entry| entry.original.map(|original| original.id))
+        .collect())
+}
+
+pub fn as_of(store: &dyn Store, query: &Query, time: DateTime<Local>) -> Result<MemoryStore> {
+    let query = Query {
+        from: query.from,
+        to: Some(query.to.map_or(time, |to| to.min(time))),
+    };
+    let later: Vec<audit::Entry> = audit::entries()?
+        .into_iter()
+        .filter(|entry| entry.action == Action::Amend && entry.time > time)
+        .filter(|entry| entry.original.is_some())
+        .collect();
+    let mut intervals = store.query(&query)?;
+    for original in later
+        .iter()
+        .rev()
+        .filter_map(|entry| entry.original.as_ref())
+    {
+        for interval in intervals
+            .iter_mut()
+            .filter(|interval| interval.id == original.id)
+        {
+            *interval = original.clone();
+        }
+    }
+    for interval in &mut intervals {
+        interval.end = interval.end.min(time);
+    }
+    intervals.retain(|interval| interval.start < interval.end);
+    let mut reverted = MemoryStore::from_intervals(intervals);
+    for annotation in store.annotations(&query)? {
+        let undone = annotation.kind == AMEND_KIND
+            && later.iter().any(|entry| entry.target == annotation.text);
+        if !undone {
+            reverted.append_annotation(&annotation)?;
+        }
+    }
+    Ok(reverted)
+}
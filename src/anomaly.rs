@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Days, Local, NaiveDate};
+
+use crate::config::{Goal, Language};
+use crate::stats;
+use crate::store::Interval;
+
+const RECENT_DAYS: u64 = 7;
+const MIN_BASELINE_DAYS: usize = 5;
+const Z_THRESHOLD: f64 = 2.0;
+const HIGH_RATIO: f64 = 2.0;
+const LOW_RATIO: f64 = 0.25;
+
+pub struct Anomaly {
+    pub day: NaiveDate,
+    pub metric: String,
+    pub value: Duration,
+    pub baseline: Duration,
+}
+
+fn detect_metric(
+    metric: &str,
+    intervals: &[Interval],
+    tracked_days: &[NaiveDate],
+    since: NaiveDate,
+    matches: impl Fn(&Interval) -> bool,
+) -> Vec<Anomaly> {
+    let totals = stats::totals_by_day(intervals, matches);
+    let hours = |day: &NaiveDate| {
+        totals
+            .get(day)
+            .map(|total| total.as_secs_f64() / 3600.0)
+            .unwrap_or(0.0)
+    };
+    let baseline: Vec<f64> = tracked_days
+        .iter()
+        .filter(|day| **day < since)
+        .map(hours)
+        .collect();
+    if baseline.len() < MIN_BASELINE_DAYS {
+        return Vec::new();
+    }
+    let mean = baseline.iter().sum::<f64>() / baseline.len() as f64;
+    let variance = baseline.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / baseline.len() as f64;
+    let deviation = variance.sqrt();
+    if mean <= 0.0 {
+        return Vec::new();
+    }
+
+    tracked_days
+        .iter()
+        .filter(|day| **day >= since)
+        .filter_map(|day| {
+            let value = hours(day);
+            let z = if deviation > 0.0 {
+                (value - mean) / deviation
+            } else {
+                0.0
+            };
+            let high = z >= Z_THRESHOLD && value >= mean * HIGH_RATIO;
+            let low = z <= -Z_THRESHOLD || value <= mean * LOW_RATIO;
+            (high || low).then(|| Anomaly {
+                day: *day,
+                metric: metric.to_string(),
+                value: Duration::from_secs_f64(value * 3600.0),
+                baseline: Duration::from_secs_f64(mean * 3600.0),
+            })
+        })
+        .collect()
+}
+
+pub fn detect(
+    goals: &[Goal],
+    intervals: &[Interval],
+    now: DateTime<Local>,
+    language: Language,
+) -> Vec<Anomaly> {
+    let since = now.date_naive() - Days::new(RECENT_DAYS - 1);
+    let mut tracked_days: Vec<NaiveDate> = intervals
+        .iter()
+        .map(|interval| interval.start.date_naive())
+        .filter(|day| *day < now.date_naive())
+        .collect();
+    tracked_days.sort();
+    tracked_days.dedup();
+
+    let mut anomalies = detect_metric(
+        language.pick("집중 시간", "Focus time"),
+        intervals,
+        &tracked_days,
+        since,
+        |interval| stats::is_focus(&interval.title),
+    );
+    for goal in goals {
+        anomalies.extend(detect_metric(
+            &goal.name,
+            intervals,
+            &tracked_days,
+            since,
+            |interval| goal.matches(&interval.title),
+        ));
+    }
+    anomalies.sort_by(|a, b| a.day.cmp(&b.day).then_with(|| a.metric.cmp(&b.metric)));
+    anomalies
+}
+
+impl Anomaly {
+    pub fn line(&self, language: Language) -> String {
+        let value = self.value.as_secs_f64() / 3600.0;
+        let baseline = self.baseline.as_secs_f64() / 3600.0;
+        match language {
+            Language::Ko => format!(
+                "{} {}: {:.1}시간 (보통 {:.1}시간, {:.1}배)",
+                language.date(self.day),
+                self.metric,
+                value,
+                baseline,
+                value / baseline
+            ),
+            Language::En => format!(
+                "{} {}: {:.1}h (usually {:.1}h, {:.1}x)",
+                language.date(self.day),
+                self.metric,
+                value,
+                baseline,
+                value / baseline
+            ),
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::format::format_duration;
+use crate::store::{Interval, Result};
+
+pub const AUDIT_FILE: &str = "audit.jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Import,
+    Rules,
+    Amend,
+    Compact,
+    Purge,
+}
+
+impl Action {
+    fn label(self) -> &'static str {
+        match self {
+            Action::Import => "가져오기",
+            Action::Rules => "규칙 변경",
+            Action::Amend => "기록 수정",
+            Action::Compact => "기록 압축",
+            Action::Purge => "기록 삭제",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub time: DateTime<Local>,
+    pub action: Action,
+    pub target: String,
+    pub before: String,
+    pub after: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original: Option<Interval>,
+}
+
+pub fn record(action: Action, target: &str, before: String, after: String) -> Result<()> {
+    append(&Entry {
+        time: Local::now(),
+        action,
+        target: target.to_string(),
+        before,
+        after,
+        original: None,
+    })
+}
+
+fn interval_state(interval: &Interval) -> String {
+    format!(
+        "{} (태그 {})",
+        interval.title,
+        interval.tag.as_deref().unwrap_or("-")
+    )
+}
+
+pub fn record_amend(note: &str, original: &Interval, amended: &Interval, keep: bool) -> Result<()> {
+    append(&Entry {
+        time: Local::now(),
+        action: Action::Amend,
+        target: note.to_string(),
+        before: interval_state(if keep { original } else { amended }),
+        after: interval_state(amended),
+        original: keep.then(|| original.clone()),
+    })
+}
+
+fn append(entry: &Entry) -> Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(AUDIT_FILE)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    file.flush()?;
+    Ok(())
+}
+
+pub fn entries() -> Result<Vec<Entry>> {
+    let file = match File::open(AUDIT_FILE) {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        entries.push(serde_json::from_str(&line)?);
+    }
+    Ok(entries)
+}
+
+pub fn summarize(intervals: &[Interval]) -> String {
+    let total: Duration = intervals.iter().map(Interval::duration).sum();
+    format!("구간 {}개 · {}", intervals.len(), format_duration(&total))
+}
+
+fn optional(value: Option<f64>, unit: &str) -> String {
+    value
+        .map(|value| format!("{}{}", value, unit))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn rules(config: &Config) -> String {
+    let goals = config.goals.iter().map(|goal| {
+        format!(
+            "목표 {}[{}] 최대 {} 최소 {} 하루 {} {:?} {:?}",
+            goal.name,
+            goal.titles.join("|"),
+            optional(goal.max_hours, "h"),
+            optional(goal.min_hours, "h"),
+            optional(goal.daily_hours, "h"),
+            goal.period,
+            goal.thresholds
+        )
+    });
+    let clients = config.billing.clients.iter().map(|client| {
+        format!(
+            "청구 {}[{}] {} {} 업무 외 {} 주말 {}",
+            client.name,
+            client.titles.join("|"),
+            client.rate,
+            client
+                .currency
+                .as_deref()
+                .unwrap_or(&config.billing.currency),
+            optional(client.after_hours, "x"),
+            optional(client.weekend, "x")
+        )
+    });
+    let titles = config
+        .title_rules
+        .iter()
+        .map(|rule| format!("제목 규칙 {}", rule));
+    let categories = config.categories.iter().map(|category| {
+        format!(
+            "분류 {}[{}][{}]",
+            category.name,
+            category.titles.join("|"),
+            category.exes.join("|")
+        )
+    });
+    goals
+        .chain(clients)
+        .chain(titles)
+        .chain(categories)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub fn record_rule_changes(config: &Config) -> Result<()> {
+    let current = rules(config);
+    let previous = entries()?
+        .into_iter()
+        .rev()
+        .find(|entry| entry.action == Action::Rules)
+        .map(|entry| entry.after);
+    if previous.as_deref() == Some(current.as_str()) {
+        return Ok(());
+    }
+    if previous.is_none() && current.is_empty() {
+        return Ok(());
+    }
+    record(
+        Action::Rules,
+        &crate::config::path().display().to_string(),
+        previous.unwrap_or_default(),
+        current,
+    )
+}
+
+pub fn print_audit() -> Result<()> {
+    let entries = entries()?;
+    if entries.is_empty() {
+        println!("감사 기록이 없습니다 ({})", AUDIT_FILE);
+        return Ok(());
+    }
+    for entry in entries {
+        println!(
+            "{}  {}  {}",
+            entry.time.format("%Y-%m-%d %H:%M:%S"),
+            entry.action.label(),
+            entry.target
+        );
+        println!("  이전: {}", or_dash(&entry.before));
+        println!("  이후: {}", or_dash(&entry.after));
+    }
+    Ok(())
+}
+
+fn or_dash(text: &str) -> &str {
+    if text.is_empty() { "-" } else { text }
+}
@@ -0,0 +1,231 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::audit::AUDIT_FILE;
+use crate::binding::BINDINGS_FILE;
+use crate::config::{self, Config, StoreBackend};
+use crate::export::CURSOR_FILE;
+use crate::signing::{KEY_FILE, PUBLIC_KEY_FILE};
+use crate::store::{Result, SqliteStore};
+use crate::tasks::TASK_STACK_FILE;
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Entry {
+    pub original: PathBuf,
+    pub name: PathBuf,
+    pub size: u64,
+    pub sha256: String,
+    #[serde(default)]
+    pub sqlite: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: String,
+    pub created: DateTime<Local>,
+    pub data_dir: PathBuf,
+    pub files: Vec<Entry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Sources {
+    store: Option<PathBuf>,
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+}
+
+pub struct Restored {
+    pub files: usize,
+    pub created: DateTime<Local>,
+}
+
+fn sha256(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+fn backup_name(original: &Path, index: usize) -> PathBuf {
+    let relative = original
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if relative {
+        Path::new("data").join(original)
+    } else {
+        Path::new("external")
+            .join(index.to_string())
+            .join(original.file_name().unwrap_or_default())
+    }
+}
+
+fn files_in(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files_in(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+impl Sources {
+    pub fn new(config: &Config) -> Sources {
+        let store = match config.store.backend {
+            StoreBackend::Sqlite => Some(config.store.path().to_path_buf()),
+            StoreBackend::Jsonl => None,
+            StoreBackend::Memory | StoreBackend::Postgres => {
+                eprintln!("저장소 백엔드가 파일이 아니어서 기록은 백업하지 않습니다");
+                None
+            }
+        };
+        let mut files = vec![config::path().to_path_buf()];
+        if config.store.backend == StoreBackend::Jsonl {
+            files.push(config.store.path().to_path_buf());
+        }
+        files.extend(
+            [
+                AUDIT_FILE,
+                BINDINGS_FILE,
+                TASK_STACK_FILE,
+                CURSOR_FILE,
+                KEY_FILE,
+                PUBLIC_KEY_FILE,
+            ]
+            .map(PathBuf::from),
+        );
+        files.extend(config.event_log.clone());
+        Sources {
+            store,
+            files,
+            dirs: vec![config.output_dir.clone(), config.screenshots.dir.clone()],
+        }
+    }
+
+    fn collect(&self) -> Result<(Option<PathBuf>, Vec<PathBuf>)> {
+        let mut files = self.files.clone();
+        for dir in &self.dirs {
+            files_in(dir, &mut files)?;
+        }
+        files.retain(|path| path.is_file());
+        files.sort();
+        files.dedup();
+        Ok((self.store.clone().filter(|path| path.is_file()), files))
+    }
+}
+
+pub fn backup(sources: &Sources, to: &Path) -> Result<(PathBuf, Manifest)> {
+    let dir = to.join(format!(
+        "wcd-backup-{}",
+        Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    if dir.exists() {
+        return Err(format!("백업 폴더가 이미 있습니다: {}", dir.display()).into());
+    }
+    let (store, files) = sources.collect()?;
+    let mut entries = Vec::new();
+    let mut add = |original: &Path, sqlite: bool| -> Result<()> {
+        let name = backup_name(original, entries.len());
+        let target = dir.join(&name);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if sqlite {
+            SqliteStore::snapshot(original, &target)?;
+        } else {
+            fs::copy(original, &target)?;
+        }
+        entries.push(Entry {
+            original: original.to_path_buf(),
+            size: fs::metadata(&target)?.len(),
+            sha256: sha256(&target)?,
+            name,
+            sqlite,
+        });
+        Ok(())
+    };
+    if let Some(store) = &store {
+        add(store, true)?;
+    }
+    for file in &files {
+        add(file, false)?;
+    }
+    let manifest = Manifest {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        created: Local::now(),
+        data_dir: std::env::current_dir()?,
+        files: entries,
+    };
+    fs::write(
+        dir.join(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    verify(&dir)?;
+    Ok((dir, manifest))
+}
+
+pub fn verify(dir: &Path) -> Result<Manifest> {
+    let manifest: Manifest = serde_json::from_str(&fs::read_to_string(dir.join(MANIFEST_FILE))?)?;
+    for entry in &manifest.files {
+        let path = dir.join(&entry.name);
+        let fail = |reason: &str| format!("{}: {}", entry.name.display(), reason);
+        let size = fs::metadata(&path).map_err(|e| fail(&e.to_string()))?.len();
+        if size != entry.size || sha256(&path)? != entry.sha256 {
+            return Err(fail("파일이 백업 이후 바뀌었거나 손상되었습니다").into());
+        }
+        if entry.sqlite {
+            SqliteStore::check_integrity(&path).map_err(|e| fail(&e.to_string()))?;
+        }
+    }
+    Ok(manifest)
+}
+
+pub fn restore(dir: &Path, force: bool) -> Result<Restored> {
+    let manifest = verify(dir)?;
+    let existing: Vec<&Path> = manifest
+        .files
+        .iter()
+        .map(|entry| entry.original.as_path())
+        .filter(|original| original.exists())
+        .collect();
+    if !force && !existing.is_empty() {
+        return Err(format!(
+            "이미 있는 파일 {}개를 덮어쓰게 됩니다 (예: {}). 덮어쓰려면 --force를 붙이세요",
+            existing.len(),
+            existing[0].display()
+        )
+        .into());
+    }
+    for entry in &manifest.files {
+        if let Some(parent) = entry.original.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if entry.sqlite {
+            for suffix in ["-wal", "-shm"] {
+                let mut side = entry.original.clone().into_os_string();
+                side.push(suffix);
+                let _ = fs::remove_file(side);
+            }
+        }
+        fs::copy(dir.join(&entry.name), &entry.original)?;
+    }
+    Ok(Restored {
+        files: manifest.files.len(),
+        created: manifest.created,
+    })
+}
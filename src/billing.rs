@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{
+    DateTime, Datelike, Days, Local, Months, NaiveDate, NaiveDateTime, TimeZone, Weekday,
+};
+
+use crate::config::{BillingConfig, Client, Language, Rounding};
+use crate::format::{format_duration, title_width, truncate_or_pad};
+use crate::store::{Query, Result, Store};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RateKind {
+    Standard,
+    AfterHours,
+    Weekend,
+}
+
+impl RateKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            RateKind::Standard => "기본",
+            RateKind::AfterHours => "업무 외",
+            RateKind::Weekend => "주말",
+        }
+    }
+
+    pub fn multiplier(self, client: &Client) -> f64 {
+        match self {
+            RateKind::Standard => 1.0,
+            RateKind::AfterHours => client.after_hours.unwrap_or(1.0),
+            RateKind::Weekend => client.weekend.unwrap_or(1.0),
+        }
+    }
+}
+
+pub struct Line<'a> {
+    pub client: &'a Client,
+    pub currency: &'a str,
+    pub times: BTreeMap<RateKind, Duration>,
+    pub amount: f64,
+}
+
+impl Line<'_> {
+    pub fn time(&self) -> Duration {
+        self.times.values().sum()
+    }
+}
+
+pub fn rate_kind(client: &Client, time: NaiveDateTime) -> RateKind {
+    let hours = client.work_hours;
+    if matches!(time.weekday(), Weekday::Sat | Weekday::Sun) {
+        RateKind::Weekend
+    } else if hours.start <= time.time() && time.time() < hours.end {
+        RateKind::Standard
+    } else {
+        RateKind::AfterHours
+    }
+}
+
+fn next_boundary(client: &Client, time: NaiveDateTime) -> NaiveDateTime {
+    let date = time.date();
+    [
+        date.and_time(client.work_hours.start),
+        date.and_time(client.work_hours.end),
+        (date + Days::new(1)).and_hms_opt(0, 0, 0).unwrap(),
+    ]
+    .into_iter()
+    .filter(|boundary| *boundary > time)
+    .min()
+    .unwrap()
+}
+
+fn split_by_rate(
+    client: &Client,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    times: &mut BTreeMap<RateKind, Duration>,
+) {
+    let mut cursor = start.naive_local();
+    let end = end.naive_local();
+    while cursor < end {
+        let next = next_boundary(client, cursor).min(end);
+        *times
+            .entry(rate_kind(client, cursor))
+            .or_insert(Duration::ZERO) += (next - cursor).to_std().unwrap_or_default();
+        cursor = next;
+    }
+}
+
+fn minor_digits(currency: &str) -> u32 {
+    match currency {
+        "KRW" | "JPY" | "VND" | "CLP" | "ISK" => 0,
+        _ => 2,
+    }
+}
+
+fn symbol(currency: &str) -> Option<&'static str> {
+    match currency {
+        "KRW" => Some("₩"),
+        "USD" => Some("$"),
+        "EUR" => Some("€"),
+        "GBP" => Some("£"),
+        "JPY" => Some("¥"),
+        _ => None,
+    }
+}
+
+pub fn round(amount: f64, currency: &str, rounding: Rounding) -> f64 {
+    let scale = 10f64.powi(minor_digits(currency) as i32);
+    let scaled = amount * scale;
+    let rounded = match rounding {
+        Rounding::Nearest => scaled.round(),
+        Rounding::Up => scaled.ceil(),
+        Rounding::Down => scaled.floor(),
+    };
+    rounded / scale
+}
+
+pub fn format_amount(amount: f64, currency: &str, config: &BillingConfig) -> String {
+    let digits = minor_digits(currency);
+    let text = format!("{:.*}", digits as usize, amount.abs());
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+
+    let mut grouped = String::new();
+    for (i, ch) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push_str(&config.thousands_separator);
+        }
+        grouped.push(ch);
+    }
+    if !fraction.is_empty() {
+        grouped.push_str(&config.decimal_separator);
+        grouped.push_str(fraction);
+    }
+
+    let sign = if amount < 0.0 { "-" } else { "" };
+    match symbol(currency) {
+        Some(symbol) => format!("{}{}{}", sign, symbol, grouped),
+        None => format!("{}{} {}", sign, grouped, currency),
+    }
+}
+
+pub fn lines<'a>(
+    config: &'a BillingConfig,
+    store: &dyn Store,
+    query: &Query,
+) -> Result<Vec<Line<'a>>> {
+    let intervals = store.query(query)?;
+    Ok(config
+        .clients
+        .iter()
+        .map(|client| {
+            let currency = client.currency.as_deref().unwrap_or(&config.currency);
+            let mut times = BTreeMap::new();
+            for interval in intervals
+                .iter()
+                .filter(|interval| client.matches(&interval.title))
+            {
+                let start = query
+                    .from
+                    .map_or(interval.start, |from| interval.start.max(from));
+                let end = query.to.map_or(interval.end, |to| interval.end.min(to));
+                split_by_rate(client, start, end, &mut times);
+            }
+            let amount: f64 = times
+                .iter()
+                .map(|(kind, time)| {
+                    time.as_secs_f64() / 3600.0 * client.rate * kind.multiplier(client)
+                })
+                .sum();
+            Line {
+                client,
+                currency,
+                times,
+                amount: round(amount, currency, config.rounding),
+            }
+        })
+        .collect())
+}
+
+pub fn month_query(month: Option<&str>) -> std::result::Result<Query, String> {
+    let first = match month {
+        Some(month) => NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+            .map_err(|e| format!("월 형식은 YYYY-MM 입니다 ({}): {}", month, e))?,
+        None => Local::now().date_naive().with_day(1).unwrap(),
+    };
+    let next = first + Months::new(1);
+    let start = |date: NaiveDate| {
+        Local
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+            .earliest()
+    };
+    Ok(Query {
+        from: start(first),
+        to: start(next),
+    })
+}
+
+pub fn print_invoice(config: &BillingConfig, store: &dyn Store, query: &Query) -> Result<()> {
+    if config.clients.is_empty() {
+        println!("설정된 청구 대상이 없습니다");
+        return Ok(());
+    }
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        println!(
+            "청구 기간: {}",
+            Language::Ko.period(
+                from.date_naive(),
+                (to - chrono::TimeDelta::days(1)).date_naive()
+            )
+        );
+    }
+
+    let lines = lines(config, store, query)?;
+    let mut totals: BTreeMap<&str, f64> = BTreeMap::new();
+    for line in &lines {
+        println!(
+            "{} {:>10} x {} = {}",
+            truncate_or_pad(&line.client.name, title_width()),
+            format_duration(&line.time()),
+            format_amount(line.client.rate, line.currency, config),
+            format_amount(line.amount, line.currency, config)
+        );
+        for (kind, time) in &line.times {
+            if *kind != RateKind::Standard && !time.is_zero() {
+                println!(
+                    "  {} x{} {:>10}",
+                    kind.label(),
+                    kind.multiplier(line.client),
+                    format_duration(time)
+                );
+            }
+        }
+        *totals.entry(line.currency).or_insert(0.0) += line.amount;
+    }
+    println!("-----------------------------------------------------");
+    for (currency, total) in totals {
+        println!(
+            "합계 ({}): {}",
+            currency,
+            format_amount(total, currency, config)
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WorkHours;
+
+    fn client() -> Client {
+        Client {
+            name: "acme".to_string(),
+            titles: vec!["acme".to_string()],
+            rate: 100.0,
+            currency: None,
+            work_hours: WorkHours::default(),
+            after_hours: Some(1.5),
+            weekend: Some(2.0),
+            id: None,
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 6, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn split_by_rate_cuts_at_work_hours_and_midnight() {
+        let mut times = BTreeMap::new();
+        split_by_rate(&client(), at(7, 17), at(8, 1), &mut times);
+        assert_eq!(times[&RateKind::Standard], Duration::from_secs(3600));
+        assert_eq!(times[&RateKind::AfterHours], Duration::from_secs(6 * 3600));
+        assert_eq!(times[&RateKind::Weekend], Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn split_by_rate_adds_to_existing_times() {
+        let mut times = BTreeMap::new();
+        split_by_rate(&client(), at(5, 8), at(5, 10), &mut times);
+        split_by_rate(&client(), at(5, 17), at(5, 19), &mut times);
+        assert_eq!(times[&RateKind::Standard], Duration::from_secs(2 * 3600));
+        assert_eq!(times[&RateKind::AfterHours], Duration::from_secs(2 * 3600));
+        assert!(!times.contains_key(&RateKind::Weekend));
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{Write, stdin, stdout};
+use std::time::Duration;
+
+use crate::format::format_duration;
+use crate::report::app_name;
+use crate::store::{Query, Result, Store};
+
+pub const BINDINGS_FILE: &str = "tag_bindings.json";
+const MIN_TAGGED: Duration = Duration::from_secs(30 * 60);
+const MIN_SHARE: f64 = 0.8;
+const LOOKBACK_DAYS: u64 = 30;
+
+pub struct Suggestion {
+    pub key: String,
+    pub tag: String,
+    pub spent: Duration,
+    pub share: f64,
+}
+
+pub fn key(title: &str, exe: Option<&str>) -> String {
+    exe.map(str::to_lowercase)
+        .unwrap_or_else(|| app_name(title))
+}
+
+pub fn load() -> Result<BTreeMap<String, String>> {
+    match fs::read_to_string(BINDINGS_FILE) {
+        Ok(text) => Ok(serde_json::from_str(&text)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn save(bindings: &BTreeMap<String, String>) -> Result<()> {
+    fs::write(BINDINGS_FILE, serde_json::to_string_pretty(bindings)?)?;
+    Ok(())
+}
+
+pub fn suggestions(store: &dyn Store) -> Result<Vec<Suggestion>> {
+    let bindings = load()?;
+    let mut tagged = HashMap::<String, HashMap<String, Duration>>::new();
+    for interval in store.query(&Query::last_days(LOOKBACK_DAYS))? {
+        let Some(tag) = &interval.tag else {
+            continue;
+        };
+        *tagged
+            .entry(key(&interval.title, interval.exe.as_deref()))
+            .or_default()
+            .entry(tag.clone())
+            .or_default() += interval.duration();
+    }
+    let mut suggestions: Vec<Suggestion> = tagged
+        .into_iter()
+        .filter(|(key, _)| !bindings.contains_key(key))
+        .filter_map(|(key, tags)| {
+            let total: Duration = tags.values().sum();
+            let (tag, spent) = tags.into_iter().max_by_key(|(_, spent)| *spent)?;
+            let share = spent.as_secs_f64() / total.as_secs_f64().max(1.0);
+            (spent >= MIN_TAGGED && share >= MIN_SHARE).then_some(Suggestion {
+                key,
+                tag,
+                spent,
+                share,
+            })
+        })
+        .collect();
+    suggestions.sort_by_key(|suggestion| std::cmp::Reverse(suggestion.spent));
+    Ok(suggestions)
+}
+
+pub fn suggest(store: &dyn Store) -> Result<()> {
+    let suggestions = suggestions(store)?;
+    if suggestions.is_empty() {
+        println!("제안할 태그 연결이 없습니다");
+        return Ok(());
+    }
+    let mut bindings = load()?;
+    for suggestion in &suggestions {
+        print!(
+            "{}: 태그 '{}'와 함께 {} 쓰였습니다 ({:.0}%). 자동으로 태그를 붙일까요? [y/N] ",
+            suggestion.key,
+            suggestion.tag,
+            format_duration(&suggestion.spent),
+            suggestion.share * 100.0
+        );
+        stdout().flush()?;
+        let mut input = String::new();
+        if stdin().read_line(&mut input)? == 0 {
+            break;
+        }
+        if input.trim().eq_ignore_ascii_case("y") {
+            bindings.insert(suggestion.key.clone(), suggestion.tag.clone());
+            save(&bindings)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn print_bindings() -> Result<()> {
+    let bindings = load()?;
+    if bindings.is_empty() {
+        println!("연결된 태그가 없습니다 ({})", BINDINGS_FILE);
+    }
+    for (key, tag) in &bindings {
+        println!("{} -> {}", key, tag);
+    }
+    Ok(())
+}
+
+pub fn remove(key: &str) -> Result<()> {
+    let mut bindings = load()?;
+    if bindings.remove(&key.to_lowercase()).is_none() && bindings.remove(key).is_none() {
+        return Err(format!("연결된 태그가 없습니다: {}", key).into());
+    }
+    save(&bindings)
+}
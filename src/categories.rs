@@ -0,0 +1,26 @@
+use std::sync::OnceLock;
+
+use crate::config::CategoryRule;
+
+pub const UNCATEGORIZED: &str = "[미분류]";
+
+static RULES: OnceLock<Vec<CategoryRule>> = OnceLock::new();
+
+pub fn set_rules(rules: Vec<CategoryRule>) {
+    let _ = RULES.set(rules);
+}
+
+pub fn rules() -> &'static [CategoryRule] {
+    RULES.get().map(Vec::as_slice).unwrap_or_default()
+}
+
+pub fn classify(title: &str, exe: Option<&str>) -> Option<&'static str> {
+    rules()
+        .iter()
+        .find(|rule| rule.matching(title, exe).is_some())
+        .map(|rule| rule.name.as_str())
+}
+
+pub fn names<'a>() -> impl Iterator<Item = &'a str> {
+    rules().iter().map(|rule| rule.name.as_str())
+}
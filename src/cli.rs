@@ -0,0 +1,440 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDate};
+use clap::{Parser, Subcommand};
+use clap_complete::Shell;
+use regex::Regex;
+use window_change_detector::config::parse_hours;
+use window_change_detector::export::Format;
+use window_change_detector::import;
+use window_change_detector::query::QueryGroup;
+use window_change_detector::standup::Style;
+
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    parse_hours(text).map(|hours| Duration::from_secs_f64(hours * 3600.0))
+}
+
+fn parse_timestamp(text: &str) -> Result<DateTime<Local>, String> {
+    import::parse_time(text).map_err(|e| e.to_string())
+}
+
+#[derive(Parser)]
+#[command(
+    version,
+    about = "활성 창 변경을 감지하고 창 별 사용 시간을 기록합니다"
+)]
+pub struct Cli {
+    /// 정규식과 일치하는 창만 기록하고 나머지는 [기타]로 묶습니다
+    #[arg(long, value_name = "REGEX")]
+    pub only: Option<Regex>,
+
+    /// 기록하는 모든 구간에 이 태그를 붙입니다
+    #[arg(long)]
+    pub tag: Option<String>,
+
+    /// 스크롤되는 로그 대신 현재 창과 누적 시간 표를 제자리에서 갱신해 보여줍니다
+    #[arg(long)]
+    pub tui: bool,
+
+    /// 알림 영역에 아이콘을 띄워 일시 중지, 재개, 요약 보기, 저장 후 종료를 메뉴로 제공합니다
+    #[arg(long)]
+    pub tray: bool,
+
+    /// 오늘 이미 기록된 시간에 이어서 셉니다 (파일을 주면 그 저장소의 오늘 기록을 먼저 가져옵니다)
+    #[arg(long, value_name = "FILE", num_args = 0..=1)]
+    pub resume: Option<Option<PathBuf>>,
+
+    /// Windows 서비스로 실행합니다 (install-service가 등록한 서비스가 사용)
+    #[arg(long)]
+    pub service: bool,
+
+    /// 창 전환 이벤트를 받을 수 없을 때 전경 창을 확인하는 간격 (밀리초)
+    #[arg(long, value_name = "MS")]
+    pub poll_interval: Option<u64>,
+
+    /// 입력이 이 시간(초)보다 오래 없으면 비활성 상태로 기록합니다
+    #[arg(long, value_name = "SECS")]
+    pub idle_threshold: Option<u64>,
+
+    /// 콘솔과 요약에서 창 제목을 보여줄 너비
+    #[arg(long, value_name = "COLUMNS")]
+    pub title_width: Option<usize>,
+
+    /// 콘솔, 알림, 로그 파일의 아이콘 표시 방식
+    #[arg(long, value_parser = ["emoji", "ascii", "plain", "a11y"])]
+    pub output: Option<String>,
+
+    /// 화면 낭독기용 출력: 아이콘과 선 없이, 열 맞춤 대신 항목 이름을 붙이고 시간을 말로 풀어 씁니다 (--output a11y와 같음)
+    #[arg(long, conflicts_with = "output")]
+    pub a11y: bool,
+
+    /// 종료 시 저장하는 요약 로그 형식
+    #[arg(long, value_parser = ["text", "csv", "json", "html", "md"])]
+    pub format: Option<String>,
+
+    /// 요약 로그에서 창을 묶는 방식 (exe는 같은 실행 파일의 창을 한 줄로, both는 그 아래에 창 제목별 시간도 보여줍니다)
+    #[arg(long, value_parser = ["title", "exe", "both"])]
+    pub group_by: Option<String>,
+
+    /// 요약 로그 파일을 저장할 폴더
+    #[arg(long, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+
+    /// 작업 폴더나 %APPDATA%\window-change-detector 대신 이 설정 파일을 읽습니다
+    #[arg(long, value_name = "FILE", global = true)]
+    pub config: Option<PathBuf>,
+
+    /// 설정 값을 덮어씁니다 (예: --set store.backend=sqlite, 여러 번 지정 가능)
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    pub set: Vec<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    pub fn overrides(&self) -> Vec<String> {
+        let mut overrides = Vec::new();
+        if let Some(ms) = self.poll_interval {
+            overrides.push(format!("poll_interval_ms={}", ms));
+        }
+        if let Some(secs) = self.idle_threshold {
+            overrides.push(format!("idle_threshold_secs={}", secs));
+        }
+        if let Some(width) = self.title_width {
+            overrides.push(format!("title_width={}", width));
+        }
+        if let Some(output) = &self.output {
+            overrides.push(format!("output={}", output));
+        }
+        if self.a11y {
+            overrides.push("output=a11y".to_string());
+        }
+        if let Some(format) = &self.format {
+            overrides.push(format!("summary_format={}", format));
+        }
+        if let Some(group) = &self.group_by {
+            overrides.push(format!("summary_group_by={}", group));
+        }
+        if let Some(dir) = &self.output_dir {
+            overrides.push(format!(
+                "output_dir={}",
+                toml::Value::String(dir.to_string_lossy().to_string())
+            ));
+        }
+        if let Some(file) = &self.resume {
+            overrides.push("resume_same_day=true".to_string());
+            if let Some(file) = file {
+                overrides.push(format!(
+                    "resume_from={}",
+                    toml::Value::String(file.to_string_lossy().to_string())
+                ));
+            }
+        }
+        overrides.extend(self.set.iter().cloned());
+        overrides
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// 외부 연동(Toggl, Jira, ActivityWatch 등) 전송 대기열
+    Sync {
+        #[command(subcommand)]
+        command: SyncCommand,
+    },
+    /// 현재 모니터 수와 Wi-Fi 해시, 일치하는 위치 프로필을 보여줍니다
+    Location,
+    /// 팀원들이 공유한 상태(여유, 작업 중, 집중 작업 중)를 보여줍니다
+    Presence,
+    /// 이번 주 목표별 사용 시간과 남은 시간을 보여줍니다
+    Status,
+    /// 창 제목, 위치, 기록에서 텍스트를 찾아 일치하는 구간과 총 시간을 보여줍니다
+    Search {
+        /// 찾을 텍스트 (대소문자 구분 없음)
+        text: String,
+    },
+    /// 조건식에 맞는 구간의 시간을 묶어서 보여 줍니다 (예: "app=chrome AND dow=Mon..Fri")
+    Query {
+        /// 필드(app, title, exe, category, tag, location, dow, hour, date, duration), 연산자(=, !=, <, <=, >, >=), AND/OR/NOT과 괄호로 이루어진 조건식
+        expr: String,
+        /// 묶는 기준
+        #[arg(long, value_enum, default_value_t = QueryGroup::None)]
+        group_by: QueryGroup,
+        /// 시작 날짜 (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// 마지막 날짜 (YYYY-MM-DD, 포함)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
+    /// SQLite 저장소에 읽기 전용 SQL을 실행합니다 (예: "SELECT exe, SUM(duration) FROM sessions GROUP BY exe")
+    Sql {
+        /// 실행할 SELECT 문
+        query: String,
+        /// 설정된 저장소 대신 읽을 SQLite 파일
+        #[arg(long, value_name = "FILE")]
+        input: Option<PathBuf>,
+        /// 표 대신 CSV로 출력
+        #[arg(long)]
+        csv: bool,
+    },
+    /// 저장된 구간을 CSV 또는 JSON으로 내보냅니다
+    Export {
+        /// 출력 형식
+        #[arg(long, value_enum, default_value_t = Format::Csv)]
+        format: Format,
+        /// 저장할 파일 (기본값은 표준 출력)
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+        /// 내보낼 열 (id,start,end,title,app,exe,app_id,category,category_id,location,duration_secs,tag,markers)
+        #[arg(long, value_delimiter = ',')]
+        fields: Vec<String>,
+        /// 가릴 열, 고를 열, 반올림을 묶은 내보내기 프로필 (client, personal, research 또는 설정의 export_profiles)
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+        /// 시작 날짜 (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// 마지막 날짜 (YYYY-MM-DD, 포함)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// 이름에 이 텍스트가 들어간 앱만 내보냅니다
+        #[arg(long, value_delimiter = ',')]
+        apps: Vec<String>,
+        /// 이 목표 분류에 속한 구간만 내보냅니다
+        #[arg(long, value_delimiter = ',')]
+        categories: Vec<String>,
+        /// 같은 출력 대상으로 지난번에 내보낸 뒤 새로 생기거나 바뀐 구간만 내보냅니다
+        #[arg(long)]
+        since_last: bool,
+        /// 내보낸 파일을 ed25519로 서명해 FILE.sig에 저장합니다
+        #[arg(long, requires = "output")]
+        sign: bool,
+    },
+    /// 서명된 내보내기 파일이 서명 후 바뀌지 않았는지 확인합니다
+    Verify {
+        /// 확인할 파일
+        file: PathBuf,
+        /// 서명 파일 (기본값은 FILE.sig)
+        #[arg(long, value_name = "FILE")]
+        signature: Option<PathBuf>,
+        /// 서명해야 할 사람의 공개 키 (base64 또는 signing.pub 파일)
+        #[arg(long, value_name = "KEY")]
+        public_key: Option<String>,
+    },
+    /// 저장소, 설정, 상태 파일, 요약 로그와 스크린샷을 백업 폴더에 복사하고 무결성을 확인합니다
+    Backup {
+        /// 백업을 만들 폴더 (그 안에 wcd-backup-날짜_시각 폴더가 생깁니다)
+        #[arg(long, value_name = "DIR", required_unless_present = "cloud")]
+        to: Option<PathBuf>,
+        /// 폴더 대신 설정한 클라우드 저장소(cloud_backup)에 암호화해서 올리고 오래된 백업을 정리합니다
+        #[arg(long, conflicts_with = "to")]
+        cloud: bool,
+    },
+    /// 백업의 무결성을 확인한 뒤 파일을 원래 위치로 되돌립니다 (추적을 멈춘 뒤 실행하세요)
+    Restore {
+        /// backup으로 만든 백업 폴더
+        #[arg(long, value_name = "DIR", required_unless_present = "cloud")]
+        from: Option<PathBuf>,
+        /// 클라우드 저장소에서 백업을 내려받아 복호화한 뒤 복원합니다 (이름을 생략하면 가장 최근 백업)
+        #[arg(long, value_name = "NAME", num_args = 0..=1, conflicts_with = "from")]
+        cloud: Option<Option<String>>,
+        /// 이미 있는 파일을 덮어씁니다
+        #[arg(long)]
+        force: bool,
+    },
+    /// 다른 곳에서 기록한 작업 시간을 저장소로 가져옵니다
+    Import {
+        /// start,end,label 열이 있는 CSV 또는 JSON 파일
+        #[arg(long, value_name = "FILE")]
+        manual: PathBuf,
+    },
+    /// 오래된 기록에서 같은 창의 인접 구간을 합치고 짧은 구간을 지운 뒤 저장소를 정리합니다
+    Compact {
+        /// 저장소를 바꾸지 않고 결과만 보여 줍니다
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 부팅할 때 추적을 시작하는 Windows 서비스로 등록하고 시작합니다 (관리자 권한 필요)
+    InstallService,
+    /// 등록한 Windows 서비스를 멈추고 제거합니다 (관리자 권한 필요)
+    UninstallService,
+    /// 하루 동안 찍은 스크린샷으로 앱/분류 이름이 표시된 타임랩스 GIF를 만듭니다
+    Timelapse {
+        /// 날짜 (YYYY-MM-DD, 기본값은 오늘)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// 프레임 사이 간격 (밀리초)
+        #[arg(long, default_value_t = 500)]
+        delay_ms: u64,
+    },
+    /// 저장된 기록이나 공유받은 기록 파일(.db, .jsonl)로 추적 없이 요약 보고서를 만듭니다
+    Report {
+        #[command(subcommand)]
+        command: Option<ReportCommand>,
+        /// 저장소 대신 읽을 기록 파일 (읽기 전용으로 엽니다)
+        #[arg(long, value_name = "FILE")]
+        input: Option<PathBuf>,
+        /// 시작 날짜 (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// 마지막 날짜 (YYYY-MM-DD, 포함)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// 이 시각까지 기록되고 수정된 상태 그대로 보고서를 다시 만듭니다 (예: "2026-03-31 18:00")
+        #[arg(long, value_name = "TIME", value_parser = parse_timestamp)]
+        as_of: Option<DateTime<Local>>,
+    },
+    /// 여러 컴퓨터에서 내보낸 기록(CSV, JSON)을 합쳐 하나의 요약 보고서를 만듭니다
+    Merge {
+        /// 합칠 내보내기 파일 (export로 만든 .csv 또는 .json)
+        #[arg(required = true, value_name = "FILE")]
+        files: Vec<PathBuf>,
+    },
+    /// 가져오기, 규칙 변경 등 기록을 바꾼 작업의 감사 기록을 보여줍니다
+    Audit,
+    /// 카운트다운 동안 추적하며 모든 구간에 라벨을 붙이고, 끝나면 목표 분류에 집중한 시간을 알려줍니다
+    Timer {
+        /// 타이머 길이 (예: 50m, 1h30m)
+        #[arg(value_parser = parse_duration)]
+        duration: Duration,
+        /// 구간에 붙일 라벨
+        #[arg(long)]
+        label: String,
+        /// 집중해야 할 목표 분류 (기본값은 라벨)
+        #[arg(long)]
+        category: Option<String>,
+    },
+    /// 작업 스택에 작업을 올립니다. 맨 위 작업이 이후 구간의 태그가 됩니다
+    Push {
+        /// 작업 이름 (예: "review PR#8")
+        task: String,
+    },
+    /// 작업 스택 맨 위 작업을 끝내고 그 아래 작업으로 돌아갑니다
+    Pop,
+    /// 작업 스택을 보여줍니다
+    Tasks,
+    /// 창과 태그의 자동 연결
+    Bindings {
+        #[command(subcommand)]
+        command: BindingsCommand,
+    },
+    /// 태그의 예상 시간을 정하고 지금까지 쓴 실제 시간과 비교합니다
+    Tag {
+        /// 태그 이름 (예: bug-1234)
+        name: String,
+        /// 예상 시간 (예: 2h, 1h30m)
+        #[arg(long, value_parser = parse_duration)]
+        estimate: Option<Duration>,
+    },
+    /// 어제와 오늘 사용 시간을 스탠드업에 붙여 넣을 짧은 목록으로 보여줍니다
+    Standup {
+        /// 출력 형식
+        #[arg(long, value_enum, default_value_t = Style::Markdown)]
+        style: Style,
+    },
+    /// 히트맵, 딥워크, 방해 요소, 지난주 대비 변화를 담은 주간 회고 문서를 만듭니다
+    Retro {
+        /// 회고할 주에 속한 날짜 (YYYY-MM-DD, 기본값은 이번 주)
+        #[arg(long)]
+        week: Option<NaiveDate>,
+        /// {week}, {heatmap}, {deep_work}, {distractions}, {deltas}를 채울 템플릿 파일
+        #[arg(long, value_name = "FILE")]
+        template: Option<PathBuf>,
+        /// 저장할 파일 (기본값은 표준 출력)
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// 특정 시각의 기록이 어떤 캡처와 규칙으로 만들어졌는지 보여줍니다
+    Explain {
+        /// 시각 (HH:MM, HH:MM:SS 또는 YYYY-MM-DD HH:MM)
+        #[arg(long)]
+        at: String,
+    },
+    /// 추적 문제를 살펴보기 위한 진단 정보
+    Debug {
+        #[command(subcommand)]
+        command: DebugCommand,
+    },
+    /// 청구 대상별 사용 시간과 청구 금액을 통화별로 보여줍니다
+    Invoice {
+        /// 청구할 달 (YYYY-MM, 기본값은 이번 달)
+        #[arg(long)]
+        month: Option<String>,
+    },
+    /// 질문에 답해 시작용 설정 파일을 만듭니다
+    Init {
+        /// 이미 있는 설정 파일을 덮어씁니다
+        #[arg(long)]
+        force: bool,
+    },
+    /// 셸 자동 완성 스크립트를 출력합니다
+    Completions {
+        /// 대상 셸
+        shell: Shell,
+    },
+    /// 설정 파일과 실제로 적용되는 설정
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// 설정 파일을 보여줍니다
+    Show {
+        /// 기본값, 설정 파일, 환경 변수(WCD_*), --set을 차례로 적용한 값과 출처를 보여줍니다
+        #[arg(long)]
+        resolved: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ReportCommand {
+    /// 부팅, 로그온, 잠금 해제로 나뉜 세션별 시간 범위와 활동 시간, 주 사용 앱을 보여줍니다
+    Sessions {
+        /// 저장소 대신 읽을 기록 파일 (.db, .jsonl)
+        #[arg(long, value_name = "FILE")]
+        input: Option<PathBuf>,
+        /// 시작 날짜 (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// 마지막 날짜 (YYYY-MM-DD, 포함)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum BindingsCommand {
+    /// 태그와 자주 함께 쓰인 창을 찾아 하나씩 확인받고 자동 태그로 연결합니다
+    Suggest,
+    /// 연결된 창과 태그를 보여줍니다
+    List,
+    /// 창의 자동 태그 연결을 지웁니다
+    Remove {
+        /// 실행 파일 이름 또는 앱 이름
+        key: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DebugCommand {
+    /// 최근 창 캡처 결과(hwnd, pid, 원래 제목, 분류 결정)를 보여줍니다
+    LastEvents {
+        /// 보여줄 개수
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+    },
+    /// 전경 창을 찾은 방법(GetForegroundWindow, GetGUIThreadInfo)별 횟수를 보여줍니다
+    Foreground,
+}
+
+#[derive(Subcommand)]
+pub enum SyncCommand {
+    /// 연동별로 아직 전송되지 않은 항목 수를 보여줍니다
+    Status,
+}
@@ -0,0 +1,415 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, Local, TimeDelta, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use ureq::http::{Request, Response};
+use ureq::{Agent, Body};
+
+use crate::backup::{self, Sources};
+use crate::config::{CloudBackupConfig, CloudTarget};
+use crate::store::Result;
+
+pub const INDEX_FILE: &str = "index.json";
+const ARCHIVE_SUFFIX: &str = ".tar.enc";
+const MAGIC: &[u8] = b"WCDBAK1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+#[cfg(not(test))]
+const KDF_ROUNDS: u32 = 600_000;
+#[cfg(test)]
+const KDF_ROUNDS: u32 = 1_000;
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+const TIMEOUT: Duration = Duration::from_secs(300);
+const RETRY: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Uploaded {
+    pub name: String,
+    pub created: DateTime<Local>,
+    pub size: u64,
+}
+
+pub struct Outcome {
+    pub uploaded: Uploaded,
+    pub removed: Vec<String>,
+}
+
+impl Outcome {
+    pub fn line(&self) -> String {
+        let mut line = format!(
+            "클라우드 백업 업로드: {} ({:.1} MB)",
+            self.uploaded.name,
+            self.uploaded.size as f64 / 1_048_576.0
+        );
+        if !self.removed.is_empty() {
+            line.push_str(&format!(", 오래된 백업 {}개 삭제", self.removed.len()));
+        }
+        line
+    }
+}
+
+fn key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(passphrase.as_bytes(), salt, KDF_ROUNDS)
+}
+
+pub fn encrypt(passphrase: &str, data: &[u8]) -> Result<Vec<u8>> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut salt).map_err(|e| e.to_string())?;
+    getrandom::getrandom(&mut nonce).map_err(|e| e.to_string())?;
+    let cipher = Aes256Gcm::new(&key(passphrase, &salt).into());
+    let sealed = cipher
+        .encrypt(Nonce::from_slice(&nonce), data)
+        .map_err(|_| "백업을 암호화할 수 없습니다")?;
+    Ok([MAGIC, &salt, &nonce, &sealed].concat())
+}
+
+pub fn decrypt(passphrase: &str, data: &[u8]) -> Result<Vec<u8>> {
+    let body = data
+        .strip_prefix(MAGIC)
+        .ok_or("암호화된 백업 파일이 아닙니다")?;
+    if body.len() < SALT_LEN + NONCE_LEN {
+        return Err("백업 파일이 잘렸습니다".into());
+    }
+    let (salt, rest) = body.split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(&key(passphrase, salt).into());
+    cipher
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| "암호가 틀렸거나 백업 파일이 손상되었습니다".into())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn hmac(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac =
+        <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC 키 길이는 제한이 없습니다");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn uri_encode(path: &str) -> String {
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+pub struct Client {
+    agent: Agent,
+    config: CloudBackupConfig,
+}
+
+impl Client {
+    pub fn new(config: CloudBackupConfig) -> Client {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(TIMEOUT))
+            .allow_non_standard_methods(true)
+            .build()
+            .into();
+        Client { agent, config }
+    }
+
+    fn key(&self, name: &str) -> String {
+        match self.config.prefix.trim_matches('/') {
+            "" => name.to_string(),
+            prefix => format!("{}/{}", prefix, name),
+        }
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        key: &str,
+        body: Vec<u8>,
+    ) -> std::result::Result<Request<Vec<u8>>, ureq::Error> {
+        let key = uri_encode(key);
+        let builder = Request::builder().method(method);
+        let builder = match &self.config.target {
+            CloudTarget::S3 {
+                endpoint,
+                bucket,
+                region,
+                access_key,
+                secret_key,
+            } => {
+                let endpoint = endpoint.trim_end_matches('/');
+                let path = format!("/{}/{}", bucket, key);
+                let now = Utc::now();
+                let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+                let date = now.format("%Y%m%d").to_string();
+                let payload = hex(&Sha256::digest(&body));
+                let canonical = format!(
+                    "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+                    method,
+                    path,
+                    host(endpoint),
+                    payload,
+                    stamp,
+                    SIGNED_HEADERS,
+                    payload
+                );
+                let scope = format!("{}/{}/s3/aws4_request", date, region);
+                let to_sign = format!(
+                    "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+                    stamp,
+                    scope,
+                    hex(&Sha256::digest(canonical.as_bytes()))
+                );
+                let mut signing_key = hmac(format!("AWS4{}", secret_key).as_bytes(), &date);
+                for part in [region.as_str(), "s3", "aws4_request"] {
+                    signing_key = hmac(&signing_key, part);
+                }
+                builder
+                    .uri(format!("{}{}", endpoint, path))
+                    .header("x-amz-date", stamp)
+                    .header("x-amz-content-sha256", payload)
+                    .header(
+                        "authorization",
+                        format!(
+                            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+                            access_key,
+                            scope,
+                            SIGNED_HEADERS,
+                            hex(&hmac(&signing_key, &to_sign))
+                        ),
+                    )
+            }
+            CloudTarget::Webdav {
+                url,
+                username,
+                password,
+            } => {
+                let builder = builder.uri(format!("{}/{}", url.trim_end_matches('/'), key));
+                match username {
+                    Some(username) => {
+                        let credentials =
+                            format!("{}:{}", username, password.as_deref().unwrap_or_default());
+                        builder.header(
+                            "authorization",
+                            format!("Basic {}", STANDARD.encode(credentials)),
+                        )
+                    }
+                    None => builder,
+                }
+            }
+        };
+        Ok(builder.body(body)?)
+    }
+
+    fn send(
+        &self,
+        method: &str,
+        key: &str,
+        body: Vec<u8>,
+    ) -> std::result::Result<Response<Body>, ureq::Error> {
+        self.agent.run(self.request(method, key, body)?)
+    }
+
+    fn get(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        match self.send("GET", &self.key(name), Vec::new()) {
+            Ok(mut response) => Ok(Some(
+                response
+                    .body_mut()
+                    .with_config()
+                    .limit(u64::MAX)
+                    .read_to_vec()?,
+            )),
+            Err(ureq::Error::StatusCode(404)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn put(&self, name: &str, body: Vec<u8>) -> Result<()> {
+        let key = self.key(name);
+        match self.send("PUT", &key, body.clone()) {
+            Err(ureq::Error::StatusCode(409))
+                if matches!(self.config.target, CloudTarget::Webdav { .. }) =>
+            {
+                let (parents, _) = key.rsplit_once('/').unwrap_or_default();
+                let mut folder = String::new();
+                for part in parents.split('/') {
+                    folder.push_str(part);
+                    folder.push('/');
+                    match self.send("MKCOL", &folder, Vec::new()) {
+                        Ok(_) | Err(ureq::Error::StatusCode(405)) => {}
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                self.send("PUT", &key, body)?;
+            }
+            result => {
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    fn delete(&self, name: &str) -> Result<()> {
+        match self.send("DELETE", &self.key(name), Vec::new()) {
+            Ok(_) | Err(ureq::Error::StatusCode(404)) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn index(&self) -> Result<Vec<Uploaded>> {
+        match self.get(INDEX_FILE)? {
+            Some(data) => Ok(serde_json::from_slice(&data)?),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+fn pack(dir: &Path) -> Result<Vec<u8>> {
+    let mut builder = tar::Builder::new(Vec::new());
+    builder.append_dir_all(dir.file_name().unwrap_or_default(), dir)?;
+    Ok(builder.into_inner()?)
+}
+
+fn staging() -> PathBuf {
+    std::env::temp_dir().join(format!("wcd-cloud-{}", std::process::id()))
+}
+
+pub fn upload(client: &Client, sources: &Sources) -> Result<Outcome> {
+    let staging = staging();
+    let sealed = backup::backup(sources, &staging).and_then(|(dir, _)| {
+        let name = format!(
+            "{}{}",
+            dir.file_name().unwrap_or_default().to_string_lossy(),
+            ARCHIVE_SUFFIX
+        );
+        Ok((name, encrypt(&client.config.passphrase, &pack(&dir)?)?))
+    });
+    let _ = fs::remove_dir_all(&staging);
+    let (name, sealed) = sealed?;
+    let uploaded = Uploaded {
+        name,
+        created: Local::now(),
+        size: sealed.len() as u64,
+    };
+    client.put(&uploaded.name, sealed)?;
+
+    let mut index = client.index()?;
+    index.push(uploaded.clone());
+    let excess = index.len().saturating_sub(client.config.keep.max(1));
+    let removed: Vec<String> = index.drain(..excess).map(|old| old.name).collect();
+    client.put(INDEX_FILE, serde_json::to_vec_pretty(&index)?)?;
+    for name in &removed {
+        client.delete(name)?;
+    }
+    Ok(Outcome { uploaded, removed })
+}
+
+pub fn download(client: &Client, name: Option<&str>, to: &Path) -> Result<PathBuf> {
+    let index = client.index()?;
+    let entry = match name {
+        Some(name) => index.iter().find(|uploaded| {
+            uploaded.name.trim_end_matches(ARCHIVE_SUFFIX) == name.trim_end_matches(ARCHIVE_SUFFIX)
+        }),
+        None => index.last(),
+    };
+    let Some(entry) = entry else {
+        let names: Vec<&str> = index
+            .iter()
+            .map(|uploaded| uploaded.name.as_str())
+            .collect();
+        return Err(format!(
+            "클라우드에서 백업을 찾을 수 없습니다 (있는 백업: {})",
+            if names.is_empty() {
+                "없음".to_string()
+            } else {
+                names.join(", ")
+            }
+        )
+        .into());
+    };
+    let sealed = client
+        .get(&entry.name)?
+        .ok_or_else(|| format!("목록에는 있지만 파일이 없습니다: {}", entry.name))?;
+    let archive = decrypt(&client.config.passphrase, &sealed)?;
+    tar::Archive::new(archive.as_slice()).unpack(to)?;
+    Ok(to.join(entry.name.trim_end_matches(ARCHIVE_SUFFIX)))
+}
+
+pub fn restore(client: &Client, name: Option<&str>, force: bool) -> Result<backup::Restored> {
+    let staging = staging();
+    let restored = download(client, name, &staging).and_then(|dir| backup::restore(&dir, force));
+    let _ = fs::remove_dir_all(&staging);
+    restored
+}
+
+pub fn spawn(sources: Sources, config: CloudBackupConfig) {
+    let every = TimeDelta::hours(config.every_hours.max(1) as i64);
+    let client = Client::new(config);
+    thread::spawn(move || {
+        loop {
+            let index = match client.index() {
+                Ok(index) => index,
+                Err(e) => {
+                    eprintln!("클라우드 백업 목록을 읽을 수 없습니다: {}", e);
+                    thread::sleep(RETRY);
+                    continue;
+                }
+            };
+            let wait = index
+                .last()
+                .and_then(|last| (last.created + every - Local::now()).to_std().ok());
+            if let Some(wait) = wait.filter(|wait| !wait.is_zero()) {
+                thread::sleep(wait);
+                continue;
+            }
+            match upload(&client, &sources) {
+                Ok(outcome) => println!("{}", outcome.line()),
+                Err(e) => {
+                    eprintln!("클라우드 백업 실패: {}", e);
+                    thread::sleep(RETRY);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_and_decrypt_round_trip() {
+        let data = b"window_log.db contents".to_vec();
+        let sealed = encrypt("secret", &data).unwrap();
+        assert!(sealed.starts_with(MAGIC));
+        assert_ne!(&sealed[MAGIC.len()..], &data[..]);
+        assert_eq!(decrypt("secret", &sealed).unwrap(), data);
+    }
+
+    #[test]
+    fn decrypt_rejects_a_wrong_passphrase_and_damaged_data() {
+        let mut sealed = encrypt("secret", b"backup").unwrap();
+        assert!(decrypt("other", &sealed).is_err());
+        assert!(decrypt("secret", b"backup").is_err());
+        assert!(decrypt("secret", &sealed[..MAGIC.len() + 4]).is_err());
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(decrypt("secret", &sealed).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{Local, TimeDelta};
+use uuid::Uuid;
+
+use crate::amend;
+use crate::audit::{self, Action};
+use crate::config::{CompactionConfig, StoreConfig};
+use crate::store::{self, Annotation, Interval, Query, Result, Store};
+
+pub const MERGE_KIND: &str = "merge";
+
+pub struct Outcome {
+    pub before: usize,
+    pub after: usize,
+    pub merged: usize,
+    pub dropped: usize,
+}
+
+impl Outcome {
+    pub fn line(&self) -> String {
+        format!(
+            "구간 {}개 -> {}개 (병합 {}, 짧은 구간 삭제 {})",
+            self.before, self.after, self.merged, self.dropped
+        )
+    }
+}
+
+fn same_window(a: &Interval, b: &Interval) -> bool {
+    a.title == b.title && a.exe == b.exe && a.tag == b.tag && a.location == b.location
+}
+
+fn compact_intervals(
+    mut intervals: Vec<Interval>,
+    config: &CompactionConfig,
+    amended: &HashSet<Uuid>,
+) -> (Vec<Interval>, Vec<Interval>, Vec<Annotation>, Outcome) {
+    let before = intervals.len();
+    let gap = Duration::from_secs(config.merge_gap_secs);
+    let min_interval = Duration::from_secs(config.min_interval_secs);
+    intervals.sort_by_key(|interval| interval.start);
+
+    let mut merged = 0;
+    let mut absorbed: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    let mut compacted: Vec<Interval> = Vec::new();
+    for interval in intervals {
+        if let Some(last) = compacted.last_mut().filter(|last| {
+            same_window(last, &interval)
+                && !amended.contains(&last.id)
+                && !amended.contains(&interval.id)
+                && (interval.start - last.end).to_std().unwrap_or_default() <= gap
+        }) {
+            last.end = last.end.max(interval.end);
+            absorbed.entry(last.id).or_default().push(interval.id);
+            merged += 1;
+            continue;
+        }
+        compacted.push(interval);
+    }
+    let (compacted, dropped): (Vec<Interval>, Vec<Interval>) =
+        compacted.into_iter().partition(|interval| {
+            amended.contains(&interval.id) || interval.duration() >= min_interval
+        });
+    let merges = compacted
+        .iter()
+        .filter_map(|interval| {
+            let ids = absorbed.get(&interval.id)?;
+            let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+            Some(Annotation {
+                time: interval.start,
+                kind: MERGE_KIND.to_string(),
+                text: format!("{} {}", interval.id, ids.join(" ")),
+            })
+        })
+        .collect();
+    let outcome = Outcome {
+        before,
+        after: compacted.len(),
+        merged,
+        dropped: dropped.len(),
+    };
+    (compacted, dropped, merges, outcome)
+}
+
+pub fn compact(store: &mut dyn Store, config: &CompactionConfig, dry_run: bool) -> Result<Outcome> {
+    let query = Query {
+        from: None,
+        to: Some(Local::now() - TimeDelta::days(config.older_than_days as i64)),
+    };
+    let intervals = store.query(&query)?;
+    let before = audit::summarize(&intervals);
+    let (compacted, dropped, merges, outcome) =
+        compact_intervals(intervals, config, &amend::amended_ids()?);
+    if !dry_run && outcome.after != outcome.before {
+        store.replace_intervals(&query, &compacted)?;
+        for merge in &merges {
+            store.append_annotation(merge)?;
+        }
+        audit::record(
+            Action::Compact,
+            &outcome.line(),
+            before,
+            audit::summarize(&compacted),
+        )?;
+        if !dropped.is_empty() {
+            audit::record(
+                Action::Purge,
+                &format!("{}초 미만 구간", config.min_interval_secs),
+                audit::summarize(&dropped),
+                String::new(),
+            )?;
+        }
+    }
+    Ok(outcome)
+}
+
+pub fn spawn_scheduler(
+    config: CompactionConfig,
+    store_config: StoreConfig,
+    store: Arc<Mutex<Box<dyn Store>>>,
+) {
+    if !config.enabled {
+        return;
+    }
+    thread::spawn(move || {
+        loop {
+            let result = compact(store.lock().unwrap().as_mut(), &config, false);
+            match result {
+                Ok(outcome) if outcome.after != outcome.before => {
+                    println!("기록 압축: {}", outcome.line());
+                    if let Err(e) = store::vacuum(&store_config) {
+                        eprintln!("압축 후 저장소 정리 실패: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("기록 압축 실패: {}", e),
+            }
+            thread::sleep(Duration::from_secs(config.every_hours.max(1) * 3600));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(from_secs: i64, to_secs: i64, title: &str) -> Interval {
+        let base = Local::now() - TimeDelta::days(30);
+        Interval::new(
+            base + TimeDelta::seconds(from_secs),
+            base + TimeDelta::seconds(to_secs),
+            title.to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn compact_intervals_merges_close_neighbours_and_drops_short_ones() {
+        let first = interval(0, 10, "a");
+        let second = interval(12, 20, "a");
+        let intervals = vec![
+            interval(50, 60, "b"),
+            second.clone(),
+            first.clone(),
+            interval(40, 41, "a"),
+        ];
+        let (compacted, dropped, merges, outcome) =
+            compact_intervals(intervals, &CompactionConfig::default(), &HashSet::new());
+
+        assert_eq!(compacted.len(), 2);
+        assert_eq!(compacted[0].id, first.id);
+        assert_eq!(compacted[0].end, second.end);
+        assert_eq!(compacted[1].title, "b");
+        assert_eq!(dropped.len(), 1);
+        assert_eq!(dropped[0].duration(), Duration::from_secs(1));
+        assert_eq!(merges.len(), 1);
+        assert_eq!(merges[0].kind, MERGE_KIND);
+        assert_eq!(merges[0].text, format!("{} {}", first.id, second.id));
+        assert_eq!(
+            (
+                outcome.before,
+                outcome.after,
+                outcome.merged,
+                outcome.dropped
+            ),
+            (4, 2, 1, 1)
+        );
+    }
+
+    #[test]
+    fn compact_intervals_leaves_amended_intervals_alone() {
+        let first = interval(0, 10, "a");
+        let amended = interval(11, 12, "a");
+        let (compacted, dropped, merges, _) = compact_intervals(
+            vec![first.clone(), amended.clone()],
+            &CompactionConfig::default(),
+            &HashSet::from([amended.id]),
+        );
+
+        assert_eq!(compacted.len(), 2);
+        assert_eq!(compacted[0].end, first.end);
+        assert_eq!(compacted[1].id, amended.id);
+        assert!(dropped.is_empty());
+        assert!(merges.is_empty());
+    }
+}
@@ -0,0 +1,776 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::Uuid;
+
+use crate::ids;
+use crate::layers::{self, Override};
+use crate::validate;
+
+pub const CONFIG_FILE: &str = "window-change-detector.toml";
+
+static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+fn find() -> PathBuf {
+    let local = PathBuf::from(CONFIG_FILE);
+    if local.exists() {
+        return local;
+    }
+    std::env::var_os("APPDATA")
+        .map(|appdata| {
+            PathBuf::from(appdata)
+                .join("window-change-detector")
+                .join(CONFIG_FILE)
+        })
+        .filter(|path| path.exists())
+        .unwrap_or(local)
+}
+
+pub fn set_path(explicit: Option<PathBuf>) {
+    let _ = PATH.set(explicit.unwrap_or_else(find));
+}
+
+pub fn path() -> &'static Path {
+    PATH.get_or_init(find)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub off_hours: Option<OffHours>,
+    pub switch_debounce_ms: u64,
+    pub poll_interval_ms: u64,
+    pub idle_threshold_secs: u64,
+    pub unlock_grace_secs: u64,
+    pub autosave_secs: u64,
+    pub resume_same_day: bool,
+    pub resume_from: Option<PathBuf>,
+    pub event_log: Option<PathBuf>,
+    pub daily_rollover: bool,
+    pub day_start_hour: u32,
+    pub title_width: usize,
+    pub output_dir: PathBuf,
+    pub summary_format: SummaryFormat,
+    pub summary_group_by: GroupBy,
+    pub ignore: Vec<String>,
+    pub title_rules: Vec<String>,
+    pub remote_tools: Vec<String>,
+    pub meaningful_input: bool,
+    pub mouse_jitter_px: u32,
+    pub raw_input: RawInputConfig,
+    pub output: OutputProfile,
+    pub theme: Theme,
+    pub report_language: Language,
+    pub store: StoreConfig,
+    pub grpc: Option<GrpcConfig>,
+    pub grafana: Option<GrafanaConfig>,
+    pub presence: Option<PresenceConfig>,
+    pub exporters: Vec<ExporterConfig>,
+    pub export_profiles: BTreeMap<String, ExportProfile>,
+    pub export_queue: ExportQueueConfig,
+    pub locations: Vec<LocationProfile>,
+    pub goals: Vec<Goal>,
+    pub categories: Vec<CategoryRule>,
+    pub limits: BTreeMap<String, String>,
+    pub billing: BillingConfig,
+    pub screenshots: ScreenshotConfig,
+    pub compaction: CompactionConfig,
+    pub cloud_backup: Option<CloudBackupConfig>,
+    pub service: ServiceConfig,
+    pub markers: Vec<Marker>,
+    pub retro_template: Option<PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            off_hours: None,
+            switch_debounce_ms: 0,
+            poll_interval_ms: 500,
+            idle_threshold_secs: 60,
+            unlock_grace_secs: 10,
+            autosave_secs: 60,
+            resume_same_day: false,
+            resume_from: None,
+            event_log: None,
+            daily_rollover: true,
+            day_start_hour: 0,
+            title_width: 40,
+            output_dir: PathBuf::from("."),
+            summary_format: SummaryFormat::default(),
+            summary_group_by: GroupBy::default(),
+            ignore: Vec::new(),
+            title_rules: Vec::new(),
+            remote_tools: vec![
+                "TeamViewer_Desktop.exe".to_string(),
+                "QuickAssist.exe".to_string(),
+            ],
+            meaningful_input: false,
+            mouse_jitter_px: 10,
+            raw_input: RawInputConfig::default(),
+            output: OutputProfile::default(),
+            theme: Theme::default(),
+            report_language: Language::default(),
+            store: StoreConfig::default(),
+            grpc: None,
+            grafana: None,
+            presence: None,
+            exporters: Vec::new(),
+            export_profiles: BTreeMap::new(),
+            export_queue: ExportQueueConfig::default(),
+            locations: Vec::new(),
+            goals: Vec::new(),
+            categories: Vec::new(),
+            limits: BTreeMap::new(),
+            billing: BillingConfig::default(),
+            screenshots: ScreenshotConfig::default(),
+            compaction: CompactionConfig::default(),
+            cloud_backup: None,
+            service: ServiceConfig::default(),
+            markers: Vec::new(),
+            retro_template: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OffHours {
+    #[serde(
+        serialize_with = "serialize_time",
+        deserialize_with = "deserialize_time"
+    )]
+    pub start: NaiveTime,
+    #[serde(
+        serialize_with = "serialize_time",
+        deserialize_with = "deserialize_time"
+    )]
+    pub end: NaiveTime,
+}
+
+impl OffHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputProfile {
+    #[default]
+    Emoji,
+    Ascii,
+    Plain,
+    A11y,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    #[default]
+    Title,
+    #[serde(rename = "exe", alias = "app")]
+    App,
+    Category,
+    Both,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryFormat {
+    #[default]
+    Text,
+    Csv,
+    Json,
+    Html,
+    Md,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    Ko,
+    En,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreBackend {
+    #[default]
+    Memory,
+    Jsonl,
+    Sqlite,
+    Postgres,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StoreConfig {
+    pub backend: StoreBackend,
+    pub path: Option<PathBuf>,
+    pub url: Option<String>,
+    pub user: Option<String>,
+}
+
+impl StoreConfig {
+    pub fn path(&self) -> &Path {
+        match (&self.path, self.backend) {
+            (Some(path), _) => path,
+            (None, StoreBackend::Sqlite) => Path::new("window_log.db"),
+            (None, _) => Path::new("window_log.jsonl"),
+        }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn user(&self) -> String {
+        self.user
+            .clone()
+            .or_else(|| std::env::var("USERNAME").ok())
+            .unwrap_or_else(|| "default".to_string())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrpcConfig {
+    pub listen: SocketAddr,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrafanaConfig {
+    pub listen: SocketAddr,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PresenceConfig {
+    pub name: String,
+    pub url: Option<String>,
+    pub path: Option<PathBuf>,
+    pub headers: BTreeMap<String, String>,
+    pub deep_work_minutes: u64,
+    pub heartbeat_secs: u64,
+}
+
+impl Default for PresenceConfig {
+    fn default() -> Self {
+        PresenceConfig {
+            name: std::env::var("USERNAME").unwrap_or_else(|_| "unknown".to_string()),
+            url: None,
+            path: None,
+            headers: BTreeMap::new(),
+            deep_work_minutes: 25,
+            heartbeat_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum ExporterConfig {
+    Webhook {
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    Influxdb {
+        url: String,
+        org: String,
+        bucket: String,
+        token: String,
+    },
+    #[cfg_attr(not(feature = "mqtt"), allow(dead_code))]
+    Mqtt {
+        host: String,
+        #[serde(default = "default_mqtt_port")]
+        port: u16,
+        topic: String,
+    },
+    Toggl {
+        api_token: String,
+        workspace_id: u64,
+    },
+    Jira {
+        url: String,
+        email: String,
+        api_token: String,
+        issue: String,
+        title_contains: Option<String>,
+    },
+    Activitywatch {
+        #[serde(default = "default_activitywatch_url")]
+        url: String,
+        bucket: Option<String>,
+    },
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_activitywatch_url() -> String {
+    "http://localhost:5600".to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportProfile {
+    pub fields: Vec<String>,
+    pub redact: Vec<String>,
+    pub pseudonymize: Vec<String>,
+    pub round_minutes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportQueueConfig {
+    pub batch_size: usize,
+    pub flush_interval_secs: u64,
+    pub min_interval_ms: u64,
+    pub max_memory: usize,
+    pub spool_dir: PathBuf,
+}
+
+impl Default for ExportQueueConfig {
+    fn default() -> Self {
+        ExportQueueConfig {
+            batch_size: 50,
+            flush_interval_secs: 10,
+            min_interval_ms: 1000,
+            max_memory: 1000,
+            spool_dir: PathBuf::from("export_spool"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScreenshotConfig {
+    pub enabled: bool,
+    pub on_switch: bool,
+    pub every_minutes: Option<u64>,
+    pub width: u32,
+    pub blur: u32,
+    pub dir: PathBuf,
+    pub max_days: u64,
+    pub max_files: usize,
+    pub redact: Vec<RedactRegion>,
+    pub private_titles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RedactRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for ScreenshotConfig {
+    fn default() -> Self {
+        ScreenshotConfig {
+            enabled: false,
+            on_switch: true,
+            every_minutes: None,
+            width: 320,
+            blur: 0,
+            dir: PathBuf::from("screenshots"),
+            max_days: 7,
+            max_files: 1000,
+            redact: Vec::new(),
+            private_titles: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawInputConfig {
+    pub gamepad: bool,
+    pub joystick: bool,
+    pub pen: bool,
+    pub touch: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CompactionConfig {
+    pub enabled: bool,
+    pub every_hours: u64,
+    pub older_than_days: u64,
+    pub merge_gap_secs: u64,
+    pub min_interval_secs: u64,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        CompactionConfig {
+            enabled: false,
+            every_hours: 24,
+            older_than_days: 7,
+            merge_gap_secs: 5,
+            min_interval_secs: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum CloudTarget {
+    S3 {
+        endpoint: String,
+        bucket: String,
+        #[serde(default = "default_s3_region")]
+        region: String,
+        access_key: String,
+        secret_key: String,
+    },
+    Webdav {
+        url: String,
+        username: Option<String>,
+        password: Option<String>,
+    },
+}
+
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CloudBackupConfig {
+    pub target: CloudTarget,
+    pub passphrase: String,
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default = "default_cloud_backup_hours")]
+    pub every_hours: u64,
+    #[serde(default = "default_cloud_backup_keep")]
+    pub keep: usize,
+}
+
+fn default_cloud_backup_hours() -> u64 {
+    24
+}
+
+fn default_cloud_backup_keep() -> usize {
+    7
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServiceConfig {
+    pub name: String,
+    pub data_dir: PathBuf,
+}
+
+impl Default for ServiceConfig {
+    fn default() -> Self {
+        ServiceConfig {
+            name: "WindowChangeDetector".to_string(),
+            data_dir: std::env::var_os("ProgramData")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(r"C:\ProgramData"))
+                .join("window-change-detector"),
+        }
+    }
+}
+
+impl ServiceConfig {
+    pub fn enter_data_dir(&self) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.data_dir)?;
+        std::env::set_current_dir(&self.data_dir)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Marker {
+    pub name: String,
+    pub hotkey: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocationProfile {
+    pub name: String,
+    pub monitors: Option<u32>,
+    pub docked: Option<bool>,
+    pub wifi: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Day,
+    #[default]
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Goal {
+    pub name: String,
+    pub titles: Vec<String>,
+    pub max_hours: Option<f64>,
+    pub min_hours: Option<f64>,
+    pub daily_hours: Option<f64>,
+    #[serde(default)]
+    pub period: Period,
+    #[serde(default)]
+    pub thresholds: Vec<u32>,
+    pub id: Option<Uuid>,
+}
+
+impl Goal {
+    pub fn id(&self) -> Uuid {
+        self.id.unwrap_or_else(|| ids::project_id(&self.name))
+    }
+
+    pub fn matches(&self, title: &str) -> bool {
+        let title = title.to_lowercase();
+        self.titles
+            .iter()
+            .any(|pattern| title.contains(&pattern.to_lowercase()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CategoryRule {
+    pub name: String,
+    #[serde(default)]
+    pub titles: Vec<String>,
+    #[serde(default)]
+    pub exes: Vec<String>,
+}
+
+impl CategoryRule {
+    pub fn matching(&self, title: &str, exe: Option<&str>) -> Option<&str> {
+        let title = title.to_lowercase();
+        let title_match = self
+            .titles
+            .iter()
+            .find(|pattern| title.contains(&pattern.to_lowercase()));
+        let exe_match = exe.and_then(|exe| {
+            self.exes
+                .iter()
+                .find(|pattern| pattern.eq_ignore_ascii_case(exe))
+        });
+        title_match.or(exe_match).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rounding {
+    #[default]
+    Nearest,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BillingConfig {
+    pub currency: String,
+    pub decimal_separator: String,
+    pub thousands_separator: String,
+    pub rounding: Rounding,
+    pub clients: Vec<Client>,
+}
+
+impl Default for BillingConfig {
+    fn default() -> Self {
+        BillingConfig {
+            currency: "KRW".to_string(),
+            decimal_separator: ".".to_string(),
+            thousands_separator: ",".to_string(),
+            rounding: Rounding::default(),
+            clients: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkHours {
+    #[serde(
+        serialize_with = "serialize_time",
+        deserialize_with = "deserialize_time"
+    )]
+    pub start: NaiveTime,
+    #[serde(
+        serialize_with = "serialize_time",
+        deserialize_with = "deserialize_time"
+    )]
+    pub end: NaiveTime,
+}
+
+impl Default for WorkHours {
+    fn default() -> Self {
+        WorkHours {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Client {
+    pub name: String,
+    pub titles: Vec<String>,
+    pub rate: f64,
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub work_hours: WorkHours,
+    pub after_hours: Option<f64>,
+    pub weekend: Option<f64>,
+    pub id: Option<Uuid>,
+}
+
+impl Client {
+    pub fn id(&self) -> Uuid {
+        self.id.unwrap_or_else(|| ids::project_id(&self.name))
+    }
+
+    pub fn matches(&self, title: &str) -> bool {
+        let title = title.to_lowercase();
+        self.titles
+            .iter()
+            .any(|pattern| title.contains(&pattern.to_lowercase()))
+    }
+}
+
+pub fn parse_limit(text: &str) -> Result<(f64, Period), String> {
+    let (amount, per) = text
+        .split_once('/')
+        .ok_or_else(|| format!("한도는 '45m/day' 형식이어야 합니다: {}", text))?;
+    let period = match per.trim() {
+        "day" => Period::Day,
+        "week" => Period::Week,
+        "month" => Period::Month,
+        other => return Err(format!("알 수 없는 기간: {}", other)),
+    };
+    let hours = parse_hours(amount).map_err(|_| format!("잘못된 한도 시간: {}", amount))?;
+    Ok((hours, period))
+}
+
+pub fn parse_hours(text: &str) -> Result<f64, String> {
+    let mut hours = 0.0;
+    let mut number = String::new();
+    for ch in text.trim().chars() {
+        match ch {
+            '0'..='9' | '.' => number.push(ch),
+            'h' | 'm' => {
+                let value: f64 = number
+                    .parse()
+                    .map_err(|_| format!("잘못된 시간: {}", text))?;
+                hours += if ch == 'h' { value } else { value / 60.0 };
+                number.clear();
+            }
+            _ => return Err(format!("잘못된 시간: {}", text)),
+        }
+    }
+    if !number.is_empty() || hours <= 0.0 {
+        return Err(format!("잘못된 시간: {}", text));
+    }
+    Ok(hours)
+}
+
+fn serialize_time<S>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&time.format("%H:%M").to_string())
+}
+
+fn deserialize_time<'de, D>(deserializer: D) -> Result<NaiveTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    NaiveTime::parse_from_str(&s, "%H:%M").map_err(serde::de::Error::custom)
+}
+
+impl Config {
+    pub fn layered(overrides: &[String]) -> Result<(Config, toml::Table, Vec<Override>), String> {
+        let path = path();
+        let text = if path.exists() {
+            std::fs::read_to_string(path).map_err(|e| e.to_string())?
+        } else if path != Path::new(CONFIG_FILE) {
+            return Err(format!("설정 파일이 없습니다: {}", path.display()));
+        } else {
+            String::new()
+        };
+        let file: toml::Table =
+            toml::from_str(&text).map_err(|e| validate::parse_error(&text, &e))?;
+        let mut config: Config =
+            toml::from_str(&text).map_err(|e| validate::parse_error(&text, &e))?;
+        let overrides = layers::overrides(overrides)?;
+        if !overrides.is_empty() {
+            let mut table = file.clone();
+            layers::apply(&mut table, &overrides)?;
+            config = toml::Value::Table(table).try_into().map_err(|e| {
+                format!(
+                    "환경 변수나 --set 값을 적용할 수 없습니다: {}",
+                    validate::parse_error("", &e)
+                )
+            })?;
+        }
+        let problems = validate::check(&text, &config);
+        if !problems.is_empty() {
+            return Err(format!(
+                "설정 오류 {}건\n{}",
+                problems.len(),
+                problems
+                    .iter()
+                    .map(|problem| format!("- {}", problem))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ));
+        }
+        Ok((config, file, overrides))
+    }
+
+    pub fn load(overrides: &[String]) -> Result<Config, String> {
+        let (mut config, _, _) = Config::layered(overrides)?;
+        for (name, limit) in &config.limits {
+            let (hours, period) =
+                parse_limit(limit).map_err(|e| format!("limits.{}: {}", name, e))?;
+            config.goals.push(Goal {
+                name: name.clone(),
+                titles: vec![name.clone()],
+                max_hours: Some(hours),
+                min_hours: None,
+                daily_hours: None,
+                period,
+                thresholds: vec![75, 90, 100],
+                id: None,
+            });
+        }
+        Ok(config)
+    }
+}
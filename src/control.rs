@@ -0,0 +1,151 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::store::Interval;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Switch,
+    IdleStart,
+    IdleChange,
+    Active,
+    OffHoursStart,
+    OffHoursEnd,
+    Paused,
+    Resumed,
+    Annotation,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Switch => "switch",
+            EventKind::IdleStart => "idle_start",
+            EventKind::IdleChange => "idle_change",
+            EventKind::Active => "active",
+            EventKind::OffHoursStart => "off_hours_start",
+            EventKind::OffHoursEnd => "off_hours_end",
+            EventKind::Paused => "paused",
+            EventKind::Resumed => "resumed",
+            EventKind::Annotation => "annotation",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub time: DateTime<Local>,
+    pub kind: EventKind,
+    pub title: String,
+    pub exe: Option<String>,
+    pub duration: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Status {
+    pub title: Option<String>,
+    pub since: Option<DateTime<Local>>,
+    pub paused: bool,
+}
+
+#[derive(Default)]
+pub struct Control {
+    paused: AtomicBool,
+    stopped: AtomicBool,
+    status: Mutex<Status>,
+    open: Mutex<Option<Interval>>,
+    session_start: Mutex<Option<DateTime<Local>>>,
+    markers: Mutex<Vec<String>>,
+    subscribers: Mutex<Vec<Sender<Event>>>,
+    interval_subscribers: Mutex<Vec<Sender<Interval>>>,
+}
+
+impl Control {
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub fn status(&self) -> Status {
+        let mut status = self.status.lock().unwrap().clone();
+        status.paused = self.is_paused();
+        status
+    }
+
+    pub fn set_status(&self, title: Option<String>, since: Option<DateTime<Local>>) {
+        let mut status = self.status.lock().unwrap();
+        status.title = title;
+        status.since = since;
+    }
+
+    pub fn set_open(&self, interval: Option<Interval>) {
+        *self.open.lock().unwrap() = interval;
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::SeqCst)
+    }
+
+    pub fn stop(&self, at: DateTime<Local>) -> Option<Interval> {
+        self.stopped.store(true, Ordering::SeqCst);
+        let mut open = self.open.lock().unwrap().take()?;
+        open.end = at;
+        (open.start < open.end).then_some(open)
+    }
+
+    pub fn session_start(&self) -> DateTime<Local> {
+        self.session_start
+            .lock()
+            .unwrap()
+            .unwrap_or_else(Local::now)
+    }
+
+    pub fn start_session(&self, at: DateTime<Local>) {
+        *self.session_start.lock().unwrap() = Some(at);
+    }
+
+    pub fn mark(&self, name: &str) {
+        self.markers.lock().unwrap().push(name.to_string());
+    }
+
+    pub fn take_markers(&self) -> Vec<String> {
+        std::mem::take(&mut *self.markers.lock().unwrap())
+    }
+
+    pub fn subscribe(&self) -> Receiver<Event> {
+        let (tx, rx) = channel();
+        self.subscribers.lock().unwrap().push(tx);
+        rx
+    }
+
+    pub fn publish(&self, event: &Event) {
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|tx| tx.send(event.clone()).is_ok());
+    }
+
+    pub fn subscribe_intervals(&self) -> Receiver<Interval> {
+        let (tx, rx) = channel();
+        self.interval_subscribers.lock().unwrap().push(tx);
+        rx
+    }
+
+    pub fn publish_interval(&self, interval: &Interval) {
+        self.interval_subscribers
+            .lock()
+            .unwrap()
+            .retain(|tx| tx.send(interval.clone()).is_ok());
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::format::truncate_or_pad;
+use crate::store::Result;
+use crate::window::{self, Capture, ForegroundSource};
+
+pub const LAST_EVENTS_FILE: &str = "last_events.json";
+pub const SOURCES_FILE: &str = "foreground_sources.json";
+const CAPACITY: usize = 200;
+const PANIC_DUMP: usize = 20;
+
+static EVENTS: Mutex<VecDeque<RawEvent>> = Mutex::new(VecDeque::new());
+static SAVED_FALLBACKS: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SourceCount {
+    pub source: String,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawEvent {
+    pub time: DateTime<Local>,
+    pub last_seen: DateTime<Local>,
+    pub repeats: u32,
+    pub hwnd: Option<isize>,
+    pub pid: Option<u32>,
+    #[serde(default)]
+    pub exe: Option<String>,
+    pub class: String,
+    pub raw_title: String,
+    pub decision: String,
+}
+
+impl RawEvent {
+    fn same_as(&self, other: &RawEvent) -> bool {
+        self.hwnd == other.hwnd
+            && self.raw_title == other.raw_title
+            && self.decision == other.decision
+    }
+
+    pub fn line(&self) -> String {
+        format!(
+            "[{} ~ {} x{}] hwnd={} pid={} exe={} class={} title={:?} -> {}",
+            self.time.format("%H:%M:%S%.3f"),
+            self.last_seen.format("%H:%M:%S%.3f"),
+            self.repeats,
+            self.hwnd
+                .map(|hwnd| format!("{:#x}", hwnd))
+                .unwrap_or_else(|| "-".to_string()),
+            self.pid
+                .map(|pid| pid.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            self.exe.as_deref().unwrap_or("-"),
+            self.class,
+            self.raw_title,
+            self.decision
+        )
+    }
+}
+
+fn save(events: &VecDeque<RawEvent>) -> Result<()> {
+    fs::write(LAST_EVENTS_FILE, serde_json::to_string_pretty(events)?)?;
+    Ok(())
+}
+
+fn write_sources(counts: &[SourceCount]) -> Result<()> {
+    fs::write(SOURCES_FILE, serde_json::to_string_pretty(counts)?)?;
+    Ok(())
+}
+
+fn save_sources(force: bool) {
+    let counts = window::source_counts();
+    let fallbacks = counts
+        .iter()
+        .filter(|(source, _)| *source != ForegroundSource::Foreground)
+        .map(|(_, count)| count)
+        .sum();
+    if SAVED_FALLBACKS.swap(fallbacks, Ordering::SeqCst) == fallbacks && !force {
+        return;
+    }
+    let counts: Vec<SourceCount> = counts
+        .into_iter()
+        .map(|(source, count)| SourceCount {
+            source: source.as_str().to_string(),
+            count,
+        })
+        .collect();
+    if let Err(e) = write_sources(&counts) {
+        eprintln!("창 찾기 통계 저장 실패 ({}): {}", SOURCES_FILE, e);
+    }
+}
+
+pub fn record(capture: Option<&Capture>, decision: &str) {
+    save_sources(false);
+    let now = Local::now();
+    let event = RawEvent {
+        time: now,
+        last_seen: now,
+        repeats: 1,
+        hwnd: capture.map(|capture| capture.hwnd),
+        pid: capture.map(|capture| capture.pid),
+        exe: capture.and_then(|capture| capture.exe.clone()),
+        class: capture
+            .map(|capture| capture.class.clone())
+            .unwrap_or_default(),
+        raw_title: capture
+            .map(|capture| capture.raw_title.clone())
+            .unwrap_or_default(),
+        decision: decision.to_string(),
+    };
+    let mut events = EVENTS.lock().unwrap();
+    if let Some(last) = events.back_mut().filter(|last| last.same_as(&event)) {
+        last.last_seen = now;
+        last.repeats += 1;
+        return;
+    }
+    events.push_back(event);
+    while events.len() > CAPACITY {
+        events.pop_front();
+    }
+    if let Err(e) = save(&events) {
+        eprintln!("캡처 기록 저장 실패 ({}): {}", LAST_EVENTS_FILE, e);
+    }
+    save_sources(true);
+}
+
+pub fn install_panic_hook() {
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default(info);
+        let Ok(events) = EVENTS.try_lock() else {
+            return;
+        };
+        let _ = save(&events);
+        eprintln!(
+            "최근 캡처 기록 ({}개, 전체는 {}):",
+            events.len().min(PANIC_DUMP),
+            LAST_EVENTS_FILE
+        );
+        for event in events.iter().skip(events.len().saturating_sub(PANIC_DUMP)) {
+            eprintln!("{}", event.line());
+        }
+    }));
+}
+
+pub fn load_events() -> Result<Vec<RawEvent>> {
+    match fs::read_to_string(LAST_EVENTS_FILE) {
+        Ok(text) => Ok(serde_json::from_str(&text)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn print_last_events(limit: usize) -> Result<()> {
+    let events = load_events()?;
+    if events.is_empty() {
+        println!("캡처 기록이 없습니다 ({})", LAST_EVENTS_FILE);
+    }
+    for event in events.iter().skip(events.len().saturating_sub(limit)) {
+        println!("{}", event.line());
+    }
+    Ok(())
+}
+
+pub fn print_sources() -> Result<()> {
+    let counts: Vec<SourceCount> = match fs::read_to_string(SOURCES_FILE) {
+        Ok(text) => serde_json::from_str(&text)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let total: u64 = counts.iter().map(|count| count.count).sum();
+    if total == 0 {
+        println!("창 찾기 기록이 없습니다 ({})", SOURCES_FILE);
+        return Ok(());
+    }
+    for count in &counts {
+        println!(
+            "{} {:>8} ({:.1}%)",
+            truncate_or_pad(&count.source, 32),
+            count.count,
+            count.count as f64 / total as f64 * 100.0
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::Local;
+
+use crate::config::{Language, parse_hours};
+use crate::format::format_duration;
+use crate::store::{Annotation, Interval, Query, Result, Store};
+
+pub const ESTIMATE_KIND: &str = "estimate";
+const LOOKBACK_DAYS: u64 = 90;
+
+pub struct Comparison {
+    pub tag: String,
+    pub estimate: Option<Duration>,
+    pub actual: Duration,
+}
+
+impl Comparison {
+    pub fn line(&self, language: Language) -> String {
+        let Some(estimate) = self.estimate else {
+            return format!(
+                "{}: {} {} ({})",
+                self.tag,
+                language.pick("실제", "actual"),
+                format_duration(&self.actual),
+                language.pick("예상 없음", "no estimate")
+            );
+        };
+        let percent = self.actual.as_secs_f64() / estimate.as_secs_f64().max(1.0) * 100.0;
+        let difference = if self.actual > estimate {
+            format!(
+                "{} {}",
+                format_duration(&(self.actual - estimate)),
+                language.pick("초과", "over")
+            )
+        } else {
+            format!(
+                "{} {}",
+                format_duration(&(estimate - self.actual)),
+                language.pick("남음", "left")
+            )
+        };
+        format!(
+            "{}: {} {} / {} {} ({:.0}%, {})",
+            self.tag,
+            language.pick("실제", "actual"),
+            format_duration(&self.actual),
+            language.pick("예상", "estimate"),
+            format_duration(&estimate),
+            percent,
+            difference
+        )
+    }
+}
+
+fn format_estimate(duration: Duration) -> String {
+    let minutes = duration.as_secs().div_ceil(60);
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h{}m", hours, minutes),
+    }
+}
+
+pub fn record(store: &mut dyn Store, tag: &str, estimate: Duration) -> Result<()> {
+    store.append_annotation(&Annotation {
+        time: Local::now(),
+        kind: ESTIMATE_KIND.to_string(),
+        text: format!("{} {}", tag, format_estimate(estimate)),
+    })
+}
+
+pub fn estimates(store: &dyn Store) -> Result<BTreeMap<String, Duration>> {
+    let mut estimates = BTreeMap::new();
+    for annotation in store.annotations(&Query::default())? {
+        if annotation.kind != ESTIMATE_KIND {
+            continue;
+        }
+        let Some((tag, amount)) = annotation.text.rsplit_once(' ') else {
+            continue;
+        };
+        if let Ok(hours) = parse_hours(amount) {
+            estimates.insert(tag.to_string(), Duration::from_secs_f64(hours * 3600.0));
+        }
+    }
+    Ok(estimates)
+}
+
+pub fn compare(store: &dyn Store, tags: &[String]) -> Result<Vec<Comparison>> {
+    let estimates = estimates(store)?;
+    let history = store.query(&Query::last_days(LOOKBACK_DAYS))?;
+    Ok(tags
+        .iter()
+        .map(|tag| Comparison {
+            tag: tag.clone(),
+            estimate: estimates.get(tag).copied(),
+            actual: history
+                .iter()
+                .filter(|interval| interval.tag.as_deref() == Some(tag.as_str()))
+                .map(Interval::duration)
+                .sum(),
+        })
+        .collect())
+}
+
+pub fn tags_in(store: &dyn Store, query: &Query) -> Result<Vec<String>> {
+    let mut tags: Vec<String> = store
+        .query(query)?
+        .into_iter()
+        .filter_map(|interval| interval.tag)
+        .collect();
+    tags.sort();
+    tags.dedup();
+    Ok(tags)
+}
@@ -0,0 +1,32 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Receiver;
+use std::thread;
+
+use crate::control::{Control, Event};
+use crate::exporter::event_record;
+use crate::store::Result;
+
+fn append(path: &Path, event: &Event) -> Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(&event_record(event))?)?;
+    file.flush()?;
+    Ok(())
+}
+
+fn run(path: PathBuf, events: Receiver<Event>) {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        let _ = fs::create_dir_all(dir);
+    }
+    for event in events {
+        if let Err(e) = append(&path, &event) {
+            eprintln!("이벤트 기록 실패 ({}): {}", path.display(), e);
+        }
+    }
+}
+
+pub fn spawn(path: PathBuf, control: &Control) {
+    let events = control.subscribe();
+    thread::spawn(move || run(path, events));
+}
@@ -0,0 +1,186 @@
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+
+use crate::billing;
+use crate::config::Config;
+use crate::debug;
+use crate::format::format_duration;
+use crate::gaps::HEARTBEAT_KIND;
+use crate::goals;
+use crate::idle::{
+    DISPLAY_REQUIRED_LABEL, IDLE_LABEL, LOCKED_LABEL, SCREENSAVER_LABEL, SLEEP_LABEL,
+    idle_threshold,
+};
+use crate::import::parse_time;
+use crate::remote::REMOTE_LABEL;
+use crate::report::{app_name, category};
+use crate::store::{Query, Result, Store};
+use crate::tracker::{OFF_HOURS_LABEL, OTHER_LABEL};
+use crate::window::DESKTOP_LABEL;
+
+const CONTEXT: Duration = Duration::minutes(5);
+
+pub fn parse_at(text: &str) -> Result<DateTime<Local>> {
+    for format in ["%H:%M", "%H:%M:%S"] {
+        if let Ok(time) = NaiveTime::parse_from_str(text.trim(), format) {
+            return Local
+                .from_local_datetime(&Local::now().date_naive().and_time(time))
+                .earliest()
+                .ok_or_else(|| format!("존재하지 않는 현지 시각입니다: {}", text).into());
+        }
+    }
+    parse_time(text)
+}
+
+fn special_label(title: &str) -> Option<String> {
+    Some(match title {
+        IDLE_LABEL => format!(
+            "입력이 {}초 넘게 없어 비활성으로 기록했습니다",
+            idle_threshold().as_secs()
+        ),
+        SCREENSAVER_LABEL => "화면 보호기가 실행 중이었습니다".to_string(),
+        LOCKED_LABEL => "화면이 잠겨 있었습니다 (Win+L 등)".to_string(),
+        SLEEP_LABEL => "PC가 절전 또는 최대 절전 모드였습니다".to_string(),
+        DISPLAY_REQUIRED_LABEL => {
+            "입력은 없었지만 프로그램이 화면 유지를 요청했습니다 (동영상 재생 등)".to_string()
+        }
+        OFF_HOURS_LABEL => "설정의 off_hours 범위라 창을 기록하지 않았습니다".to_string(),
+        REMOTE_LABEL => "원격 지원 도구가 이 PC를 제어하고 있었습니다".to_string(),
+        DESKTOP_LABEL => "바탕 화면이나 작업 표시줄이 전경 창이었습니다".to_string(),
+        OTHER_LABEL => "--only 정규식과 일치하지 않아 제목을 숨겼습니다".to_string(),
+        _ => return None,
+    })
+}
+
+fn matching_pattern<'a>(patterns: &'a [String], title: &str) -> Option<&'a String> {
+    let title = title.to_lowercase();
+    patterns
+        .iter()
+        .find(|pattern| title.contains(&pattern.to_lowercase()))
+}
+
+pub fn print_explain(config: &Config, store: &dyn Store, at: DateTime<Local>) -> Result<()> {
+    println!("{} 시점의 기록", at.format("%Y-%m-%d %H:%M:%S"));
+    let query = Query {
+        from: Some(at),
+        to: Some(at + Duration::seconds(1)),
+    };
+    let Some(interval) = store
+        .query(&query)?
+        .into_iter()
+        .find(|interval| interval.start <= at && at < interval.end)
+    else {
+        println!("이 시각에 저장된 구간이 없습니다 (추적 중이 아니었거나 일시 중지 상태였습니다)");
+        return Ok(());
+    };
+
+    println!(
+        "구간: {} ~ {} ({})",
+        interval.start.format("%H:%M:%S"),
+        interval.end.format("%H:%M:%S"),
+        format_duration(&interval.duration())
+    );
+    println!("ID: {}", interval.id);
+    println!("제목: {}", interval.title);
+    println!("앱: {}", app_name(&interval.title));
+    if let Some(exe) = &interval.exe {
+        println!("실행 파일: {}", exe);
+    }
+    if let Some(location) = &interval.location {
+        println!("위치: {}", location);
+    }
+    if let Some(tag) = &interval.tag {
+        println!("태그: {}", tag);
+    }
+    if let Some(reason) = special_label(&interval.title) {
+        println!("특수 라벨: {}", reason);
+    }
+
+    println!();
+    println!("적용된 규칙");
+    let mut classified = false;
+    for rule in &config.categories {
+        match rule.matching(&interval.title, interval.exe.as_deref()) {
+            Some(pattern) if !classified => {
+                classified = true;
+                println!("- 분류 규칙 {}: '{}' 일치 → 분류", rule.name, pattern);
+            }
+            Some(pattern) => println!(
+                "- 분류 규칙 {}: '{}' 일치, 앞선 규칙이 먼저 일치해 분류에는 쓰이지 않음",
+                rule.name, pattern
+            ),
+            None => {}
+        }
+    }
+    for goal in &config.goals {
+        match matching_pattern(&goal.titles, &interval.title) {
+            Some(pattern) if !classified => {
+                classified = true;
+                println!("- 목표 {}: '{}' 포함 → 분류", goal.name, pattern);
+            }
+            Some(pattern) => println!(
+                "- 목표 {}: '{}' 포함, 앞선 규칙이나 목표가 먼저 일치해 분류에는 쓰이지 않음",
+                goal.name, pattern
+            ),
+            None => {}
+        }
+    }
+    println!(
+        "- 분류 결과: {}",
+        category(&interval.title, interval.exe.as_deref(), &config.goals)
+    );
+    for client in &config.billing.clients {
+        if let Some(pattern) = matching_pattern(&client.titles, &interval.title) {
+            let kind = billing::rate_kind(client, at.naive_local());
+            println!(
+                "- 청구 대상 {}: '{}' 포함 → {} 요율 (x{})",
+                client.name,
+                pattern,
+                kind.label(),
+                kind.multiplier(client)
+            );
+        }
+    }
+    if config.switch_debounce_ms > 0 {
+        println!(
+            "- 창 전환 지연: {}ms 이상 머문 창만 전환으로 기록하고, 구간은 그 창이 처음 보인 시각부터 시작합니다",
+            config.switch_debounce_ms
+        );
+    }
+
+    let from = interval.start - CONTEXT;
+    let to = interval.end + CONTEXT;
+    let events: Vec<_> = debug::load_events()?
+        .into_iter()
+        .filter(|event| event.last_seen >= from && event.time <= to)
+        .collect();
+    println!();
+    println!("원시 캡처 ({})", debug::LAST_EVENTS_FILE);
+    if events.is_empty() {
+        println!("- 남아 있는 캡처 기록이 없습니다 (최근 기록만 보관됩니다)");
+    }
+    for event in &events {
+        println!("- {}", event.line());
+    }
+
+    let annotations: Vec<_> = store
+        .annotations(&Query {
+            from: Some(from),
+            to: Some(to),
+        })?
+        .into_iter()
+        .filter(|annotation| annotation.kind != HEARTBEAT_KIND)
+        .collect();
+    if !annotations.is_empty() {
+        println!();
+        println!("주변 기록");
+        for annotation in &annotations {
+            println!(
+                "- [{}] {}: {}",
+                annotation.time.format("%H:%M:%S"),
+                annotation.kind,
+                goals::describe_note(annotation, config.report_language)
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use chrono::{DurationRound, NaiveDate, TimeDelta};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::amend::PRIVATE_TITLE;
+use crate::categories;
+use crate::config::{ExportProfile, Goal};
+use crate::hotkey::MARKER_KIND;
+use crate::ids;
+use crate::report::{app_name, category};
+use crate::store::{Annotation, Interval, Query, Result, Store};
+
+pub const FIELDS: &[&str] = &[
+    "id",
+    "start",
+    "end",
+    "title",
+    "app",
+    "exe",
+    "app_id",
+    "category",
+    "category_id",
+    "location",
+    "duration_secs",
+    "tag",
+    "markers",
+];
+
+pub const PROFILES: &[&str] = &["client", "personal", "research"];
+
+pub const CURSOR_FILE: &str = "export_cursors.json";
+
+type Cursors = BTreeMap<String, BTreeMap<String, String>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+pub struct Filter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub apps: Vec<String>,
+    pub categories: Vec<String>,
+}
+
+impl Filter {
+    fn query(&self) -> Query {
+        Query::between(self.from, self.to)
+    }
+
+    fn matches(&self, interval: &Interval, goals: &[Goal]) -> bool {
+        let contains_any = |patterns: &[String], value: &str| {
+            let value = value.to_lowercase();
+            patterns.is_empty()
+                || patterns
+                    .iter()
+                    .any(|pattern| value.contains(&pattern.to_lowercase()))
+        };
+        contains_any(&self.apps, &app_name(&interval.title))
+            && contains_any(
+                &self.categories,
+                &category(&interval.title, interval.exe.as_deref(), goals),
+            )
+    }
+}
+
+fn field(interval: &Interval, goals: &[Goal], markers: &[Annotation], name: &str) -> Value {
+    match name {
+        "id" => interval.id.to_string().into(),
+        "start" => interval.start.to_rfc3339().into(),
+        "end" => interval.end.to_rfc3339().into(),
+        "title" => interval.title.clone().into(),
+        "app" => app_name(&interval.title).into(),
+        "exe" => interval.exe.clone().into(),
+        "app_id" => ids::app_id(&app_name(&interval.title)).to_string().into(),
+        "category" => category(&interval.title, interval.exe.as_deref(), goals).into(),
+        "category_id" => categories::classify(&interval.title, interval.exe.as_deref())
+            .map(ids::project_id)
+            .or_else(|| {
+                goals
+                    .iter()
+                    .find(|goal| goal.matches(&interval.title))
+                    .map(Goal::id)
+            })
+            .map(|id| id.to_string())
+            .into(),
+        "location" => interval.location.clone().into(),
+        "duration_secs" => interval.duration().as_secs_f64().into(),
+        "tag" => interval.tag.clone().into(),
+        "markers" => markers
+            .iter()
+            .filter(|marker| marker.time >= interval.start && marker.time < interval.end)
+            .map(|marker| marker.text.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+            .into(),
+        _ => Value::Null,
+    }
+}
+
+fn hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn profile_field(
+    interval: &Interval,
+    goals: &[Goal],
+    markers: &[Annotation],
+    name: &str,
+    profile: &ExportProfile,
+) -> Value {
+    let value = field(interval, goals, markers, name);
+    if value.is_null() {
+        return value;
+    }
+    if profile.redact.iter().any(|redacted| redacted == name) {
+        PRIVATE_TITLE.into()
+    } else if profile.pseudonymize.iter().any(|hidden| hidden == name) {
+        hash(&csv_value(&value)).into()
+    } else {
+        value
+    }
+}
+
+fn builtin(name: &str) -> Option<ExportProfile> {
+    let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+    Some(match name {
+        "client" => ExportProfile {
+            fields: names(&["start", "end", "category", "tag", "duration_secs"]),
+            round_minutes: 15,
+            ..Default::default()
+        },
+        "personal" => ExportProfile::default(),
+        "research" => ExportProfile {
+            fields: names(&[
+                "start",
+                "end",
+                "title",
+                "app_id",
+                "category_id",
+                "duration_secs",
+            ]),
+            pseudonymize: names(&["title"]),
+            round_minutes: 5,
+            ..Default::default()
+        },
+        _ => return None,
+    })
+}
+
+pub fn profile(configured: &BTreeMap<String, ExportProfile>, name: &str) -> Result<ExportProfile> {
+    configured
+        .get(name)
+        .cloned()
+        .or_else(|| builtin(name))
+        .ok_or_else(|| {
+            let mut names: Vec<&str> = PROFILES.to_vec();
+            names.extend(configured.keys().map(String::as_str));
+            names.sort();
+            names.dedup();
+            format!(
+                "알 수 없는 내보내기 프로필: {} (가능한 프로필: {})",
+                name,
+                names.join(",")
+            )
+            .into()
+        })
+}
+
+fn round(interval: &Interval, minutes: u64) -> Interval {
+    let mut rounded = interval.clone();
+    if minutes > 0 {
+        let step = TimeDelta::minutes(minutes as i64);
+        rounded.start = interval
+            .start
+            .duration_round(step)
+            .unwrap_or(interval.start);
+        rounded.end = interval.end.duration_round(step).unwrap_or(interval.end);
+    }
+    rounded
+}
+
+fn fingerprint(interval: &Interval) -> String {
+    hash(&format!(
+        "{}|{}|{}|{}",
+        interval.start.to_rfc3339(),
+        interval.end.to_rfc3339(),
+        interval.title,
+        interval.location.as_deref().unwrap_or_default()
+    ))
+}
+
+fn load_cursors() -> Cursors {
+    fs::read_to_string(CURSOR_FILE)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_cursors(cursors: &Cursors) -> Result<()> {
+    fs::write(CURSOR_FILE, serde_json::to_string_pretty(cursors)?)?;
+    Ok(())
+}
+
+pub fn csv_value(value: &Value) -> String {
+    let text = match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text
+    }
+}
+
+pub fn export(
+    store: &dyn Store,
+    goals: &[Goal],
+    filter: &Filter,
+    profile: &ExportProfile,
+    format: Format,
+    output: Option<&Path>,
+    since_last: bool,
+) -> Result<usize> {
+    let unknown = profile
+        .fields
+        .iter()
+        .chain(&profile.redact)
+        .chain(&profile.pseudonymize)
+        .find(|name| !FIELDS.contains(&name.as_str()));
+    if let Some(unknown) = unknown {
+        return Err(format!(
+            "알 수 없는 필드: {} (가능한 필드: {})",
+            unknown,
+            FIELDS.join(",")
+        )
+        .into());
+    }
+    let fields: Vec<&str> = if profile.fields.is_empty() {
+        FIELDS.to_vec()
+    } else {
+        profile.fields.iter().map(String::as_str).collect()
+    };
+
+    let destination = output
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let mut cursors = load_cursors();
+    let cursor = cursors.entry(destination).or_default();
+    let intervals: Vec<Interval> = store
+        .query(&filter.query())?
+        .into_iter()
+        .filter(|interval| filter.matches(interval, goals))
+        .filter(|interval| {
+            !since_last || cursor.get(&interval.id.to_string()) != Some(&fingerprint(interval))
+        })
+        .collect();
+    let rows: Vec<Interval> = intervals
+        .iter()
+        .map(|interval| round(interval, profile.round_minutes))
+        .filter(|interval| interval.start < interval.end)
+        .collect();
+    let markers: Vec<Annotation> = if fields.contains(&"markers") {
+        store
+            .annotations(&filter.query())?
+            .into_iter()
+            .filter(|annotation| annotation.kind == MARKER_KIND)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout()),
+    };
+    match format {
+        Format::Csv => {
+            writeln!(out, "{}", fields.join(","))?;
+            for interval in &rows {
+                let row: Vec<String> = fields
+                    .iter()
+                    .map(|name| csv_value(&profile_field(interval, goals, &markers, name, profile)))
+                    .collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+        Format::Json => {
+            let records: Vec<Value> = rows
+                .iter()
+                .map(|interval| {
+                    let record: Map<String, Value> = fields
+                        .iter()
+                        .map(|name| {
+                            (
+                                name.to_string(),
+                                profile_field(interval, goals, &markers, name, profile),
+                            )
+                        })
+                        .collect();
+                    Value::Object(record)
+                })
+                .collect();
+            serde_json::to_writer_pretty(&mut out, &records)?;
+            writeln!(out)?;
+        }
+    }
+    out.flush()?;
+
+    for interval in &intervals {
+        cursor.insert(interval.id.to_string(), fingerprint(interval));
+    }
+    save_cursors(&cursors)?;
+    Ok(rows.len())
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use ureq::Agent;
+
+use super::{Sink, deliver_all};
+use crate::store::Result;
+
+pub struct ActivitywatchSink {
+    agent: Agent,
+    bucket_url: String,
+    hostname: String,
+    bucket_ready: bool,
+}
+
+impl ActivitywatchSink {
+    pub fn new(url: &str, bucket: Option<&str>) -> ActivitywatchSink {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(10)))
+            .build()
+            .into();
+        let hostname = std::env::var("COMPUTERNAME").unwrap_or_else(|_| "unknown".to_string());
+        let bucket = bucket
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("window-change-detector_{}", hostname));
+        ActivitywatchSink {
+            agent,
+            bucket_url: format!("{}/api/0/buckets/{}", url.trim_end_matches('/'), bucket),
+            hostname,
+            bucket_ready: false,
+        }
+    }
+
+    fn ensure_bucket(&mut self) -> Result<()> {
+        if self.bucket_ready {
+            return Ok(());
+        }
+        let bucket = json!({
+            "client": "window-change-detector",
+            "type": "currentwindow",
+            "hostname": self.hostname,
+        });
+        match self.agent.post(&self.bucket_url).send_json(&bucket) {
+            Ok(_) | Err(ureq::Error::StatusCode(304)) => {}
+            Err(e) => return Err(e.into()),
+        }
+        self.bucket_ready = true;
+        Ok(())
+    }
+
+    fn send(&mut self, batch: &[Value]) -> Result<()> {
+        self.ensure_bucket()?;
+        let events: Vec<Value> = batch
+            .iter()
+            .map(|record| {
+                json!({
+                    "timestamp": record["start"],
+                    "duration": record["duration_secs"],
+                    "data": { "title": record["title"], "app": "" },
+                })
+            })
+            .collect();
+        self.agent
+            .post(&format!("{}/events", self.bucket_url))
+            .send_json(&events)?;
+        Ok(())
+    }
+}
+
+impl Sink for ActivitywatchSink {
+    fn name(&self) -> &str {
+        "activitywatch"
+    }
+
+    fn deliver(&mut self, batch: &[Value]) -> (usize, Result<()>) {
+        let sent = self.send(batch);
+        deliver_all(batch, sent)
+    }
+}
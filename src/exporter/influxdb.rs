@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use chrono::DateTime;
+use serde_json::Value;
+use ureq::Agent;
+
+use super::{Sink, deliver_all};
+use crate::store::Result;
+
+pub struct InfluxdbSink {
+    agent: Agent,
+    url: String,
+    token: String,
+}
+
+fn escape_tag(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ")
+}
+
+fn escape_field(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn line_protocol(record: &Value) -> Option<String> {
+    let time = DateTime::parse_from_rfc3339(record["time"].as_str()?).ok()?;
+    let mut fields = format!(
+        "title=\"{}\"",
+        escape_field(record["title"].as_str().unwrap_or_default())
+    );
+    if let Some(duration) = record["duration_secs"].as_f64() {
+        fields.push_str(&format!(",duration_secs={}", duration));
+    }
+    Some(format!(
+        "window_event,kind={} {} {}",
+        escape_tag(record["kind"].as_str().unwrap_or_default()),
+        fields,
+        time.timestamp_millis()
+    ))
+}
+
+impl InfluxdbSink {
+    pub fn new(url: &str, org: &str, bucket: &str, token: &str) -> InfluxdbSink {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(10)))
+            .build()
+            .into();
+        InfluxdbSink {
+            agent,
+            url: format!(
+                "{}/api/v2/write?org={}&bucket={}&precision=ms",
+                url.trim_end_matches('/'),
+                org,
+                bucket
+            ),
+            token: token.to_string(),
+        }
+    }
+
+    fn send(&mut self, batch: &[Value]) -> Result<()> {
+        let body = batch
+            .iter()
+            .filter_map(line_protocol)
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.agent
+            .post(&self.url)
+            .header("Authorization", &format!("Token {}", self.token))
+            .content_type("text/plain; charset=utf-8")
+            .send(body.as_str())?;
+        Ok(())
+    }
+}
+
+impl Sink for InfluxdbSink {
+    fn name(&self) -> &str {
+        "influxdb"
+    }
+
+    fn deliver(&mut self, batch: &[Value]) -> (usize, Result<()>) {
+        let sent = self.send(batch);
+        deliver_all(batch, sent)
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use chrono::DateTime;
+use serde_json::{Value, json};
+use ureq::Agent;
+
+use super::{Sink, deliver_each, is_pseudo_interval};
+use crate::store::Result;
+
+const MIN_WORKLOG_SECS: f64 = 60.0;
+
+pub struct JiraSink {
+    agent: Agent,
+    url: String,
+    authorization: String,
+    title_contains: Option<String>,
+}
+
+impl JiraSink {
+    pub fn new(
+        url: &str,
+        email: &str,
+        api_token: &str,
+        issue: &str,
+        title_contains: Option<String>,
+    ) -> JiraSink {
+        use base64::Engine;
+
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(10)))
+            .build()
+            .into();
+        let credentials =
+            base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", email, api_token));
+        JiraSink {
+            agent,
+            url: format!(
+                "{}/rest/api/3/issue/{}/worklog",
+                url.trim_end_matches('/'),
+                issue
+            ),
+            authorization: format!("Basic {}", credentials),
+            title_contains,
+        }
+    }
+}
+
+impl Sink for JiraSink {
+    fn name(&self) -> &str {
+        "jira"
+    }
+
+    fn deliver(&mut self, batch: &[Value]) -> (usize, Result<()>) {
+        deliver_each(batch, |record| {
+            let title = record["title"].as_str().unwrap_or_default();
+            let seconds = record["duration_secs"].as_f64().unwrap_or_default();
+            if seconds < MIN_WORKLOG_SECS
+                || is_pseudo_interval(record)
+                || self
+                    .title_contains
+                    .as_ref()
+                    .is_some_and(|text| !title.contains(text.as_str()))
+            {
+                return Ok(());
+            }
+            let Some(start) = record["start"]
+                .as_str()
+                .and_then(|start| DateTime::parse_from_rfc3339(start).ok())
+            else {
+                return Ok(());
+            };
+            let worklog = json!({
+                "started": start.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string(),
+                "timeSpentSeconds": seconds.round() as i64,
+                "comment": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{
+                        "type": "paragraph",
+                        "content": [{ "type": "text", "text": title }],
+                    }],
+                },
+            });
+            self.agent
+                .post(&self.url)
+                .header("Authorization", &self.authorization)
+                .send_json(&worklog)?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,437 @@
+mod activitywatch;
+mod influxdb;
+mod jira;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+mod toggl;
+mod webhook;
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use crate::config::{ExportQueueConfig, ExporterConfig};
+use crate::control::{Control, Event};
+use crate::store::{Interval, Result};
+use crate::tracker::PSEUDO_LABELS;
+
+const MAX_BACKOFF: Duration = Duration::from_secs(300);
+
+pub trait Sink: Send {
+    fn name(&self) -> &str;
+
+    fn deliver(&mut self, batch: &[Value]) -> (usize, Result<()>);
+}
+
+fn deliver_all(batch: &[Value], sent: Result<()>) -> (usize, Result<()>) {
+    match sent {
+        Ok(()) => (batch.len(), Ok(())),
+        Err(e) => (0, Err(e)),
+    }
+}
+
+fn deliver_each(
+    batch: &[Value],
+    mut send: impl FnMut(&Value) -> Result<()>,
+) -> (usize, Result<()>) {
+    for (sent, record) in batch.iter().enumerate() {
+        if let Err(e) = send(record) {
+            return (sent, Err(e));
+        }
+    }
+    (batch.len(), Ok(()))
+}
+
+fn is_pseudo_interval(record: &Value) -> bool {
+    PSEUDO_LABELS.contains(&record["title"].as_str().unwrap_or_default())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Events,
+    Intervals,
+}
+
+impl ExporterConfig {
+    fn kind(&self) -> &'static str {
+        match self {
+            ExporterConfig::Webhook { .. } => "webhook",
+            ExporterConfig::Influxdb { .. } => "influxdb",
+            ExporterConfig::Mqtt { .. } => "mqtt",
+            ExporterConfig::Toggl { .. } => "toggl",
+            ExporterConfig::Jira { .. } => "jira",
+            ExporterConfig::Activitywatch { .. } => "activitywatch",
+        }
+    }
+
+    fn target(&self) -> String {
+        match self {
+            ExporterConfig::Webhook { url, .. } => url.clone(),
+            ExporterConfig::Influxdb {
+                url, org, bucket, ..
+            } => format!("{} {} {}", url, org, bucket),
+            ExporterConfig::Mqtt { host, port, topic } => format!("{}:{} {}", host, port, topic),
+            ExporterConfig::Toggl { workspace_id, .. } => workspace_id.to_string(),
+            ExporterConfig::Jira { url, issue, .. } => format!("{} {}", url, issue),
+            ExporterConfig::Activitywatch { url, bucket } => {
+                format!("{} {}", url, bucket.as_deref().unwrap_or_default())
+            }
+        }
+    }
+
+    fn source(&self) -> Source {
+        match self {
+            ExporterConfig::Webhook { .. }
+            | ExporterConfig::Influxdb { .. }
+            | ExporterConfig::Mqtt { .. } => Source::Events,
+            ExporterConfig::Toggl { .. }
+            | ExporterConfig::Jira { .. }
+            | ExporterConfig::Activitywatch { .. } => Source::Intervals,
+        }
+    }
+}
+
+pub fn event_record(event: &Event) -> Value {
+    json!({
+        "time": event.time.to_rfc3339(),
+        "kind": event.kind.as_str(),
+        "title": event.title,
+        "exe": event.exe,
+        "duration_secs": event.duration.map(|duration| duration.as_secs_f64()),
+    })
+}
+
+pub fn interval_record(interval: &Interval) -> Value {
+    json!({
+        "id": interval.id.to_string(),
+        "start": interval.start.to_rfc3339(),
+        "end": interval.end.to_rfc3339(),
+        "title": interval.title,
+        "duration_secs": interval.duration().as_secs_f64(),
+        "location": interval.location,
+        "tag": interval.tag,
+        "exe": interval.exe,
+    })
+}
+
+fn read_spool(path: &Path) -> Vec<Value> {
+    File::open(path)
+        .map(|file| {
+            BufReader::new(file)
+                .lines()
+                .map_while(|line| line.ok())
+                .filter_map(|line| serde_json::from_str(&line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn write_spool<'a>(path: &Path, records: impl IntoIterator<Item = &'a Value>) {
+    let temp = path.with_extension("jsonl.tmp");
+    let written = File::create(&temp)
+        .and_then(|mut file| {
+            for record in records {
+                writeln!(file, "{}", record)?;
+            }
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp, path));
+    if let Err(e) = written {
+        eprintln!("내보내기 대기열 기록 실패 ({}): {}", path.display(), e);
+    }
+}
+
+struct Queue {
+    durable: bool,
+    memory: VecDeque<Value>,
+    max_memory: usize,
+    spool_path: PathBuf,
+    spooled: usize,
+}
+
+impl Queue {
+    fn open(spool_path: PathBuf, max_memory: usize, durable: bool) -> Queue {
+        let spooled = read_spool(&spool_path).len();
+        Queue {
+            durable,
+            memory: VecDeque::new(),
+            max_memory,
+            spool_path,
+            spooled,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.memory.len() + self.spooled
+    }
+
+    fn push(&mut self, record: Value) {
+        if !self.durable && self.spooled == 0 && self.memory.len() < self.max_memory {
+            self.memory.push_back(record);
+            return;
+        }
+        let spooled = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.spool_path)
+            .and_then(|mut file| writeln!(file, "{}", record));
+        match spooled {
+            Ok(()) => self.spooled += 1,
+            Err(e) => eprintln!(
+                "내보내기 대기열 기록 실패 ({}): {}",
+                self.spool_path.display(),
+                e
+            ),
+        }
+    }
+
+    fn batch(&self, size: usize) -> Vec<Value> {
+        if self.memory.is_empty() {
+            return read_spool(&self.spool_path)
+                .into_iter()
+                .take(size)
+                .collect();
+        }
+        self.memory.iter().take(size).cloned().collect()
+    }
+
+    fn acknowledge(&mut self, count: usize) {
+        if count == 0 {
+            return;
+        }
+        if !self.memory.is_empty() {
+            self.memory.drain(..count.min(self.memory.len()));
+            return;
+        }
+        let records = read_spool(&self.spool_path);
+        write_spool(&self.spool_path, records.iter().skip(count));
+        self.spooled = records.len().saturating_sub(count);
+    }
+}
+
+struct Pipeline {
+    sink: Box<dyn Sink>,
+    queue: Queue,
+    settings: ExportQueueConfig,
+    backoff: Duration,
+    next_attempt: Instant,
+    last_flush: Instant,
+}
+
+impl Pipeline {
+    fn run<T>(mut self, records: Receiver<T>, to_record: fn(&T) -> Value) {
+        let flush_interval = Duration::from_secs(self.settings.flush_interval_secs);
+        loop {
+            match records.recv_timeout(Duration::from_millis(500)) {
+                Ok(record) => self.queue.push(to_record(&record)),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            let now = Instant::now();
+            let due = self.queue.len() >= self.settings.batch_size
+                || (self.queue.len() > 0 && now - self.last_flush >= flush_interval);
+            if due && now >= self.next_attempt {
+                self.flush(now);
+            }
+        }
+    }
+
+    fn flush(&mut self, now: Instant) {
+        let batch = self.queue.batch(self.settings.batch_size);
+        if batch.is_empty() {
+            return;
+        }
+        let (sent, result) = self.sink.deliver(&batch);
+        self.queue.acknowledge(sent);
+        match result {
+            Ok(()) => {
+                self.backoff = Duration::from_secs(1);
+                self.last_flush = now;
+                self.next_attempt = now + Duration::from_millis(self.settings.min_interval_ms);
+            }
+            Err(e) => {
+                eprintln!(
+                    "{} 내보내기 실패 ({}초 후 재시도): {}",
+                    self.sink.name(),
+                    self.backoff.as_secs(),
+                    e
+                );
+                self.next_attempt = now + self.backoff;
+                self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+fn open_sink(config: &ExporterConfig) -> Result<Box<dyn Sink>> {
+    Ok(match config {
+        ExporterConfig::Webhook { url, headers } => {
+            Box::new(webhook::WebhookSink::new(url, headers.clone()))
+        }
+        ExporterConfig::Influxdb {
+            url,
+            org,
+            bucket,
+            token,
+        } => Box::new(influxdb::InfluxdbSink::new(url, org, bucket, token)),
+        #[cfg(feature = "mqtt")]
+        ExporterConfig::Mqtt { host, port, topic } => {
+            Box::new(mqtt::MqttSink::new(host, *port, topic))
+        }
+        #[cfg(not(feature = "mqtt"))]
+        ExporterConfig::Mqtt { .. } => {
+            return Err("mqtt 기능 없이 빌드되었습니다 (--features mqtt)".into());
+        }
+        ExporterConfig::Toggl {
+            api_token,
+            workspace_id,
+        } => Box::new(toggl::TogglSink::new(api_token, *workspace_id)),
+        ExporterConfig::Jira {
+            url,
+            email,
+            api_token,
+            issue,
+            title_contains,
+        } => Box::new(jira::JiraSink::new(
+            url,
+            email,
+            api_token,
+            issue,
+            title_contains.clone(),
+        )),
+        ExporterConfig::Activitywatch { url, bucket } => Box::new(
+            activitywatch::ActivitywatchSink::new(url, bucket.as_deref()),
+        ),
+    })
+}
+
+fn spool_path(settings: &ExportQueueConfig, config: &ExporterConfig) -> PathBuf {
+    let identity = format!("{} {}", config.kind(), config.target());
+    let hash: String = Sha256::digest(identity.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    settings
+        .spool_dir
+        .join(format!("{}_{}.jsonl", config.kind(), hash))
+}
+
+fn orphaned_spools(
+    exporters: &[ExporterConfig],
+    settings: &ExportQueueConfig,
+) -> Vec<(PathBuf, usize)> {
+    let configured: Vec<PathBuf> = exporters
+        .iter()
+        .map(|config| spool_path(settings, config))
+        .collect();
+    let Ok(entries) = fs::read_dir(&settings.spool_dir) else {
+        return Vec::new();
+    };
+    let mut orphans: Vec<(PathBuf, usize)> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "jsonl")
+        })
+        .filter(|path| !configured.contains(path))
+        .map(|path| {
+            let count = read_spool(&path).len();
+            (path, count)
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    orphans.sort();
+    orphans
+}
+
+pub fn spawn_all(exporters: &[ExporterConfig], settings: &ExportQueueConfig, control: &Control) {
+    if exporters.is_empty() {
+        return;
+    }
+    if let Err(e) = fs::create_dir_all(&settings.spool_dir) {
+        eprintln!(
+            "내보내기 대기열 폴더 생성 실패 ({}): {}",
+            settings.spool_dir.display(),
+            e
+        );
+    }
+    for (path, count) in orphaned_spools(exporters, settings) {
+        eprintln!(
+            "설정에 없는 연동의 대기 항목 {}개가 남아 있습니다 ({}). 연동 설정을 되돌리거나 파일을 지우세요",
+            count,
+            path.display()
+        );
+    }
+    for config in exporters {
+        let sink = match open_sink(config) {
+            Ok(sink) => sink,
+            Err(e) => {
+                eprintln!("내보내기 설정 오류: {}", e);
+                continue;
+            }
+        };
+        let source = config.source();
+        let pipeline = Pipeline {
+            sink,
+            queue: Queue::open(
+                spool_path(settings, config),
+                settings.max_memory,
+                source == Source::Intervals,
+            ),
+            settings: settings.clone(),
+            backoff: Duration::from_secs(1),
+            next_attempt: Instant::now(),
+            last_flush: Instant::now(),
+        };
+        match source {
+            Source::Events => {
+                let events = control.subscribe();
+                thread::spawn(move || pipeline.run(events, event_record));
+            }
+            Source::Intervals => {
+                let intervals = control.subscribe_intervals();
+                thread::spawn(move || pipeline.run(intervals, interval_record));
+            }
+        }
+    }
+}
+
+pub fn print_sync_status(exporters: &[ExporterConfig], settings: &ExportQueueConfig) {
+    if exporters.is_empty() {
+        println!("설정된 외부 연동이 없습니다.");
+        return;
+    }
+    println!("{:<4} {:<15} {:>8}  가장 오래된 항목", "#", "연동", "대기");
+    for (index, config) in exporters.iter().enumerate() {
+        let records = read_spool(&spool_path(settings, config));
+        let oldest = records
+            .first()
+            .and_then(|record| record.get("start").or_else(|| record.get("time")))
+            .and_then(|time| time.as_str())
+            .unwrap_or("-");
+        println!(
+            "{:<4} {:<15} {:>8}  {}",
+            index,
+            config.kind(),
+            records.len(),
+            oldest
+        );
+    }
+    for (path, count) in orphaned_spools(exporters, settings) {
+        println!(
+            "{:<4} {:<15} {:>8}  {}",
+            "-",
+            "(설정에 없음)",
+            count,
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::thread;
+use std::time::Duration;
+
+use rumqttc::{Client, MqttOptions, QoS};
+use serde_json::Value;
+
+use super::{Sink, deliver_each};
+use crate::store::Result;
+
+pub struct MqttSink {
+    client: Client,
+    topic: String,
+}
+
+impl MqttSink {
+    pub fn new(host: &str, port: u16, topic: &str) -> MqttSink {
+        let client_id = format!("window-change-detector-{}", std::process::id());
+        let mut options = MqttOptions::new(client_id, host, port);
+        options.set_keep_alive(Duration::from_secs(30));
+        let (client, mut connection) = Client::new(options, 64);
+        thread::spawn(move || {
+            for notification in connection.iter() {
+                if notification.is_err() {
+                    thread::sleep(Duration::from_secs(5));
+                }
+            }
+        });
+        MqttSink {
+            client,
+            topic: topic.to_string(),
+        }
+    }
+}
+
+impl Sink for MqttSink {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
+    fn deliver(&mut self, batch: &[Value]) -> (usize, Result<()>) {
+        deliver_each(batch, |record| {
+            self.client
+                .try_publish(&self.topic, QoS::AtLeastOnce, false, record.to_string())?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use ureq::Agent;
+
+use super::{Sink, deliver_each, is_pseudo_interval};
+use crate::store::Result;
+
+pub struct TogglSink {
+    agent: Agent,
+    url: String,
+    authorization: String,
+    workspace_id: u64,
+}
+
+impl TogglSink {
+    pub fn new(api_token: &str, workspace_id: u64) -> TogglSink {
+        use base64::Engine;
+
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(10)))
+            .build()
+            .into();
+        let credentials =
+            base64::engine::general_purpose::STANDARD.encode(format!("{}:api_token", api_token));
+        TogglSink {
+            agent,
+            url: format!(
+                "https://api.track.toggl.com/api/v9/workspaces/{}/time_entries",
+                workspace_id
+            ),
+            authorization: format!("Basic {}", credentials),
+            workspace_id,
+        }
+    }
+}
+
+impl Sink for TogglSink {
+    fn name(&self) -> &str {
+        "toggl"
+    }
+
+    fn deliver(&mut self, batch: &[Value]) -> (usize, Result<()>) {
+        deliver_each(batch, |record| {
+            if is_pseudo_interval(record) {
+                return Ok(());
+            }
+            let entry = json!({
+                "description": record["title"],
+                "start": record["start"],
+                "stop": record["end"],
+                "duration": record["duration_secs"].as_f64().unwrap_or_default().round() as i64,
+                "workspace_id": self.workspace_id,
+                "created_with": "window-change-detector",
+            });
+            self.agent
+                .post(&self.url)
+                .header("Authorization", &self.authorization)
+                .send_json(&entry)?;
+            Ok(())
+        })
+    }
+}
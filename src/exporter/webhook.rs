@@ -0,0 +1,48 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde_json::Value;
+use ureq::Agent;
+
+use super::{Sink, deliver_all};
+use crate::store::Result;
+
+pub struct WebhookSink {
+    agent: Agent,
+    url: String,
+    headers: BTreeMap<String, String>,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str, headers: BTreeMap<String, String>) -> WebhookSink {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(10)))
+            .build()
+            .into();
+        WebhookSink {
+            agent,
+            url: url.to_string(),
+            headers,
+        }
+    }
+
+    fn send(&mut self, batch: &[Value]) -> Result<()> {
+        let mut request = self.agent.post(&self.url);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        request.send_json(batch)?;
+        Ok(())
+    }
+}
+
+impl Sink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn deliver(&mut self, batch: &[Value]) -> (usize, Result<()>) {
+        let sent = self.send(batch);
+        deliver_all(batch, sent)
+    }
+}
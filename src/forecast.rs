@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, Weekday};
+
+use crate::config::Goal;
+use crate::stats;
+use crate::store::{Interval, Query, Result, Store};
+
+const LOOKBACK_DAYS: u64 = 8 * 7;
+
+pub struct Forecast {
+    pub name: String,
+    pub weekday: Weekday,
+    pub typical: Duration,
+    pub today: Duration,
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "월요일",
+        Weekday::Tue => "화요일",
+        Weekday::Wed => "수요일",
+        Weekday::Thu => "목요일",
+        Weekday::Fri => "금요일",
+        Weekday::Sat => "토요일",
+        Weekday::Sun => "일요일",
+    }
+}
+
+fn hours(duration: &Duration) -> f64 {
+    duration.as_secs_f64() / 3600.0
+}
+
+fn forecast(
+    name: &str,
+    intervals: &[Interval],
+    tracked_days: &[NaiveDate],
+    today: NaiveDate,
+    matches: impl Fn(&Interval) -> bool,
+) -> Option<Forecast> {
+    let totals = stats::totals_by_day(intervals, matches);
+    let past: Vec<Duration> = tracked_days
+        .iter()
+        .filter(|day| **day != today && day.weekday() == today.weekday())
+        .map(|day| totals.get(day).copied().unwrap_or_default())
+        .collect();
+    if past.is_empty() {
+        return None;
+    }
+    Some(Forecast {
+        name: name.to_string(),
+        weekday: today.weekday(),
+        typical: past.iter().sum::<Duration>() / past.len() as u32,
+        today: totals.get(&today).copied().unwrap_or_default(),
+    })
+}
+
+pub fn compute(goals: &[Goal], intervals: &[Interval], now: DateTime<Local>) -> Vec<Forecast> {
+    let today = now.date_naive();
+    let mut tracked_days: Vec<NaiveDate> = intervals
+        .iter()
+        .map(|interval| interval.start.date_naive())
+        .collect();
+    tracked_days.sort();
+    tracked_days.dedup();
+
+    let mut forecasts: Vec<Forecast> =
+        forecast("집중 시간", intervals, &tracked_days, today, |i| {
+            stats::is_focus(&i.title)
+        })
+        .into_iter()
+        .collect();
+    for goal in goals.iter().filter(|goal| goal.min_hours.is_some()) {
+        forecasts.extend(forecast(
+            &goal.name,
+            intervals,
+            &tracked_days,
+            today,
+            |interval| goal.matches(&interval.title),
+        ));
+    }
+    forecasts
+}
+
+impl Forecast {
+    pub fn line(&self) -> String {
+        format!(
+            "{}에는 보통 {} {:.1}시간, 오늘은 지금까지 {:.1}시간",
+            weekday_name(self.weekday),
+            self.name,
+            hours(&self.typical),
+            hours(&self.today)
+        )
+    }
+}
+
+pub fn lines(goals: &[Goal], store: &dyn Store) -> Result<Vec<String>> {
+    let history = store.query(&Query::last_days(LOOKBACK_DAYS))?;
+    Ok(compute(goals, &history, Local::now())
+        .iter()
+        .map(Forecast::line)
+        .collect())
+}
+
+pub fn print_forecast(goals: &[Goal], store: &dyn Store) -> Result<()> {
+    let lines = lines(goals, store)?;
+    if !lines.is_empty() {
+        println!();
+        println!("요일별 예상");
+        for line in &lines {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::config::{Language, OutputProfile};
+
+static OUTPUT_PROFILE: OnceLock<OutputProfile> = OnceLock::new();
+static TITLE_WIDTH: OnceLock<usize> = OnceLock::new();
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    Warning,
+    Active,
+    OffHoursStart,
+    OffHoursEnd,
+    Paused,
+    Resumed,
+    Network,
+    VpnOn,
+    VpnOff,
+    Location,
+    LimitExceeded,
+    Trophy,
+    Target,
+    Streak,
+    ShortStreak,
+    DeepFocus,
+    Focus,
+    WithinLimit,
+    Marker,
+    Timer,
+    Remote,
+    Sleep,
+}
+
+pub fn set_output_profile(profile: OutputProfile) {
+    let _ = OUTPUT_PROFILE.set(profile);
+}
+
+pub fn set_language(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
+
+pub fn is_a11y() -> bool {
+    OUTPUT_PROFILE.get() == Some(&OutputProfile::A11y)
+}
+
+pub fn set_title_width(width: usize) {
+    let _ = TITLE_WIDTH.set(width);
+}
+
+pub fn title_width() -> usize {
+    TITLE_WIDTH.get().copied().unwrap_or(40)
+}
+
+pub fn icon(icon: Icon) -> &'static str {
+    match OUTPUT_PROFILE.get().copied().unwrap_or_default() {
+        OutputProfile::Emoji => match icon {
+            Icon::Warning => "⚠️ ",
+            Icon::Active => "✅ ",
+            Icon::OffHoursStart => "🌙 ",
+            Icon::OffHoursEnd => "☀️ ",
+            Icon::Paused => "⏸️ ",
+            Icon::Resumed => "▶️ ",
+            Icon::Network => "🌐 ",
+            Icon::VpnOn => "🔒 ",
+            Icon::VpnOff => "🔓 ",
+            Icon::Location => "📍 ",
+            Icon::LimitExceeded => "⛔ ",
+            Icon::Trophy => "🏆 ",
+            Icon::Target => "🎯 ",
+            Icon::Streak => "🔥 ",
+            Icon::ShortStreak => "✨ ",
+            Icon::DeepFocus => "🧠 ",
+            Icon::Focus => "⏱️ ",
+            Icon::WithinLimit => "🧘 ",
+            Icon::Marker => "🚩 ",
+            Icon::Timer => "⏳ ",
+            Icon::Remote => "🖥️ ",
+            Icon::Sleep => "💤 ",
+        },
+        OutputProfile::Ascii => match icon {
+            Icon::Warning => "[!] ",
+            Icon::Active => "[v] ",
+            Icon::OffHoursStart => "[off] ",
+            Icon::OffHoursEnd => "[on] ",
+            Icon::Paused => "[||] ",
+            Icon::Resumed => "[>] ",
+            Icon::Network => "[net] ",
+            Icon::VpnOn => "[vpn+] ",
+            Icon::VpnOff => "[vpn-] ",
+            Icon::Location => "[@] ",
+            Icon::LimitExceeded => "[x] ",
+            Icon::Trophy => "[*] ",
+            Icon::Target => "[o] ",
+            Icon::Streak => "[+] ",
+            Icon::ShortStreak => "[+] ",
+            Icon::DeepFocus => "[#] ",
+            Icon::Focus => "[#] ",
+            Icon::WithinLimit => "[=] ",
+            Icon::Marker => "[>>] ",
+            Icon::Timer => "[t] ",
+            Icon::Remote => "[rc] ",
+            Icon::Sleep => "[zz] ",
+        },
+        OutputProfile::Plain | OutputProfile::A11y => "",
+    }
+}
+
+pub fn shade(level: usize) -> char {
+    let shades = match OUTPUT_PROFILE.get().copied().unwrap_or_default() {
+        OutputProfile::Emoji => [' ', '░', '▒', '▓', '█'],
+        OutputProfile::Ascii | OutputProfile::Plain | OutputProfile::A11y => {
+            [' ', '.', ':', '*', '#']
+        }
+    };
+    shades[level.min(shades.len() - 1)]
+}
+
+pub fn clean_window_title(title: &str) -> String {
+    if title.contains('\\') || title.contains('/') {
+        std::path::Path::new(title)
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| title.to_string())
+    } else {
+        title.to_string()
+    }
+}
+
+pub fn format_duration(duration: &Duration) -> String {
+    if is_a11y() {
+        return LANGUAGE
+            .get()
+            .copied()
+            .unwrap_or_default()
+            .spelled_duration(duration);
+    }
+    let secs = (duration.as_millis() as u64 + 500) / 1000;
+    let hours = secs / 3600;
+    let minutes = (secs % 3600) / 60;
+    let seconds = secs % 60;
+    format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+}
+
+pub fn table_row(title: &str, label: &str, duration: &Duration) -> String {
+    if is_a11y() {
+        return format!(
+            "{}: {} {}",
+            clean_window_title(title),
+            label,
+            format_duration(duration)
+        );
+    }
+    format!(
+        "{} {:>10}",
+        truncate_or_pad(title, title_width()),
+        format_duration(duration)
+    )
+}
+
+pub fn truncate_or_pad(title: &str, max_width: usize) -> String {
+    let clean = clean_window_title(title);
+    let mut current_width = 0;
+    let mut result = String::new();
+
+    for ch in clean.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if current_width + ch_width > max_width {
+            result.push_str("...");
+            break;
+        }
+        current_width += ch_width;
+        result.push(ch);
+    }
+
+    let total_width = UnicodeWidthStr::width(result.as_str());
+    if total_width < max_width {
+        let padding = " ".repeat(max_width - total_width);
+        result.push_str(&padding);
+    }
+
+    result
+}
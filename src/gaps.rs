@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local, TimeZone};
+
+use crate::config::Language;
+use crate::format::format_duration;
+use crate::store::{Annotation, Interval, Query, Result, Store};
+
+pub const BOOT_KIND: &str = "boot";
+pub const LOGON_KIND: &str = "logon";
+pub const UNLOCK_KIND: &str = "unlock";
+pub const WAKE_KIND: &str = "wake";
+pub const HEARTBEAT_KIND: &str = "heartbeat";
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+const GAP_TOLERANCE: Duration = Duration::from_secs(2 * 60);
+
+pub struct Gap {
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub logon: Option<DateTime<Local>>,
+}
+
+impl Gap {
+    pub fn duration(&self) -> Duration {
+        (self.end - self.start).to_std().unwrap_or_default()
+    }
+
+    pub fn logged_off(&self) -> Duration {
+        self.logon
+            .map(|logon| {
+                (logon.min(self.end) - self.start)
+                    .to_std()
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default()
+    }
+}
+
+pub struct Coverage {
+    pub gaps: Vec<Gap>,
+    pub tracked: Duration,
+    pub boot: Option<DateTime<Local>>,
+    pub logon: Option<DateTime<Local>>,
+}
+
+impl Coverage {
+    pub fn untracked(&self) -> Duration {
+        self.gaps.iter().map(|gap| gap.duration()).sum()
+    }
+
+    pub fn percent(&self) -> u32 {
+        let total = (self.tracked + self.untracked()).as_secs_f64();
+        if total == 0.0 {
+            return 100;
+        }
+        (self.tracked.as_secs_f64() / total * 100.0).round() as u32
+    }
+
+    pub fn lines(&self, language: Language) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(boot) = self.boot {
+            lines.push(format!(
+                "{}: {}",
+                language.pick("부팅", "Boot"),
+                language.datetime(boot)
+            ));
+        }
+        if let Some(logon) = self.logon {
+            lines.push(format!(
+                "{}: {}",
+                language.pick("로그온", "Logon"),
+                language.datetime(logon)
+            ));
+        }
+        for gap in &self.gaps {
+            let logon = gap
+                .logon
+                .map(|logon| {
+                    format!(
+                        " ({} {})",
+                        language.pick("로그온", "logon"),
+                        language.time(logon)
+                    )
+                })
+                .unwrap_or_default();
+            lines.push(format!(
+                "{} {} ~ {} {:>10}{}",
+                language.pick("미추적", "Untracked"),
+                language.time(gap.start),
+                language.time(gap.end),
+                format_duration(&gap.duration()),
+                logon
+            ));
+        }
+        let logged_off: Duration = self.gaps.iter().map(|gap| gap.logged_off()).sum();
+        lines.push(format!(
+            "{}: {}",
+            language.pick("추적된 시간", "Tracked"),
+            format_duration(&self.tracked)
+        ));
+        lines.push(format!(
+            "{}: {} ({} {})",
+            language.pick(
+                "켜져 있었지만 추적되지 않은 시간 (추정)",
+                "Machine on but untracked (estimate)"
+            ),
+            format_duration(&self.untracked()),
+            language.pick("로그온 전", "before logon"),
+            format_duration(&logged_off)
+        ));
+        lines.push(format!(
+            "{}: {}%",
+            language.pick("추적률", "Coverage"),
+            self.percent()
+        ));
+        lines
+    }
+}
+
+pub fn is_internal(annotation: &Annotation) -> bool {
+    [
+        BOOT_KIND,
+        LOGON_KIND,
+        UNLOCK_KIND,
+        WAKE_KIND,
+        HEARTBEAT_KIND,
+    ]
+    .contains(&annotation.kind.as_str())
+}
+
+pub fn day_start(now: DateTime<Local>) -> DateTime<Local> {
+    Local
+        .from_local_datetime(&now.date_naive().and_hms_opt(0, 0, 0).unwrap())
+        .earliest()
+        .unwrap_or(now)
+}
+
+pub fn parse_time(text: &str) -> Option<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|time| time.with_timezone(&Local))
+}
+
+pub fn find(annotations: &[Annotation], from: DateTime<Local>, to: DateTime<Local>) -> Vec<Gap> {
+    let mut records: Vec<&Annotation> = annotations.iter().filter(|a| is_internal(a)).collect();
+    records.sort_by_key(|annotation| annotation.time);
+
+    let mut gaps: Vec<Gap> = Vec::new();
+    let mut last_alive: Option<DateTime<Local>> = None;
+    let mut boot_gap = false;
+    for record in records {
+        match record.kind.as_str() {
+            BOOT_KIND => {
+                boot_gap = false;
+                if let Some(boot) = parse_time(&record.text) {
+                    let start = last_alive.map_or(boot, |alive| alive.max(boot)).max(from);
+                    let end = record.time.min(to);
+                    if (end - start).to_std().unwrap_or_default() > GAP_TOLERANCE {
+                        gaps.push(Gap {
+                            start,
+                            end,
+                            logon: None,
+                        });
+                        boot_gap = true;
+                    }
+                }
+            }
+            LOGON_KIND => match (boot_gap, parse_time(&record.text), gaps.last_mut()) {
+                (true, Some(logon), Some(gap)) if gap.start < logon && logon < gap.end => {
+                    gap.logon = Some(logon);
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        last_alive = Some(record.time);
+    }
+    gaps
+}
+
+fn tracked(intervals: &[Interval], from: DateTime<Local>, to: DateTime<Local>) -> Duration {
+    intervals
+        .iter()
+        .map(|interval| {
+            (interval.end.min(to) - interval.start.max(from))
+                .to_std()
+                .unwrap_or_default()
+        })
+        .sum()
+}
+
+pub fn today(store: &dyn Store) -> Result<Coverage> {
+    let now = Local::now();
+    let from = day_start(now);
+    let query = Query {
+        from: Some(from),
+        to: None,
+    };
+    let annotations = store.annotations(&query)?;
+    let latest = |kind: &str| {
+        annotations
+            .iter()
+            .filter(|annotation| annotation.kind == kind)
+            .max_by_key(|annotation| annotation.time)
+            .and_then(|annotation| parse_time(&annotation.text))
+    };
+    Ok(Coverage {
+        gaps: find(&annotations, from, now),
+        tracked: tracked(&store.query(&query)?, from, now),
+        boot: latest(BOOT_KIND),
+        logon: latest(LOGON_KIND),
+    })
+}
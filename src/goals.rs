@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, TimeZone};
+
+use crate::config::{Goal, Language, Period};
+use crate::control::Control;
+use crate::format::{Icon, format_duration, icon, title_width, truncate_or_pad};
+use crate::notify;
+use crate::store::{Annotation, Interval, Query, Result, Store};
+
+pub const LIMIT_KIND: &str = "limit";
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const WARNING_PERCENT: u32 = 80;
+const BAR_WIDTH: usize = 20;
+
+pub struct GoalProgress<'a> {
+    pub goal: &'a Goal,
+    pub spent: Duration,
+}
+
+impl GoalProgress<'_> {
+    pub fn target(&self) -> Duration {
+        let hours = self.goal.max_hours.or(self.goal.min_hours).unwrap_or(0.0);
+        Duration::from_secs_f64(hours.max(0.0) * 3600.0)
+    }
+
+    pub fn percent(&self) -> u32 {
+        let target = self.target().as_secs_f64();
+        if target == 0.0 {
+            return 100;
+        }
+        (self.spent.as_secs_f64() / target * 100.0) as u32
+    }
+
+    pub fn is_limit(&self) -> bool {
+        self.goal.max_hours.is_some()
+    }
+}
+
+fn day_start(date: NaiveDate, now: DateTime<Local>) -> DateTime<Local> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+        .earliest()
+        .unwrap_or(now)
+}
+
+pub fn week_start(now: DateTime<Local>) -> DateTime<Local> {
+    let monday = now.date_naive() - Days::new(now.weekday().num_days_from_monday() as u64);
+    day_start(monday, now)
+}
+
+pub fn month_start(now: DateTime<Local>) -> DateTime<Local> {
+    day_start(now.date_naive().with_day(1).unwrap(), now)
+}
+
+impl Period {
+    pub fn start(self, now: DateTime<Local>) -> DateTime<Local> {
+        match self {
+            Period::Day => day_start(now.date_naive(), now),
+            Period::Week => week_start(now),
+            Period::Month => month_start(now),
+        }
+    }
+
+    pub fn days(self) -> f64 {
+        match self {
+            Period::Day => 1.0,
+            Period::Week => 7.0,
+            Period::Month => 30.0,
+        }
+    }
+
+    pub fn label(self, language: Language) -> &'static str {
+        match self {
+            Period::Day => language.pick("일일", "daily"),
+            Period::Week => language.pick("주간", "weekly"),
+            Period::Month => language.pick("월간", "monthly"),
+        }
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Period::Day => "day",
+            Period::Week => "week",
+            Period::Month => "month",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Period> {
+        match key {
+            "day" => Some(Period::Day),
+            "week" => Some(Period::Week),
+            "month" => Some(Period::Month),
+            _ => None,
+        }
+    }
+}
+
+fn thresholds(goal: &Goal) -> Vec<u32> {
+    if goal.thresholds.is_empty() {
+        vec![WARNING_PERCENT, 100]
+    } else {
+        goal.thresholds.clone()
+    }
+}
+
+pub fn earliest_start(goals: &[Goal], now: DateTime<Local>) -> DateTime<Local> {
+    goals
+        .iter()
+        .map(|goal| goal.period.start(now))
+        .min()
+        .unwrap_or_else(|| week_start(now))
+}
+
+fn clipped(interval: &Interval, from: DateTime<Local>) -> Duration {
+    (interval.end - interval.start.max(from))
+        .to_std()
+        .unwrap_or_default()
+}
+
+pub fn progress<'a>(
+    goals: &'a [Goal],
+    intervals: &[Interval],
+    now: DateTime<Local>,
+) -> Vec<GoalProgress<'a>> {
+    goals
+        .iter()
+        .map(|goal| {
+            let from = goal.period.start(now);
+            GoalProgress {
+                goal,
+                spent: intervals
+                    .iter()
+                    .filter(|interval| goal.matches(&interval.title))
+                    .map(|interval| clipped(interval, from))
+                    .sum(),
+            }
+        })
+        .collect()
+}
+
+pub fn current_progress<'a>(goals: &'a [Goal], store: &dyn Store) -> Result<Vec<GoalProgress<'a>>> {
+    let now = Local::now();
+    let intervals = store.query(&Query {
+        from: Some(earliest_start(goals, now)),
+        to: None,
+    })?;
+    Ok(progress(goals, &intervals, now))
+}
+
+pub fn format_progress(progress: &GoalProgress, language: Language) -> String {
+    let percent = progress.percent();
+    let filled = (percent as usize * BAR_WIDTH / 100).min(BAR_WIDTH);
+    let target = progress.target();
+    let remaining = if progress.is_limit() {
+        match target.checked_sub(progress.spent) {
+            Some(left) => format!(
+                "{} {}",
+                language.pick("남음", "left"),
+                format_duration(&left)
+            ),
+            None => format!(
+                "{} {}",
+                language.pick("초과", "over"),
+                format_duration(&(progress.spent - target))
+            ),
+        }
+    } else {
+        match target.checked_sub(progress.spent) {
+            Some(left) if !left.is_zero() => {
+                format!(
+                    "{} {}",
+                    language.pick("부족", "short"),
+                    format_duration(&left)
+                )
+            }
+            _ => language.pick("달성", "met").to_string(),
+        }
+    };
+    format!(
+        "{} [{}{}] {} / {} ({}%) {}",
+        truncate_or_pad(
+            &format!(
+                "{} ({})",
+                progress.goal.name,
+                progress.goal.period.label(language)
+            ),
+            title_width()
+        ),
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        format_duration(&progress.spent),
+        format_duration(&target),
+        percent,
+        remaining
+    )
+}
+
+pub fn print_status(goals: &[Goal], store: &dyn Store, language: Language) -> Result<()> {
+    if goals.is_empty() {
+        println!(
+            "{}",
+            language.pick(
+                "설정된 목표나 예산이 없습니다",
+                "No goals or budgets configured"
+            )
+        );
+        return Ok(());
+    }
+    let now = Local::now();
+    let week = language.date(week_start(now).date_naive());
+    let month = language.date(month_start(now).date_naive());
+    match language {
+        Language::Ko => println!("목표와 예산 (주간: {} 부터, 월간: {} 부터)", week, month),
+        Language::En => println!(
+            "Goals and budgets (weekly from {}, monthly from {})",
+            week, month
+        ),
+    }
+    for progress in current_progress(goals, store)? {
+        println!("{}", format_progress(&progress, language));
+    }
+    Ok(())
+}
+
+fn breach_title(period: Period, name: &str, language: Language) -> String {
+    format!(
+        "{}{} {}: {}",
+        icon(Icon::LimitExceeded),
+        period.label(language),
+        language.pick("한도 초과", "limit exceeded"),
+        name
+    )
+}
+
+fn record_breach(store: &Mutex<Box<dyn Store>>, progress: &GoalProgress) {
+    let annotation = Annotation {
+        time: Local::now(),
+        kind: LIMIT_KIND.to_string(),
+        text: format!(
+            "{} {}/{} {}",
+            progress.goal.period.key(),
+            progress.spent.as_secs(),
+            progress.target().as_secs(),
+            progress.goal.name
+        ),
+    };
+    if let Err(e) = store.lock().unwrap().append_annotation(&annotation) {
+        eprintln!("사용 기록 저장 실패: {}", e);
+    }
+}
+
+fn describe_breach(text: &str, language: Language) -> Option<String> {
+    let (period, rest) = text.split_once(' ')?;
+    let (amounts, name) = rest.split_once(' ')?;
+    let (spent, target) = amounts.split_once('/')?;
+    let spent = Duration::from_secs(spent.parse().ok()?);
+    let target = Duration::from_secs(target.parse().ok()?);
+    Some(format!(
+        "{} ({} / {})",
+        breach_title(Period::from_key(period)?, name, language),
+        format_duration(&spent),
+        format_duration(&target)
+    ))
+}
+
+pub fn describe_note(annotation: &Annotation, language: Language) -> String {
+    match annotation.kind.as_str() {
+        LIMIT_KIND => describe_breach(&annotation.text, language),
+        _ => None,
+    }
+    .unwrap_or_else(|| annotation.text.clone())
+}
+
+fn notify_threshold(progress: &GoalProgress, level: u32, language: Language) {
+    let name = &progress.goal.name;
+    let text = format!(
+        "{} / {}",
+        format_duration(&progress.spent),
+        format_duration(&progress.target())
+    );
+    let period = progress.goal.period.label(language);
+    let title = match (progress.is_limit(), level) {
+        (true, 100..) => breach_title(progress.goal.period, name, language),
+        (true, _) => format!(
+            "{}{} {} {}%: {}",
+            icon(Icon::Warning),
+            period,
+            language.pick("한도", "limit"),
+            level,
+            name
+        ),
+        (false, 100..) => format!(
+            "{}{} {}: {}",
+            icon(Icon::Trophy),
+            period,
+            language.pick("목표 달성", "goal met"),
+            name
+        ),
+        (false, _) => format!(
+            "{}{} {} {}%: {}",
+            icon(Icon::Target),
+            period,
+            language.pick("목표", "goal"),
+            level,
+            name
+        ),
+    };
+    notify::send(&title, &text);
+}
+
+pub fn spawn_watcher(
+    goals: Vec<Goal>,
+    store: Arc<Mutex<Box<dyn Store>>>,
+    control: Arc<Control>,
+    language: Language,
+) {
+    if goals.is_empty() {
+        return;
+    }
+    let intervals_rx = control.subscribe_intervals();
+    thread::spawn(move || {
+        let mut starts: Vec<DateTime<Local>> = Vec::new();
+        let mut from = Local::now();
+        let mut intervals = Vec::new();
+        let mut notified: HashMap<String, u32> = HashMap::new();
+
+        loop {
+            let now = Local::now();
+            let current_starts: Vec<_> = goals.iter().map(|goal| goal.period.start(now)).collect();
+            if current_starts != starts {
+                for (i, goal) in goals.iter().enumerate() {
+                    if starts.get(i) != Some(&current_starts[i]) {
+                        notified.remove(&goal.name);
+                    }
+                }
+                starts = current_starts;
+                from = earliest_start(&goals, now);
+                let query = Query {
+                    from: Some(from),
+                    to: None,
+                };
+                intervals = store.lock().unwrap().query(&query).unwrap_or_else(|e| {
+                    eprintln!("목표 기록 조회 실패: {}", e);
+                    Vec::new()
+                });
+            }
+
+            let mut current = intervals.clone();
+            let status = control.status();
+            if let (Some(title), Some(since), false) = (status.title, status.since, status.paused) {
+                current.push(Interval::new(since, now, title, None));
+            }
+            for progress in progress(&goals, &current, now) {
+                let percent = progress.percent();
+                let Some(level) = thresholds(progress.goal)
+                    .into_iter()
+                    .filter(|threshold| percent >= *threshold)
+                    .max()
+                else {
+                    continue;
+                };
+                let last = notified.entry(progress.goal.name.clone()).or_insert(0);
+                if level > *last {
+                    *last = level;
+                    notify_threshold(&progress, level, language);
+                    if progress.is_limit() && level >= 100 {
+                        record_breach(&store, &progress);
+                    }
+                }
+            }
+
+            match intervals_rx.recv_timeout(CHECK_INTERVAL) {
+                Ok(interval) => {
+                    if interval.end > from {
+                        intervals.push(interval);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(period: Period) -> Goal {
+        Goal {
+            name: "code".to_string(),
+            titles: vec!["code".to_string()],
+            max_hours: None,
+            min_hours: Some(10.0),
+            daily_hours: None,
+            period,
+            thresholds: Vec::new(),
+            id: None,
+        }
+    }
+
+    fn interval(from: (u32, u32, u32), to: (u32, u32, u32), title: &str) -> Interval {
+        let at = |(day, hour, minute)| Local.with_ymd_and_hms(2024, 6, day, hour, minute, 0);
+        Interval::new(at(from).unwrap(), at(to).unwrap(), title.to_string(), None)
+    }
+
+    #[test]
+    fn progress_clips_intervals_to_the_period_start() {
+        let goals = [goal(Period::Day), goal(Period::Week), goal(Period::Month)];
+        let intervals = [
+            interval((1, 10, 0), (1, 11, 0), "code"),
+            interval((2, 23, 30), (3, 0, 30), "code"),
+            interval((4, 23, 0), (5, 1, 0), "code"),
+            interval((5, 9, 0), (5, 10, 0), "code"),
+            interval((5, 10, 0), (5, 11, 0), "mail"),
+        ];
+        let now = Local.with_ymd_and_hms(2024, 6, 5, 12, 0, 0).unwrap();
+        let spent: Vec<Duration> = progress(&goals, &intervals, now)
+            .iter()
+            .map(|progress| progress.spent)
+            .collect();
+
+        assert_eq!(
+            spent,
+            [
+                Duration::from_secs(2 * 3600),
+                Duration::from_secs(3 * 3600 + 1800),
+                Duration::from_secs(5 * 3600),
+            ]
+        );
+    }
+
+    #[test]
+    fn progress_ignores_intervals_that_ended_before_the_period() {
+        let goals = [goal(Period::Day)];
+        let intervals = [interval((4, 9, 0), (4, 10, 0), "code")];
+        let now = Local.with_ymd_and_hms(2024, 6, 5, 12, 0, 0).unwrap();
+        assert_eq!(progress(&goals, &intervals, now)[0].spent, Duration::ZERO);
+    }
+}
@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, TimeDelta, TimeZone};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::control::Control;
+use crate::idle::IDLE_LABELS;
+use crate::report::app_name;
+use crate::store::{Interval, Query, Result, Store};
+
+pub const ACTIVE_MINUTES: &str = "active_minutes";
+pub const TOP_APPS: &str = "top_apps";
+
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_BUCKETS: i64 = 10_000;
+
+#[derive(Deserialize)]
+struct Range {
+    from: DateTime<Local>,
+    to: DateTime<Local>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    #[serde(default)]
+    target: String,
+    #[serde(default)]
+    hide: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryRequest {
+    range: Range,
+    #[serde(default)]
+    interval_ms: Option<i64>,
+    #[serde(default)]
+    targets: Vec<Target>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    params: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+struct Server {
+    store: Arc<Mutex<Box<dyn Store>>>,
+    control: Arc<Control>,
+}
+
+impl Server {
+    fn intervals(&self, from: DateTime<Local>, to: DateTime<Local>) -> Result<Vec<Interval>> {
+        let mut intervals = self.store.lock().unwrap().query(&Query {
+            from: Some(from),
+            to: Some(to),
+        })?;
+        let status = self.control.status();
+        let now = Local::now();
+        let open = match (status.title, status.since) {
+            (Some(title), Some(since)) if !status.paused && since < now => {
+                Some(Interval::new(since, now, title, None))
+            }
+            _ => None,
+        };
+        intervals.extend(open);
+        intervals.retain(|interval| !IDLE_LABELS.contains(&interval.title.as_str()));
+        for interval in &mut intervals {
+            interval.start = interval.start.max(from);
+            interval.end = interval.end.min(to);
+        }
+        intervals.retain(|interval| interval.start < interval.end);
+        Ok(intervals)
+    }
+
+    fn active_minutes(
+        &self,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+        step_ms: i64,
+    ) -> Result<Vec<(DateTime<Local>, f64)>> {
+        let step_ms = step_ms
+            .max(60_000)
+            .max((to - from).num_milliseconds() / MAX_BUCKETS);
+        let step = TimeDelta::milliseconds(step_ms);
+        let first = Local
+            .timestamp_millis_opt(from.timestamp_millis() / step_ms * step_ms)
+            .single()
+            .unwrap_or(from);
+        let intervals = self.intervals(first, to)?;
+        let mut points = Vec::new();
+        let mut bucket = first;
+        while bucket < to {
+            let end = bucket + step;
+            let active: TimeDelta = intervals
+                .iter()
+                .map(|interval| interval.end.min(end) - interval.start.max(bucket))
+                .filter(|overlap| *overlap > TimeDelta::zero())
+                .sum();
+            points.push((bucket, active.num_seconds() as f64 / 60.0));
+            bucket = end;
+        }
+        Ok(points)
+    }
+
+    fn top_apps(&self, from: DateTime<Local>, to: DateTime<Local>) -> Result<Vec<(String, f64)>> {
+        let mut totals = HashMap::<String, f64>::new();
+        for interval in self.intervals(from, to)? {
+            let app = interval
+                .exe
+                .clone()
+                .unwrap_or_else(|| app_name(&interval.title));
+            *totals.entry(app).or_default() += interval.duration().as_secs_f64() / 60.0;
+        }
+        let mut apps: Vec<_> = totals.into_iter().collect();
+        apps.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(apps)
+    }
+
+    fn query(&self, request: &QueryRequest) -> Result<Value> {
+        let (from, to) = (request.range.from, request.range.to);
+        let mut results = Vec::new();
+        for target in request.targets.iter().filter(|target| !target.hide) {
+            match target.target.as_str() {
+                ACTIVE_MINUTES => {
+                    let datapoints: Vec<Value> = self
+                        .active_minutes(from, to, request.interval_ms.unwrap_or(60_000))?
+                        .into_iter()
+                        .map(|(time, minutes)| json!([minutes, time.timestamp_millis()]))
+                        .collect();
+                    results.push(json!({ "target": ACTIVE_MINUTES, "datapoints": datapoints }));
+                }
+                TOP_APPS => {
+                    let rows: Vec<Value> = self
+                        .top_apps(from, to)?
+                        .into_iter()
+                        .map(|(app, minutes)| json!([app, minutes]))
+                        .collect();
+                    results.push(json!({
+                        "type": "table",
+                        "columns": [
+                            { "text": "app", "type": "string" },
+                            { "text": "minutes", "type": "number" },
+                        ],
+                        "rows": rows,
+                    }));
+                }
+                other => return Err(format!("알 수 없는 지표: {}", other).into()),
+            }
+        }
+        Ok(Value::Array(results))
+    }
+
+    fn range(request: &Request) -> Result<(DateTime<Local>, DateTime<Local>)> {
+        let time = |name: &str, default: DateTime<Local>| -> Result<DateTime<Local>> {
+            match request.params.get(name) {
+                Some(ms) => Local
+                    .timestamp_millis_opt(ms.parse()?)
+                    .single()
+                    .ok_or_else(|| format!("잘못된 시각입니다: {}", ms).into()),
+                None => Ok(default),
+            }
+        };
+        let now = Local::now();
+        Ok((time("from", now - TimeDelta::days(1))?, time("to", now)?))
+    }
+
+    fn respond(&self, request: &Request) -> Result<Value> {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/") | ("OPTIONS", _) => Ok(Value::Null),
+            ("POST", "/search") | ("POST", "/metrics") => Ok(json!([
+                { "text": "활동 시간 (분)", "label": "활동 시간 (분)", "value": ACTIVE_MINUTES },
+                { "text": "앱 순위", "label": "앱 순위", "value": TOP_APPS },
+            ])),
+            ("POST", "/metric-payload-options") | ("POST", "/tag-keys") => Ok(json!([])),
+            ("POST", "/query") => self.query(&serde_json::from_slice(&request.body)?),
+            ("GET", "/active_minutes") => {
+                let (from, to) = Self::range(request)?;
+                let step = match request.params.get("interval_ms") {
+                    Some(ms) => ms.parse()?,
+                    None => 60_000,
+                };
+                Ok(self
+                    .active_minutes(from, to, step)?
+                    .into_iter()
+                    .map(|(time, minutes)| json!({ "time": time.to_rfc3339(), "minutes": minutes }))
+                    .collect())
+            }
+            ("GET", "/top_apps") => {
+                let (from, to) = Self::range(request)?;
+                Ok(self
+                    .top_apps(from, to)?
+                    .into_iter()
+                    .map(|(app, minutes)| json!({ "app": app, "minutes": minutes }))
+                    .collect())
+            }
+            _ => Err(format!("없는 경로입니다: {} {}", request.method, request.path).into()),
+        }
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().ok_or("빈 요청입니다")?.to_string();
+    let target = parts.next().ok_or("요청 경로가 없습니다")?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let params = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        match header.split_once(':') {
+            Some((name, value)) if name.trim().eq_ignore_ascii_case("content-length") => {
+                length = value.trim().parse()?
+            }
+            _ => {}
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    let path = match path.trim_end_matches('/') {
+        "" => "/",
+        path => path,
+    };
+    Ok(Request {
+        method,
+        path: path.to_string(),
+        params,
+        body,
+    })
+}
+
+fn write_response(mut stream: &TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: accept, content-type\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn handle(server: &Server, stream: &TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    match read_request(stream).and_then(|request| server.respond(&request)) {
+        Ok(Value::Null) => write_response(stream, "200 OK", ""),
+        Ok(value) => write_response(stream, "200 OK", &value.to_string()),
+        Err(e) => write_response(
+            stream,
+            "400 Bad Request",
+            &json!({ "message": e.to_string() }).to_string(),
+        ),
+    }
+}
+
+pub fn spawn(addr: SocketAddr, store: Arc<Mutex<Box<dyn Store>>>, control: Arc<Control>) {
+    let listener = match TcpListener::bind(addr) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!(
+                "Grafana 데이터 소스 서버를 시작할 수 없습니다 ({}): {}",
+                addr, e
+            );
+            return;
+        }
+    };
+    println!("Grafana 데이터 소스 서버 시작: http://{}", addr);
+    let server = Server { store, control };
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let result = stream.and_then(|stream| handle(&server, &stream));
+            if let Err(e) = result {
+                eprintln!("Grafana 요청 처리 실패: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,143 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use chrono::{DateTime, Local, TimeZone};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+
+use crate::control::{self, Control};
+use crate::store::{Query, Store};
+
+mod proto {
+    tonic::include_proto!("wcd");
+}
+
+use proto::window_tracker_server::{WindowTracker, WindowTrackerServer};
+
+struct Service {
+    store: Arc<Mutex<Box<dyn Store>>>,
+    control: Arc<Control>,
+}
+
+fn from_millis(ms: Option<i64>) -> Result<Option<DateTime<Local>>, ()> {
+    ms.map(|ms| Local.timestamp_millis_opt(ms).single().ok_or(()))
+        .transpose()
+}
+
+impl From<control::Status> for proto::StatusReply {
+    fn from(status: control::Status) -> Self {
+        proto::StatusReply {
+            paused: status.paused,
+            title: status.title,
+            since_ms: status.since.map(|since| since.timestamp_millis()),
+        }
+    }
+}
+
+impl From<control::Event> for proto::Event {
+    fn from(event: control::Event) -> Self {
+        proto::Event {
+            time_ms: event.time.timestamp_millis(),
+            kind: event.kind.as_str().to_string(),
+            title: event.title,
+            duration_ms: event.duration.map(|duration| duration.as_millis() as i64),
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl WindowTracker for Service {
+    async fn track(
+        &self,
+        _: Request<proto::TrackRequest>,
+    ) -> Result<Response<proto::StatusReply>, Status> {
+        self.control.resume();
+        Ok(Response::new(self.control.status().into()))
+    }
+
+    async fn pause(
+        &self,
+        _: Request<proto::PauseRequest>,
+    ) -> Result<Response<proto::StatusReply>, Status> {
+        self.control.pause();
+        Ok(Response::new(self.control.status().into()))
+    }
+
+    async fn status(
+        &self,
+        _: Request<proto::StatusRequest>,
+    ) -> Result<Response<proto::StatusReply>, Status> {
+        Ok(Response::new(self.control.status().into()))
+    }
+
+    async fn query_intervals(
+        &self,
+        request: Request<proto::QueryIntervalsRequest>,
+    ) -> Result<Response<proto::QueryIntervalsReply>, Status> {
+        let request = request.into_inner();
+        let (Ok(from), Ok(to)) = (from_millis(request.from_ms), from_millis(request.to_ms)) else {
+            return Err(Status::invalid_argument("잘못된 시각입니다"));
+        };
+        let query = Query { from, to };
+        let store = self.store.clone();
+        let intervals = tokio::task::spawn_blocking(move || store.lock().unwrap().query(&query))
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(Response::new(proto::QueryIntervalsReply {
+            intervals: intervals
+                .into_iter()
+                .map(|interval| proto::Interval {
+                    title: interval.title,
+                    start_ms: interval.start.timestamp_millis(),
+                    end_ms: interval.end.timestamp_millis(),
+                    location: interval.location,
+                    id: interval.id.to_string(),
+                    tag: interval.tag,
+                    exe: interval.exe,
+                })
+                .collect(),
+        }))
+    }
+
+    type EventsStream = ReceiverStream<Result<proto::Event, Status>>;
+
+    async fn events(
+        &self,
+        _: Request<proto::EventsRequest>,
+    ) -> Result<Response<Self::EventsStream>, Status> {
+        let events = self.control.subscribe();
+        let (tx, rx) = mpsc::channel(64);
+        thread::spawn(move || {
+            while let Ok(event) = events.recv() {
+                if tx.blocking_send(Ok(event.into())).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+}
+
+pub fn spawn(addr: SocketAddr, store: Arc<Mutex<Box<dyn Store>>>, control: Arc<Control>) {
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("gRPC 런타임 생성 실패");
+        let service = Service { store, control };
+        runtime.block_on(async move {
+            println!("gRPC 서버 시작: {}", addr);
+            if let Err(e) = Server::builder()
+                .add_service(WindowTrackerServer::new(service))
+                .serve(addr)
+                .await
+            {
+                eprintln!("gRPC 서버 오류: {}", e);
+            }
+        });
+    });
+}
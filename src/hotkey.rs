@@ -0,0 +1,87 @@
+use std::sync::Arc;
+#[cfg(windows)]
+use std::thread;
+
+#[cfg(windows)]
+use windows::Win32::UI::Input::KeyboardAndMouse::{HOT_KEY_MODIFIERS, RegisterHotKey};
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::{GetMessageW, MSG, WM_HOTKEY};
+
+use crate::config::Marker;
+use crate::control::Control;
+use crate::store::Result;
+
+pub const MARKER_KIND: &str = "marker";
+const MOD_ALT: u32 = 0x0001;
+const MOD_CONTROL: u32 = 0x0002;
+const MOD_SHIFT: u32 = 0x0004;
+const MOD_WIN: u32 = 0x0008;
+const MOD_NOREPEAT: u32 = 0x4000;
+
+pub fn parse_hotkey(text: &str) -> Result<(u32, u32)> {
+    let mut modifiers = MOD_NOREPEAT;
+    let mut key = None;
+    for part in text.split('+').map(|part| part.trim().to_lowercase()) {
+        match part.as_str() {
+            "ctrl" | "control" => modifiers |= MOD_CONTROL,
+            "alt" => modifiers |= MOD_ALT,
+            "shift" => modifiers |= MOD_SHIFT,
+            "win" => modifiers |= MOD_WIN,
+            _ if key.is_some() => return Err(format!("키가 두 개 이상입니다: {}", text).into()),
+            _ => key = Some(virtual_key(&part).ok_or_else(|| format!("알 수 없는 키: {}", part))?),
+        }
+    }
+    let key = key.ok_or_else(|| format!("단축키에 키가 없습니다: {}", text))?;
+    if modifiers == MOD_NOREPEAT {
+        return Err(format!("Ctrl, Alt, Shift, Win 중 하나가 필요합니다: {}", text).into());
+    }
+    Ok((modifiers, key))
+}
+
+fn virtual_key(name: &str) -> Option<u32> {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii_alphanumeric() => Some(ch.to_ascii_uppercase() as u32),
+        _ => match name.strip_prefix('f')?.parse::<u32>().ok()? {
+            number @ 1..=24 => Some(0x70 + number - 1),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(windows)]
+pub fn spawn_markers(markers: &[Marker], control: Arc<Control>) {
+    if markers.is_empty() {
+        return;
+    }
+    let markers = markers.to_vec();
+    thread::spawn(move || {
+        for (id, marker) in markers.iter().enumerate() {
+            let registered = parse_hotkey(&marker.hotkey).and_then(|(modifiers, key)| {
+                Ok(unsafe { RegisterHotKey(None, id as i32, HOT_KEY_MODIFIERS(modifiers), key) }?)
+            });
+            if let Err(e) = registered {
+                eprintln!(
+                    "마커 단축키 등록 실패 ({}, {}): {}",
+                    marker.name, marker.hotkey, e
+                );
+            }
+        }
+        let mut msg = MSG::default();
+        while unsafe { GetMessageW(&mut msg, None, 0, 0) }.as_bool() {
+            if let Some(marker) = markers
+                .get(msg.wParam.0)
+                .filter(|_| msg.message == WM_HOTKEY)
+            {
+                control.mark(&marker.name);
+            }
+        }
+    });
+}
+
+#[cfg(not(windows))]
+pub fn spawn_markers(markers: &[Marker], _control: Arc<Control>) {
+    if !markers.is_empty() {
+        eprintln!("마커 단축키는 Windows에서만 지원되어 등록하지 않습니다");
+    }
+}
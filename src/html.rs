@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta};
+
+use crate::config::{Goal, Language, Theme};
+use crate::format::format_duration;
+use crate::idle::IDLE_LABELS;
+use crate::report::{View, app_name, category};
+use crate::store::{Interval, Query, Result, Store};
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+const PIE_SLICES: usize = 7;
+const PIE_RADIUS: f64 = 90.0;
+const DAY_MINUTES: f64 = 1440.0;
+
+struct Colors {
+    background: &'static str,
+    text: &'static str,
+    muted: &'static str,
+    line: &'static str,
+    slices: [&'static str; PIE_SLICES],
+}
+
+const DARK: Colors = Colors {
+    background: "#1e1e1e",
+    text: "#e6e6e6",
+    muted: "#6b6b6b",
+    line: "#3a3a3a",
+    slices: [
+        "#56b4e9", "#e69f00", "#009e73", "#f0e442", "#cc79a7", "#0072b2", "#d55e00",
+    ],
+};
+
+const LIGHT: Colors = Colors {
+    background: "#ffffff",
+    text: "#111111",
+    muted: "#b0b0b0",
+    line: "#dddddd",
+    slices: [
+        "#0072b2", "#d55e00", "#009e73", "#cc79a7", "#e69f00", "#56b4e9", "#000000",
+    ],
+};
+
+const HIGH_CONTRAST: Colors = Colors {
+    background: "#000000",
+    text: "#ffffff",
+    muted: "#808080",
+    line: "#ffffff",
+    slices: [
+        "#ffff00", "#00ffff", "#ff00ff", "#00ff00", "#ffffff", "#ff6060", "#6080ff",
+    ],
+};
+
+const SCRIPT: &str = r#"
+document.querySelectorAll("th").forEach((th, column) => th.addEventListener("click", () => {
+  const body = th.closest("table").tBodies[0];
+  const ascending = th.dataset.order !== "asc";
+  th.closest("tr").querySelectorAll("th").forEach(other => delete other.dataset.order);
+  th.dataset.order = ascending ? "asc" : "desc";
+  const value = row => row.cells[column].dataset.value ?? row.cells[column].textContent;
+  [...body.rows]
+    .sort((a, b) => {
+      const x = value(a), y = value(b);
+      const order = isNaN(x) || isNaN(y) ? x.localeCompare(y) : x - y;
+      return ascending ? order : -order;
+    })
+    .forEach(row => body.appendChild(row));
+}));
+"#;
+
+pub fn set_theme(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+fn colors() -> &'static Colors {
+    match THEME.get().copied().unwrap_or_default() {
+        Theme::Dark => &DARK,
+        Theme::Light => &LIGHT,
+        Theme::HighContrast => &HIGH_CONTRAST,
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn app(interval: &Interval) -> String {
+    interval
+        .exe
+        .clone()
+        .unwrap_or_else(|| app_name(&interval.title))
+}
+
+fn is_idle(interval: &Interval) -> bool {
+    IDLE_LABELS.contains(&interval.title.as_str())
+}
+
+struct Apps {
+    slices: Vec<(String, Duration)>,
+    other: Duration,
+}
+
+impl Apps {
+    fn new(intervals: &[Interval]) -> Apps {
+        let mut totals = HashMap::<String, Duration>::new();
+        for interval in intervals.iter().filter(|interval| !is_idle(interval)) {
+            *totals.entry(app(interval)).or_default() += interval.duration();
+        }
+        let mut totals: Vec<_> = totals.into_iter().collect();
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let rest = totals.split_off(totals.len().min(PIE_SLICES));
+        Apps {
+            slices: totals,
+            other: rest.iter().map(|(_, duration)| *duration).sum(),
+        }
+    }
+
+    fn color(&self, interval: &Interval) -> &'static str {
+        if is_idle(interval) {
+            return "var(--line)";
+        }
+        let app = app(interval);
+        match self.slices.iter().position(|(name, _)| *name == app) {
+            Some(index) => colors().slices[index],
+            None => "var(--muted)",
+        }
+    }
+
+    fn total(&self) -> Duration {
+        self.slices
+            .iter()
+            .map(|(_, duration)| *duration)
+            .sum::<Duration>()
+            + self.other
+    }
+}
+
+fn point(fraction: f64) -> (f64, f64) {
+    let angle = fraction * std::f64::consts::TAU - std::f64::consts::FRAC_PI_2;
+    (PIE_RADIUS * angle.cos(), PIE_RADIUS * angle.sin())
+}
+
+fn pie(apps: &Apps, language: Language) -> Result<String> {
+    let total = apps.total().as_secs_f64();
+    if total == 0.0 {
+        return Ok(String::new());
+    }
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg class="pie" viewBox="-100 -100 200 200" role="img" aria-label="{}">"#,
+        language.pick("앱별 사용 시간", "Time per application")
+    )?;
+    let other = language.pick("기타", "Other");
+    let slices = apps
+        .slices
+        .iter()
+        .enumerate()
+        .map(|(index, (name, duration))| (name.as_str(), *duration, colors().slices[index]))
+        .chain((!apps.other.is_zero()).then_some((other, apps.other, "var(--muted)")));
+    let mut start = 0.0;
+    for (name, duration, color) in slices {
+        let share = duration.as_secs_f64() / total;
+        let label = format!(
+            "{} {} ({:.0}%)",
+            escape(name),
+            format_duration(&duration),
+            share * 100.0
+        );
+        if share >= 1.0 {
+            writeln!(
+                svg,
+                r#"<circle r="{}" fill="{}"><title>{}</title></circle>"#,
+                PIE_RADIUS, color, label
+            )?;
+        } else {
+            let (x1, y1) = point(start);
+            let (x2, y2) = point(start + share);
+            writeln!(
+                svg,
+                r#"<path d="M0 0 L{:.2} {:.2} A{r} {r} 0 {} 1 {:.2} {:.2} Z" fill="{}"><title>{}</title></path>"#,
+                x1,
+                y1,
+                u8::from(share > 0.5),
+                x2,
+                y2,
+                color,
+                label,
+                r = PIE_RADIUS
+            )?;
+        }
+        start += share;
+    }
+    writeln!(svg, "</svg>")?;
+    writeln!(svg, r#"<ul class="legend">"#)?;
+    for (index, (name, duration)) in apps.slices.iter().enumerate() {
+        writeln!(
+            svg,
+            r#"<li><span style="background:{}"></span>{} <b>{}</b></li>"#,
+            colors().slices[index],
+            escape(name),
+            format_duration(duration)
+        )?;
+    }
+    if !apps.other.is_zero() {
+        writeln!(
+            svg,
+            r#"<li><span style="background:var(--muted)"></span>{} <b>{}</b></li>"#,
+            other,
+            format_duration(&apps.other)
+        )?;
+    }
+    writeln!(svg, "</ul>")?;
+    Ok(svg)
+}
+
+fn midnight(day: NaiveDate) -> Option<DateTime<Local>> {
+    day.and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+}
+
+fn timeline(
+    day: NaiveDate,
+    intervals: &[Interval],
+    apps: &Apps,
+    language: Language,
+) -> Result<String> {
+    let (Some(from), Some(to)) = (midnight(day), day.succ_opt().and_then(midnight)) else {
+        return Ok(String::new());
+    };
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<h3>{}</h3><svg class="timeline" viewBox="0 0 {} 56">"#,
+        language.date(day),
+        DAY_MINUTES
+    )?;
+    writeln!(
+        svg,
+        r#"<rect width="{}" height="28" fill="none" stroke="var(--line)"/>"#,
+        DAY_MINUTES
+    )?;
+    for interval in intervals {
+        let start = interval.start.max(from);
+        let end = interval.end.min(to);
+        if start >= end {
+            continue;
+        }
+        writeln!(
+            svg,
+            r#"<rect x="{:.2}" width="{:.2}" height="28" fill="{}"><title>{}~{} {}</title></rect>"#,
+            (start - from).num_seconds() as f64 / 60.0,
+            (end - start).num_seconds() as f64 / 60.0,
+            apps.color(interval),
+            language.time(start),
+            language.time(end),
+            escape(&interval.title)
+        )?;
+    }
+    for hour in (0..=24).step_by(3) {
+        writeln!(
+            svg,
+            r#"<text x="{}" y="52" text-anchor="{}">{:02}</text>"#,
+            hour * 60,
+            match hour {
+                0 => "start",
+                24 => "end",
+                _ => "middle",
+            },
+            hour
+        )?;
+    }
+    writeln!(svg, "</svg>")?;
+    Ok(svg)
+}
+
+fn table(intervals: &[Interval], goals: &[Goal], language: Language) -> Result<String> {
+    let mut totals = HashMap::<&str, (String, String, Duration)>::new();
+    for interval in intervals {
+        totals
+            .entry(&interval.title)
+            .or_insert_with(|| {
+                (
+                    app(interval),
+                    category(&interval.title, interval.exe.as_deref(), goals),
+                    Duration::ZERO,
+                )
+            })
+            .2 += interval.duration();
+    }
+    let mut rows: Vec<_> = totals.into_iter().collect();
+    rows.sort_by(|a, b| b.1.2.cmp(&a.1.2).then_with(|| a.0.cmp(b.0)));
+    let total: Duration = rows.iter().map(|(_, (_, _, duration))| *duration).sum();
+
+    let mut html = String::new();
+    writeln!(
+        html,
+        "<table><thead><tr><th>{}</th><th>{}</th><th>{}</th><th>{}</th><th>{}</th></tr></thead><tbody>",
+        language.pick("창", "Window"),
+        language.pick("앱", "Application"),
+        language.pick("분류", "Category"),
+        language.pick("사용 시간", "Time"),
+        language.pick("비율", "Share")
+    )?;
+    for (title, (app, category, duration)) in &rows {
+        let share = duration.as_secs_f64() / total.as_secs_f64().max(1.0) * 100.0;
+        writeln!(
+            html,
+            r#"<tr><td>{}</td><td>{}</td><td>{}</td><td data-value="{}">{}</td><td data-value="{:.3}">{:.1}%</td></tr>"#,
+            escape(title),
+            escape(app),
+            escape(category),
+            duration.as_secs(),
+            format_duration(duration),
+            share,
+            share
+        )?;
+    }
+    writeln!(html, "</tbody></table>")?;
+    Ok(html)
+}
+
+pub fn write(
+    store: &dyn Store,
+    query: &Query,
+    goals: &[Goal],
+    view: &View,
+    language: Language,
+    path: &Path,
+) -> Result<()> {
+    let mut intervals = view.intervals(store, query)?;
+    intervals.sort_by_key(|interval| interval.start);
+    let apps = Apps::new(&intervals);
+    let idle: Duration = intervals
+        .iter()
+        .filter(|interval| is_idle(interval))
+        .map(Interval::duration)
+        .sum();
+    let mut days: Vec<NaiveDate> = Vec::new();
+    for interval in &intervals {
+        let mut day = interval.start.date_naive();
+        let last = (interval.end - TimeDelta::nanoseconds(1)).date_naive();
+        while day <= last {
+            if !days.contains(&day) {
+                days.push(day);
+            }
+            let Some(next) = day.succ_opt() else {
+                break;
+            };
+            day = next;
+        }
+    }
+    days.sort();
+    let title = language.pick("창 사용 시간 요약", "Window usage summary");
+    let period = match (days.first(), days.last()) {
+        (Some(first), Some(last)) => language.period(*first, *last),
+        _ => "-".to_string(),
+    };
+    let colors = colors();
+
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>")?;
+    writeln!(
+        html,
+        r#"<html lang="{}"><head><meta charset="utf-8">"#,
+        language.pick("ko", "en")
+    )?;
+    writeln!(html, "<title>{} {}</title>", title, period)?;
+    writeln!(
+        html,
+        "<style>
+:root {{ --background: {}; --text: {}; --muted: {}; --line: {}; }}
+body {{ background: var(--background); color: var(--text); font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 960px; padding: 0 1rem; }}
+.charts {{ display: flex; flex-wrap: wrap; gap: 2rem; align-items: center; }}
+.pie {{ width: 240px; height: 240px; }}
+.legend {{ list-style: none; padding: 0; }}
+.legend span {{ display: inline-block; width: 0.8em; height: 0.8em; margin-right: 0.5em; }}
+.timeline {{ width: 100%; }}
+.timeline text {{ fill: var(--text); font-size: 20px; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ border-bottom: 1px solid var(--line); padding: 0.3em 0.5em; text-align: left; }}
+th {{ cursor: pointer; user-select: none; }}
+th[data-order=asc]::after {{ content: \" ▲\"; }}
+th[data-order=desc]::after {{ content: \" ▼\"; }}
+td[data-value] {{ text-align: right; font-variant-numeric: tabular-nums; }}
+</style></head><body>",
+        colors.background, colors.text, colors.muted, colors.line
+    )?;
+    writeln!(html, "<h1>{}</h1>", title)?;
+    writeln!(
+        html,
+        "<p>{} {} · {} {} · {} {}</p>",
+        language.pick("기간", "Period"),
+        period,
+        language.pick("사용 시간", "Active time"),
+        format_duration(&apps.total()),
+        language.pick("자리 비움", "Idle"),
+        format_duration(&idle)
+    )?;
+    writeln!(
+        html,
+        r#"<h2>{}</h2><div class="charts">"#,
+        language.pick("앱별 사용 시간", "Time per application")
+    )?;
+    html.push_str(&pie(&apps, language)?);
+    writeln!(html, "</div>")?;
+    writeln!(html, "<h2>{}</h2>", language.pick("타임라인", "Timeline"))?;
+    for day in &days {
+        html.push_str(&timeline(*day, &intervals, &apps, language)?);
+    }
+    writeln!(html, "<h2>{}</h2>", language.pick("창 목록", "Windows"))?;
+    html.push_str(&table(&intervals, goals, language)?);
+    writeln!(html, "<script>{}</script></body></html>", SCRIPT)?;
+    fs::write(path, html)?;
+    Ok(())
+}
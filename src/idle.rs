@@ -0,0 +1,362 @@
+#[cfg(windows)]
+use std::sync::Mutex;
+use std::sync::OnceLock;
+#[cfg(windows)]
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+#[cfg(windows)]
+use std::thread;
+use std::time::Duration;
+
+#[cfg(windows)]
+use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, POINT, WPARAM};
+#[cfg(windows)]
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+#[cfg(windows)]
+use windows::Win32::System::SystemInformation::GetTickCount;
+#[cfg(windows)]
+use windows::Win32::UI::Input::{
+    GetRawInputData, HRAWINPUT, RAWINPUT, RAWINPUTDEVICE, RAWINPUTHEADER, RID_INPUT,
+    RIDEV_INPUTSINK, RIM_TYPEHID, RegisterRawInputDevices,
+};
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::{
+    CallNextHookEx, CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, HC_ACTION,
+    HWND_MESSAGE, MSG, MSLLHOOKSTRUCT, RegisterClassW, SPI_GETSCREENSAVERRUNNING,
+    SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS, SetWindowsHookExW, SystemParametersInfoW, WH_KEYBOARD_LL,
+    WH_MOUSE_LL, WINDOW_EX_STYLE, WINDOW_STYLE, WM_INPUT, WM_MOUSEMOVE, WNDCLASSW,
+};
+#[cfg(windows)]
+use windows::core::{PCWSTR, w};
+
+use crate::config::RawInputConfig;
+
+pub const IDLE_LABEL: &str = "[비활성 상태]";
+pub const SCREENSAVER_LABEL: &str = "[화면 보호기]";
+pub const DISPLAY_REQUIRED_LABEL: &str = "[화면 유지 요청]";
+pub const LOCKED_LABEL: &str = "[잠금]";
+pub const SLEEP_LABEL: &str = "[절전]";
+pub const IDLE_LABELS: &[&str] = &[
+    IDLE_LABEL,
+    SCREENSAVER_LABEL,
+    DISPLAY_REQUIRED_LABEL,
+    LOCKED_LABEL,
+    SLEEP_LABEL,
+];
+
+static IDLE_THRESHOLD: OnceLock<Duration> = OnceLock::new();
+#[cfg(windows)]
+static MEANINGFUL_INPUT_ONLY: AtomicBool = AtomicBool::new(false);
+#[cfg(windows)]
+static LAST_MEANINGFUL_INPUT: AtomicU32 = AtomicU32::new(0);
+#[cfg(windows)]
+static MOUSE_JITTER_PX: AtomicU32 = AtomicU32::new(0);
+#[cfg(windows)]
+static MOUSE_ANCHOR: Mutex<Option<POINT>> = Mutex::new(None);
+#[cfg(windows)]
+static RAW_INPUT_SEEN: AtomicBool = AtomicBool::new(false);
+#[cfg(windows)]
+static LAST_RAW_INPUT: AtomicU32 = AtomicU32::new(0);
+#[cfg(windows)]
+static RAW_REPORTS: Mutex<Vec<(usize, Vec<u8>)>> = Mutex::new(Vec::new());
+
+const GENERIC_DESKTOP_PAGE: u16 = 0x01;
+const DIGITIZER_PAGE: u16 = 0x0d;
+
+pub fn set_idle_threshold(threshold: Duration) {
+    let _ = IDLE_THRESHOLD.set(threshold);
+}
+
+pub fn idle_threshold() -> Duration {
+    IDLE_THRESHOLD
+        .get()
+        .copied()
+        .unwrap_or(Duration::from_secs(60))
+}
+
+#[cfg(windows)]
+fn mark_meaningful_input() {
+    LAST_MEANINGFUL_INPUT.store(unsafe { GetTickCount() }, Ordering::SeqCst);
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn keyboard_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        mark_meaningful_input();
+    }
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn mouse_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        if wparam.0 as u32 == WM_MOUSEMOVE {
+            let point = unsafe { (*(lparam.0 as *const MSLLHOOKSTRUCT)).pt };
+            let jitter = MOUSE_JITTER_PX.load(Ordering::SeqCst) as i64;
+            let mut anchor = MOUSE_ANCHOR.lock().unwrap();
+            let moved = anchor.is_none_or(|anchor| {
+                let dx = (point.x - anchor.x) as i64;
+                let dy = (point.y - anchor.y) as i64;
+                dx * dx + dy * dy > jitter * jitter
+            });
+            if moved {
+                *anchor = Some(point);
+                mark_meaningful_input();
+            }
+        } else {
+            mark_meaningful_input();
+        }
+    }
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+#[cfg(windows)]
+pub fn start_input_classifier(mouse_jitter_px: u32) {
+    MOUSE_JITTER_PX.store(mouse_jitter_px, Ordering::SeqCst);
+    mark_meaningful_input();
+    thread::spawn(|| unsafe {
+        let hooks = (
+            SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook), None, 0),
+            SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook), None, 0),
+        );
+        if let (Err(e), _) | (_, Err(e)) = hooks {
+            eprintln!(
+                "입력 후킹 실패, 모든 입력으로 비활성 상태를 판단합니다: {}",
+                e
+            );
+            return;
+        }
+        MEANINGFUL_INPUT_ONLY.store(true, Ordering::SeqCst);
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {}
+    });
+}
+
+fn raw_devices(config: &RawInputConfig) -> Vec<(u16, u16)> {
+    [
+        (config.joystick, GENERIC_DESKTOP_PAGE, 0x04),
+        (config.gamepad, GENERIC_DESKTOP_PAGE, 0x05),
+        (config.pen, DIGITIZER_PAGE, 0x02),
+        (config.touch, DIGITIZER_PAGE, 0x04),
+    ]
+    .into_iter()
+    .filter(|(enabled, _, _)| *enabled)
+    .map(|(_, page, usage)| (page, usage))
+    .collect()
+}
+
+#[cfg(windows)]
+fn mark_raw_input() {
+    LAST_RAW_INPUT.store(unsafe { GetTickCount() }, Ordering::SeqCst);
+    RAW_INPUT_SEEN.store(true, Ordering::SeqCst);
+}
+
+#[cfg(windows)]
+fn read_raw_input(lparam: LPARAM) {
+    let handle = HRAWINPUT(lparam.0 as *mut _);
+    let header_size = size_of::<RAWINPUTHEADER>() as u32;
+    let mut size = 0;
+    unsafe { GetRawInputData(handle, RID_INPUT, None, &mut size, header_size) };
+    if size == 0 {
+        return;
+    }
+    let mut buffer = vec![0u64; (size as usize).div_ceil(size_of::<u64>())];
+    let read = unsafe {
+        GetRawInputData(
+            handle,
+            RID_INPUT,
+            Some(buffer.as_mut_ptr().cast()),
+            &mut size,
+            header_size,
+        )
+    };
+    if read == u32::MAX || (read as usize) < size_of::<RAWINPUTHEADER>() {
+        return;
+    }
+    let input = unsafe { &*(buffer.as_ptr() as *const RAWINPUT) };
+    if input.header.dwType != RIM_TYPEHID.0 {
+        mark_raw_input();
+        return;
+    }
+    let hid = unsafe { &input.data.hid };
+    let length = (hid.dwSizeHid * hid.dwCount) as usize;
+    let available = read as usize - (hid.bRawData.as_ptr() as usize - buffer.as_ptr() as usize);
+    let report =
+        unsafe { std::slice::from_raw_parts(hid.bRawData.as_ptr(), length.min(available)) };
+    let device = input.header.hDevice.0 as usize;
+    let mut reports = RAW_REPORTS.lock().unwrap();
+    match reports.iter_mut().find(|(known, _)| *known == device) {
+        Some((_, last)) if last.as_slice() == report => return,
+        Some((_, last)) => *last = report.to_vec(),
+        None => reports.push((device, report.to_vec())),
+    }
+    mark_raw_input();
+}
+
+#[cfg(windows)]
+extern "system" fn raw_input_proc(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if message == WM_INPUT {
+        read_raw_input(lparam);
+    }
+    unsafe { DefWindowProcW(hwnd, message, wparam, lparam) }
+}
+
+#[cfg(windows)]
+fn listen_raw_input(devices: &[(u16, u16)]) -> windows::core::Result<()> {
+    unsafe {
+        let instance = HINSTANCE::from(GetModuleHandleW(None)?);
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(raw_input_proc),
+            hInstance: instance,
+            lpszClassName: w!("WindowChangeDetectorRawInput"),
+            ..Default::default()
+        };
+        RegisterClassW(&class);
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            w!("WindowChangeDetectorRawInput"),
+            PCWSTR::null(),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            Some(HWND_MESSAGE),
+            None,
+            Some(instance),
+            None,
+        )?;
+        let devices: Vec<RAWINPUTDEVICE> = devices
+            .iter()
+            .map(|&(page, usage)| RAWINPUTDEVICE {
+                usUsagePage: page,
+                usUsage: usage,
+                dwFlags: RIDEV_INPUTSINK,
+                hwndTarget: hwnd,
+            })
+            .collect();
+        RegisterRawInputDevices(&devices, size_of::<RAWINPUTDEVICE>() as u32)?;
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            DispatchMessageW(&msg);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn start_input_classifier(_mouse_jitter_px: u32) {
+    eprintln!(
+        "의미 있는 입력 구분은 Windows에서만 지원되어 모든 입력으로 비활성 상태를 판단합니다"
+    );
+}
+
+#[cfg(windows)]
+pub fn start_raw_input(config: &RawInputConfig) {
+    let devices = raw_devices(config);
+    if devices.is_empty() {
+        return;
+    }
+    thread::spawn(move || {
+        if let Err(e) = listen_raw_input(&devices) {
+            eprintln!(
+                "원시 입력 등록 실패, 컨트롤러와 펜 입력은 비활성 판단에 쓰이지 않습니다: {}",
+                e
+            );
+        }
+    });
+}
+
+#[cfg(not(windows))]
+pub fn start_raw_input(config: &RawInputConfig) {
+    if !raw_devices(config).is_empty() {
+        eprintln!(
+            "원시 입력은 Windows에서만 지원되어 컨트롤러와 펜 입력은 비활성 판단에 쓰이지 않습니다"
+        );
+    }
+}
+
+#[cfg(windows)]
+pub fn get_idle_duration() -> Duration {
+    let idle = input_idle_duration();
+    if !RAW_INPUT_SEEN.load(Ordering::SeqCst) {
+        return idle;
+    }
+    let last_raw_input = LAST_RAW_INPUT.load(Ordering::SeqCst);
+    let raw_idle_ms = unsafe { GetTickCount() }.wrapping_sub(last_raw_input);
+    idle.min(Duration::from_millis(raw_idle_ms as u64))
+}
+
+#[cfg(windows)]
+fn input_idle_duration() -> Duration {
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetLastInputInfo;
+    use windows::Win32::UI::Input::KeyboardAndMouse::LASTINPUTINFO;
+
+    if MEANINGFUL_INPUT_ONLY.load(Ordering::SeqCst) {
+        let last_input = LAST_MEANINGFUL_INPUT.load(Ordering::SeqCst);
+        let idle_time_ms = unsafe { GetTickCount() }.wrapping_sub(last_input);
+        return Duration::from_millis(idle_time_ms as u64);
+    }
+
+    unsafe {
+        let mut info = LASTINPUTINFO {
+            cbSize: size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+        if GetLastInputInfo(&mut info).as_bool() {
+            let current_tick = GetTickCount();
+            let idle_time_ms = current_tick - info.dwTime;
+            return Duration::from_millis(idle_time_ms as u64);
+        }
+    }
+    Duration::from_secs(0)
+}
+
+#[cfg(windows)]
+fn is_screensaver_running() -> bool {
+    unsafe {
+        let mut running = windows::core::BOOL(0);
+        SystemParametersInfoW(
+            SPI_GETSCREENSAVERRUNNING,
+            0,
+            Some(&mut running as *mut _ as *mut std::ffi::c_void),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+        .is_ok()
+            && running.as_bool()
+    }
+}
+
+#[cfg(windows)]
+fn is_display_required() -> bool {
+    use windows::Win32::System::Power::{
+        CallNtPowerInformation, ES_DISPLAY_REQUIRED, SystemExecutionState,
+    };
+
+    unsafe {
+        let mut state: u32 = 0;
+        let status = CallNtPowerInformation(
+            SystemExecutionState,
+            None,
+            0,
+            Some(&mut state as *mut _ as *mut std::ffi::c_void),
+            size_of::<u32>() as u32,
+        );
+        status.is_ok() && state & ES_DISPLAY_REQUIRED.0 != 0
+    }
+}
+
+#[cfg(windows)]
+pub fn get_idle_label() -> &'static str {
+    if is_screensaver_running() {
+        SCREENSAVER_LABEL
+    } else if is_display_required() {
+        DISPLAY_REQUIRED_LABEL
+    } else {
+        IDLE_LABEL
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Local};
+use uuid::{Uuid, uuid};
+
+const NAMESPACE: Uuid = uuid!("6d1f3c2a-8b47-4e0e-9a55-2f7c1b9d4e60");
+
+fn derived(kind: &str, key: &str) -> Uuid {
+    Uuid::new_v5(&NAMESPACE, format!("{}:{}", kind, key).as_bytes())
+}
+
+pub fn legacy_interval_id(start: DateTime<Local>, end: DateTime<Local>, title: &str) -> Uuid {
+    derived(
+        "interval",
+        &format!(
+            "{}|{}|{}",
+            start.timestamp_millis(),
+            end.timestamp_millis(),
+            title
+        ),
+    )
+}
+
+pub fn app_id(app: &str) -> Uuid {
+    derived("app", &app.to_lowercase())
+}
+
+pub fn project_id(name: &str) -> Uuid {
+    derived("project", name)
+}
+
+pub fn parse_or_legacy(
+    text: Option<String>,
+    start: DateTime<Local>,
+    end: DateTime<Local>,
+    title: &str,
+) -> Uuid {
+    text.and_then(|text| Uuid::parse_str(&text).ok())
+        .unwrap_or_else(|| legacy_interval_id(start, end, title))
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::Deserialize;
+
+use crate::audit::{self, Action};
+use crate::store::{Interval, Query, Result, Store};
+
+#[derive(Deserialize)]
+struct ManualEntry {
+    start: String,
+    end: String,
+    label: String,
+}
+
+pub fn parse_time(text: &str) -> Result<DateTime<Local>> {
+    let text = text.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Ok(time.with_timezone(&Local));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M:%S"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(text, format) {
+            return Local
+                .from_local_datetime(&time)
+                .earliest()
+                .ok_or_else(|| format!("존재하지 않는 현지 시각입니다: {}", text).into());
+        }
+    }
+    Err(format!("시각 형식을 알 수 없습니다: {}", text).into())
+}
+
+pub fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(ch),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn read_csv(text: &str) -> Result<Vec<ManualEntry>> {
+    let mut entries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fields = split_csv_line(line);
+        if number == 0 && fields.first().is_some_and(|field| field.trim() == "start") {
+            continue;
+        }
+        let [start, end, label] = <[String; 3]>::try_from(fields)
+            .map_err(|_| format!("{}번째 줄: start,end,label 세 칸이 필요합니다", number + 1))?;
+        entries.push(ManualEntry { start, end, label });
+    }
+    Ok(entries)
+}
+
+pub fn import_manual(store: &mut dyn Store, path: &Path) -> Result<usize> {
+    let text = fs::read_to_string(path)?;
+    let entries = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::from_str(&text)?,
+        _ => read_csv(&text)?,
+    };
+
+    let mut intervals = Vec::new();
+    for entry in entries {
+        let interval = Interval::new(
+            parse_time(&entry.start)?,
+            parse_time(&entry.end)?,
+            entry.label.trim().to_string(),
+            None,
+        );
+        if interval.end <= interval.start {
+            return Err(format!(
+                "끝 시각이 시작 시각보다 빠릅니다: {} ~ {}",
+                entry.start, entry.end
+            )
+            .into());
+        }
+        intervals.push(interval);
+    }
+    let range = Query {
+        from: intervals.iter().map(|interval| interval.start).min(),
+        to: intervals.iter().map(|interval| interval.end).max(),
+    };
+    let before = audit::summarize(&store.query(&range)?);
+    for interval in &intervals {
+        store.append_interval(interval)?;
+    }
+    audit::record(
+        Action::Import,
+        &path.display().to_string(),
+        before,
+        audit::summarize(&store.query(&range)?),
+    )?;
+    Ok(intervals.len())
+}
@@ -0,0 +1,112 @@
+use std::io::{Write, stdin, stdout};
+use std::path::Path;
+
+use toml::Value;
+
+use crate::config::{self, Config, parse_hours};
+use crate::store::Result;
+
+fn ask(question: &str, default: &str) -> Result<String> {
+    if default.is_empty() {
+        print!("{}: ", question);
+    } else {
+        print!("{} [{}]: ", question, default);
+    }
+    stdout().flush()?;
+    let mut input = String::new();
+    if stdin().read_line(&mut input)? == 0 {
+        return Ok(default.to_string());
+    }
+    let input = input.trim();
+    Ok(if input.is_empty() { default } else { input }.to_string())
+}
+
+fn ask_until<T>(
+    question: &str,
+    default: &str,
+    parse: impl Fn(&str) -> std::result::Result<T, String>,
+) -> Result<T> {
+    loop {
+        match parse(&ask(question, default)?) {
+            Ok(value) => return Ok(value),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+fn quote(text: &str) -> String {
+    Value::String(text.to_string()).to_string()
+}
+
+pub fn run(force: bool) -> Result<()> {
+    let file = config::path();
+    if file.exists() && !force {
+        return Err(format!(
+            "{}이(가) 이미 있습니다 (덮어쓰려면 --force)",
+            file.display()
+        )
+        .into());
+    }
+    println!(
+        "{} 파일을 만듭니다. Enter를 누르면 기본값을 씁니다.",
+        file.display()
+    );
+
+    let language = ask_until("로그 파일 언어 (ko/en)", "ko", |text| match text {
+        "ko" | "en" => Ok(text.to_string()),
+        _ => Err("ko 또는 en을 입력하세요".to_string()),
+    })?;
+    let idle = ask_until("비활성으로 볼 무입력 시간 (초)", "60", |text| {
+        text.parse::<u64>()
+            .map_err(|_| "초 단위 숫자를 입력하세요".to_string())
+    })?;
+    let dir = ask("기록과 요약 로그를 저장할 폴더", ".")?;
+    let path = Path::new(&dir).join("window_log.db");
+
+    let mut goals = Vec::new();
+    println!("분류 규칙을 추가합니다. 이름을 비워 두면 끝납니다.");
+    loop {
+        let name = ask("분류 이름 (예: 코딩)", "")?;
+        if name.is_empty() {
+            break;
+        }
+        let titles: Vec<String> = ask("창 제목에 포함될 텍스트 (쉼표로 구분)", &name)?
+            .split(',')
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty())
+            .collect();
+        let hours = ask_until("주간 목표 시간 (예: 10h)", "10h", parse_hours)?;
+        goals.push((name, titles, hours));
+    }
+
+    let mut text = format!(
+        "report_language = {}\nidle_threshold_secs = {}\noutput_dir = {}\n\n[store]\nbackend = \"sqlite\"\npath = {}\n",
+        quote(&language),
+        idle,
+        quote(&dir),
+        quote(&path.to_string_lossy())
+    );
+    for (name, titles, hours) in &goals {
+        text.push_str(&format!(
+            "\n[[goals]]\nname = {}\ntitles = [{}]\nmin_hours = {}\n",
+            quote(name),
+            titles
+                .iter()
+                .map(|title| quote(title))
+                .collect::<Vec<_>>()
+                .join(", "),
+            hours
+        ));
+    }
+    std::fs::create_dir_all(&dir)?;
+    if let Some(parent) = file
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(file, text)?;
+    Config::load(&[])?;
+    println!("{} 저장됨", file.display());
+    Ok(())
+}
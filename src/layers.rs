@@ -0,0 +1,175 @@
+use std::fmt;
+
+use toml::{Table, Value};
+
+use crate::config::{self, Config};
+
+pub const ENV_PREFIX: &str = "WCD_";
+const SECRET_KEYS: &[&str] = &["token", "api_token", "password", "secret_key", "passphrase"];
+
+pub enum Source {
+    Default,
+    File,
+    Env(String),
+    Cli,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "기본값"),
+            Source::File => write!(f, "{}", config::path().display()),
+            Source::Env(name) => write!(f, "환경 변수 {}", name),
+            Source::Cli => write!(f, "명령줄"),
+        }
+    }
+}
+
+pub struct Override {
+    pub path: Vec<String>,
+    pub value: Value,
+    pub source: Source,
+}
+
+fn parse_value(raw: &str) -> Value {
+    toml::from_str::<Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+pub fn overrides(cli: &[String]) -> Result<Vec<Override>, String> {
+    let mut env: Vec<(String, String)> = std::env::vars()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+        .collect();
+    env.sort();
+    let mut overrides: Vec<Override> = env
+        .into_iter()
+        .map(|(name, raw)| Override {
+            path: name[ENV_PREFIX.len()..]
+                .to_lowercase()
+                .split("__")
+                .map(str::to_string)
+                .collect(),
+            value: parse_value(&raw),
+            source: Source::Env(name),
+        })
+        .collect();
+    for item in cli {
+        let (key, raw) = item
+            .split_once('=')
+            .ok_or_else(|| format!("--set은 KEY=VALUE 형식이어야 합니다: {}", item))?;
+        overrides.push(Override {
+            path: key.trim().split('.').map(str::to_string).collect(),
+            value: parse_value(raw.trim()),
+            source: Source::Cli,
+        });
+    }
+    Ok(overrides)
+}
+
+pub fn apply(table: &mut Table, overrides: &[Override]) -> Result<(), String> {
+    for item in overrides {
+        let Some((last, parents)) = item.path.split_last() else {
+            continue;
+        };
+        let mut current = &mut *table;
+        for key in parents {
+            current = current
+                .entry(key.clone())
+                .or_insert_with(|| Value::Table(Table::new()))
+                .as_table_mut()
+                .ok_or_else(|| format!("{}: '{}'은(는) 표가 아닙니다", item.source, key))?;
+        }
+        current.insert(last.clone(), item.value.clone());
+    }
+    Ok(())
+}
+
+fn leaves(prefix: &str, table: &Table, out: &mut Vec<(String, Value)>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Table(table) => leaves(&path, table, out),
+            _ => out.push((path, value.clone())),
+        }
+    }
+}
+
+fn redact(value: &mut Value) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if SECRET_KEYS.contains(&key.as_str()) {
+                    *value = Value::String("***".to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn overlaps(a: &str, b: &str) -> bool {
+    a == b || a.starts_with(&format!("{}.", b)) || b.starts_with(&format!("{}.", a))
+}
+
+fn in_file(file: &Table, path: &str) -> bool {
+    let mut current = file;
+    for key in path.split('.') {
+        match current.get(key) {
+            Some(Value::Table(table)) => current = table,
+            Some(_) => return true,
+            None => return false,
+        }
+    }
+    true
+}
+
+fn source<'a>(path: &str, file: &Table, overrides: &'a [Override]) -> Option<&'a Source> {
+    overrides
+        .iter()
+        .rev()
+        .find(|item| overlaps(path, &item.path.join(".")))
+        .map(|item| &item.source)
+        .or_else(|| in_file(file, path).then_some(&Source::File))
+}
+
+pub fn print_config(resolved: bool, overrides: &[String]) -> Result<(), String> {
+    if !resolved {
+        return match std::fs::read_to_string(config::path()) {
+            Ok(text) => {
+                print!("{}", text);
+                Ok(())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                println!(
+                    "{} 파일이 없어 기본 설정을 사용합니다",
+                    config::path().display()
+                );
+                Ok(())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+    }
+
+    let (config, file, overrides) = Config::layered(overrides)?;
+    let mut resolved = Value::try_from(&config).map_err(|e| e.to_string())?;
+    redact(&mut resolved);
+    let Value::Table(table) = resolved else {
+        return Err("설정을 표로 바꿀 수 없습니다".to_string());
+    };
+    let mut values = Vec::new();
+    leaves("", &table, &mut values);
+    for (path, value) in values {
+        let source = source(&path, &file, &overrides).unwrap_or(&Source::Default);
+        println!("{} = {}  # {}", path, value, source);
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+pub mod amend;
+pub mod anomaly;
+pub mod audit;
+pub mod backup;
+pub mod billing;
+pub mod binding;
+pub mod categories;
+pub mod cloud;
+pub mod compact;
+pub mod config;
+pub mod control;
+pub mod debug;
+pub mod estimate;
+pub mod events;
+pub mod explain;
+pub mod export;
+pub mod exporter;
+pub mod forecast;
+pub mod format;
+pub mod gaps;
+pub mod goals;
+pub mod grafana;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod hotkey;
+pub mod html;
+pub mod idle;
+pub mod ids;
+pub mod import;
+pub mod init;
+pub mod layers;
+pub mod locale;
+pub mod location;
+pub mod merge;
+pub mod network;
+pub mod normalize;
+pub mod notify;
+pub mod platform;
+pub mod presence;
+pub mod query;
+pub mod recovery;
+pub mod remote;
+pub mod report;
+pub mod resume;
+pub mod retro;
+pub mod rollover;
+pub mod screenshot;
+pub mod search;
+#[cfg(windows)]
+pub mod service;
+pub mod sessions;
+pub mod signing;
+pub mod sql;
+pub mod standup;
+pub mod stats;
+pub mod store;
+pub mod summary;
+pub mod tasks;
+pub mod timelapse;
+pub mod timer;
+pub mod tracker;
+#[cfg(windows)]
+pub mod tray;
+pub mod tui;
+pub mod uptime;
+pub mod validate;
+pub mod window;
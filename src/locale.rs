@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, Weekday};
+
+use crate::config::Language;
+
+impl Language {
+    pub fn pick(self, ko: &'static str, en: &'static str) -> &'static str {
+        match self {
+            Language::Ko => ko,
+            Language::En => en,
+        }
+    }
+
+    pub fn date(self, date: NaiveDate) -> String {
+        match self {
+            Language::Ko => format!(
+                "{} ({})",
+                date.format("%Y-%m-%d"),
+                self.weekday(date.weekday())
+            ),
+            Language::En => format!(
+                "{}, {}",
+                self.weekday(date.weekday()),
+                date.format("%b %-d, %Y")
+            ),
+        }
+    }
+
+    pub fn period(self, first: NaiveDate, last: NaiveDate) -> String {
+        if first == last {
+            self.date(first)
+        } else {
+            format!("{} ~ {}", self.date(first), self.date(last))
+        }
+    }
+
+    pub fn datetime(self, time: DateTime<Local>) -> String {
+        time.format(self.pick("%Y-%m-%d %H:%M:%S", "%b %-d, %Y %-I:%M:%S %p"))
+            .to_string()
+    }
+
+    pub fn short_datetime(self, time: DateTime<Local>) -> String {
+        time.format(self.pick("%Y-%m-%d %H:%M", "%b %-d, %Y %-I:%M %p"))
+            .to_string()
+    }
+
+    pub fn time(self, time: DateTime<Local>) -> String {
+        time.format(self.pick("%H:%M:%S", "%-I:%M:%S %p"))
+            .to_string()
+    }
+
+    pub fn weekday(self, weekday: Weekday) -> &'static str {
+        match weekday {
+            Weekday::Mon => self.pick("월", "Mon"),
+            Weekday::Tue => self.pick("화", "Tue"),
+            Weekday::Wed => self.pick("수", "Wed"),
+            Weekday::Thu => self.pick("목", "Thu"),
+            Weekday::Fri => self.pick("금", "Fri"),
+            Weekday::Sat => self.pick("토", "Sat"),
+            Weekday::Sun => self.pick("일", "Sun"),
+        }
+    }
+
+    pub fn days(self, days: u32) -> String {
+        match self {
+            Language::Ko => format!("{}일", days),
+            Language::En if days == 1 => "1 day".to_string(),
+            Language::En => format!("{} days", days),
+        }
+    }
+
+    fn unit(self, count: u64, ko: &str, en: &str) -> String {
+        match self {
+            Language::Ko => format!("{}{}", count, ko),
+            Language::En if count == 1 => format!("1 {}", en),
+            Language::En => format!("{} {}s", count, en),
+        }
+    }
+
+    pub fn spelled_duration(self, duration: &Duration) -> String {
+        let secs = (duration.as_millis() as u64 + 500) / 1000;
+        let parts: Vec<String> = [
+            (secs / 3600, "시간", "hour"),
+            ((secs % 3600) / 60, "분", "minute"),
+            (secs % 60, "초", "second"),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, ko, en)| self.unit(count, ko, en))
+        .collect();
+        if parts.is_empty() {
+            return self.unit(0, "초", "second");
+        }
+        parts.join(" ")
+    }
+}
@@ -0,0 +1,127 @@
+use sha2::{Digest, Sha256};
+#[cfg(windows)]
+use windows::Win32::Foundation::HANDLE;
+#[cfg(windows)]
+use windows::Win32::NetworkManagement::WiFi::{
+    WLAN_CONNECTION_ATTRIBUTES, WLAN_INTERFACE_INFO_LIST, WlanCloseHandle, WlanEnumInterfaces,
+    WlanFreeMemory, WlanOpenHandle, WlanQueryInterface, wlan_interface_state_connected,
+    wlan_intf_opcode_current_connection,
+};
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CMONITORS};
+
+use crate::config::LocationProfile;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationInfo {
+    pub monitors: u32,
+    pub wifi: Option<String>,
+}
+
+impl LocationInfo {
+    pub fn detect() -> LocationInfo {
+        LocationInfo {
+            monitors: monitor_count(),
+            wifi: wifi_ssid().map(|ssid| hash_ssid(&ssid)),
+        }
+    }
+
+    pub fn docked(&self) -> bool {
+        self.monitors > 1
+    }
+
+    pub fn profile<'a>(&self, profiles: &'a [LocationProfile]) -> Option<&'a LocationProfile> {
+        profiles.iter().find(|profile| {
+            profile
+                .monitors
+                .is_none_or(|monitors| monitors == self.monitors)
+                && profile.docked.is_none_or(|docked| docked == self.docked())
+                && profile
+                    .wifi
+                    .as_ref()
+                    .is_none_or(|wifi| self.wifi.as_ref() == Some(wifi))
+        })
+    }
+}
+
+pub fn print_location(profiles: &[LocationProfile]) {
+    let info = LocationInfo::detect();
+    println!("모니터 수: {}", info.monitors);
+    println!("도킹 상태: {}", if info.docked() { "예" } else { "아니오" });
+    println!("Wi-Fi 해시: {}", info.wifi.as_deref().unwrap_or("-"));
+    println!(
+        "위치 프로필: {}",
+        info.profile(profiles)
+            .map(|profile| profile.name.as_str())
+            .unwrap_or("-")
+    );
+}
+
+pub fn hash_ssid(ssid: &str) -> String {
+    Sha256::digest(ssid.as_bytes())
+        .iter()
+        .take(8)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(windows)]
+fn monitor_count() -> u32 {
+    unsafe { GetSystemMetrics(SM_CMONITORS).max(0) as u32 }
+}
+
+#[cfg(not(windows))]
+fn monitor_count() -> u32 {
+    0
+}
+
+#[cfg(windows)]
+fn wifi_ssid() -> Option<String> {
+    unsafe {
+        let mut version = 0;
+        let mut client = HANDLE::default();
+        if WlanOpenHandle(2, None, &mut version, &mut client) != 0 {
+            return None;
+        }
+
+        let mut ssid = None;
+        let mut list: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+        if WlanEnumInterfaces(client, None, &mut list) == 0 && !list.is_null() {
+            let count = (*list).dwNumberOfItems as usize;
+            let interfaces = std::slice::from_raw_parts((*list).InterfaceInfo.as_ptr(), count);
+            for interface in interfaces {
+                if interface.isState != wlan_interface_state_connected {
+                    continue;
+                }
+                let mut size = 0;
+                let mut data: *mut std::ffi::c_void = std::ptr::null_mut();
+                let result = WlanQueryInterface(
+                    client,
+                    &interface.InterfaceGuid,
+                    wlan_intf_opcode_current_connection,
+                    None,
+                    &mut size,
+                    &mut data,
+                    None,
+                );
+                if result != 0 || data.is_null() {
+                    continue;
+                }
+                let attributes = &*(data as *const WLAN_CONNECTION_ATTRIBUTES);
+                let raw = &attributes.wlanAssociationAttributes.dot11Ssid;
+                let len = (raw.uSSIDLength as usize).min(raw.ucSSID.len());
+                ssid = Some(String::from_utf8_lossy(&raw.ucSSID[..len]).to_string());
+                WlanFreeMemory(data);
+                break;
+            }
+            WlanFreeMemory(list as *const std::ffi::c_void);
+        }
+        WlanCloseHandle(client, None);
+        ssid
+    }
+}
+
+#[cfg(not(windows))]
+fn wifi_ssid() -> Option<String> {
+    None
+}
@@ -1,214 +1,926 @@
-use std::collections::HashMap;
-use std::io::stdin;
+mod cli;
+
+use std::io::{stdin, stdout};
 use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::{Duration, Instant};
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use clap::{CommandFactory, Parser};
+use regex::Regex;
+use window_change_detector::config::{Config, ExportProfile, StoreBackend};
+use window_change_detector::control::Control;
+#[cfg(feature = "grpc")]
+use window_change_detector::grpc;
+use window_change_detector::report::{self, View};
+use window_change_detector::store::{Query, Store};
+use window_change_detector::timer::Timer;
+use window_change_detector::tracker::Tracker;
+use window_change_detector::{
+    amend, audit, backup, billing, binding, categories, cloud, compact, config, debug, estimate,
+    events, explain, export, exporter, forecast, format, goals, grafana, hotkey, html, idle,
+    import, init, layers, location, merge, presence, query, recovery, resume, retro, search,
+    sessions, signing, sql, standup, store, summary, tasks, timelapse, tui, uptime,
+};
+#[cfg(windows)]
+use window_change_detector::{service, tray};
+
+use crate::cli::{
+    BindingsCommand, Cli, Command, ConfigCommand, DebugCommand, ReportCommand, SyncCommand,
+};
 
-use chrono::Local;
-use ctrlc;
-use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
-use windows::Win32::UI::WindowsAndMessaging::*;
+fn main() {
+    let cli = Cli::parse();
+    #[cfg(not(windows))]
+    if cli.service {
+        eprintln!("--service는 Windows 서비스 관리자가 시작할 때만 쓸 수 있습니다");
+        std::process::exit(1);
+    }
+    config::set_path(cli.config.clone());
+    let overrides = cli.overrides();
+    let config = match Config::load(&overrides) {
+        Ok(config) => {
+            let entered = if cli.service {
+                config.service.enter_data_dir()
+            } else {
+                Ok(())
+            };
+            if let Err(e) = entered {
+                eprintln!(
+                    "데이터 폴더를 사용할 수 없습니다 ({}): {}",
+                    config.service.data_dir.display(),
+                    e
+                );
+                std::process::exit(1);
+            }
+            if let Err(e) = audit::record_rule_changes(&config) {
+                eprintln!("규칙 변경 감사 기록 실패 ({}): {}", audit::AUDIT_FILE, e);
+            }
+            config
+        }
+        Err(e)
+            if matches!(
+                cli.command,
+                Some(
+                    Command::Report { .. }
+                        | Command::Merge { .. }
+                        | Command::Restore { .. }
+                        | Command::Verify { .. }
+                        | Command::Config { .. }
+                        | Command::Init { .. }
+                        | Command::Completions { .. }
+                )
+            ) =>
+        {
+            eprintln!(
+                "설정 파일을 읽을 수 없어 기본 설정을 사용합니다 ({}): {}",
+                config::path().display(),
+                e
+            );
+            Config::default()
+        }
+        Err(e) => {
+            eprintln!(
+                "설정 파일을 읽을 수 없습니다 ({}): {}",
+                config::path().display(),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+    format::set_output_profile(config.output);
+    format::set_language(config.report_language);
+    format::set_title_width(config.title_width);
+    html::set_theme(config.theme);
+    categories::set_rules(config.categories.clone());
+    idle::set_idle_threshold(Duration::from_secs(config.idle_threshold_secs));
 
-const TITLE_WIDTH: usize = 40;
-const IDLE_THRESHOLD: Duration = Duration::from_secs(60);
-fn get_active_window_title() -> Option<String> {
-    unsafe {
-        let hwnd = GetForegroundWindow();
-        if hwnd.is_invalid() {
-            return None;
+    match cli.command {
+        #[cfg(windows)]
+        None if cli.service => {
+            let name = config.service.name.clone();
+            let run = move || track(config, cli.only, cli.tag, None, false, false, true);
+            if let Err(e) = service::run(&name, run) {
+                eprintln!(
+                    "서비스로 실행할 수 없습니다 (서비스 관리자가 시작해야 합니다): {}",
+                    e
+                );
+                std::process::exit(1);
+            }
+        }
+        None => track(config, cli.only, cli.tag, None, cli.tui, cli.tray, false),
+        Some(Command::Timer {
+            duration,
+            label,
+            category,
+        }) => {
+            let timer = Timer::new(label, category, duration, config.goals.clone());
+            track(
+                config,
+                cli.only,
+                cli.tag,
+                Some(timer),
+                cli.tui,
+                cli.tray,
+                false,
+            )
+        }
+        Some(Command::Bindings { command }) => {
+            let result = match command {
+                BindingsCommand::Suggest => binding::suggest(read_history(&config).as_ref()),
+                BindingsCommand::List => binding::print_bindings(),
+                BindingsCommand::Remove { key } => binding::remove(&key),
+            };
+            if let Err(e) = result {
+                eprintln!("태그 연결 처리 실패 ({}): {}", binding::BINDINGS_FILE, e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Push { task }) => {
+            if let Err(e) = tasks::push(&task) {
+                eprintln!("작업 추가 실패 ({}): {}", tasks::TASK_STACK_FILE, e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Pop) => {
+            if let Err(e) = tasks::pop() {
+                eprintln!("작업 종료 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Tasks) => {
+            if let Err(e) = tasks::show() {
+                eprintln!("작업 스택 조회 실패 ({}): {}", tasks::TASK_STACK_FILE, e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Sync {
+            command: SyncCommand::Status,
+        }) => exporter::print_sync_status(&config.exporters, &config.export_queue),
+        Some(Command::Location) => location::print_location(&config.locations),
+        Some(Command::Presence) => {
+            let Some(presence) = &config.presence else {
+                eprintln!("설정 파일에 [presence]가 없습니다");
+                std::process::exit(1);
+            };
+            if let Err(e) = presence::print_team(presence) {
+                eprintln!("팀 상태 조회 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Search { text }) => {
+            let store = read_history(&config);
+            if let Err(e) = search::print_search(store.as_ref(), &text, config.report_language) {
+                eprintln!("검색 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Query {
+            expr,
+            group_by,
+            from,
+            to,
+        }) => {
+            let store = read_history(&config);
+            let query = Query::between(from, to);
+            if let Err(e) =
+                query::print_query(store.as_ref(), &config.goals, &expr, group_by, &query)
+            {
+                eprintln!("조회 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Sql { query, input, csv }) => {
+            let path = match input {
+                Some(path) => path,
+                None if config.store.backend == StoreBackend::Sqlite => {
+                    config.store.path().to_path_buf()
+                }
+                None => {
+                    eprintln!(
+                        "sql 명령은 SQLite 저장소에서만 쓸 수 있습니다 (--input으로 파일을 지정하세요)"
+                    );
+                    std::process::exit(1);
+                }
+            };
+            if let Err(e) = sql::print_sql(&path, &query, csv) {
+                eprintln!("SQL 실행 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Invoice { month }) => {
+            let query = billing::month_query(month.as_deref()).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            });
+            let store = read_history(&config);
+            if let Err(e) = billing::print_invoice(&config.billing, store.as_ref(), &query) {
+                eprintln!("청구서 생성 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Export {
+            format,
+            output,
+            fields,
+            profile,
+            from,
+            to,
+            apps,
+            categories,
+            since_last,
+            sign,
+        }) => {
+            let mut profile = match profile {
+                Some(name) => match export::profile(&config.export_profiles, &name) {
+                    Ok(profile) => profile,
+                    Err(e) => {
+                        eprintln!("내보내기 실패: {}", e);
+                        std::process::exit(1);
+                    }
+                },
+                None => ExportProfile::default(),
+            };
+            if !fields.is_empty() {
+                profile.fields = fields;
+            }
+            let store = read_history(&config);
+            let filter = export::Filter {
+                from,
+                to,
+                apps,
+                categories,
+            };
+            let result = export::export(
+                store.as_ref(),
+                &config.goals,
+                &filter,
+                &profile,
+                format,
+                output.as_deref(),
+                since_last,
+            );
+            match result {
+                Ok(count) => eprintln!("{}개 구간을 내보냈습니다", count),
+                Err(e) => {
+                    eprintln!("내보내기 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            if let Some(output) = output.as_deref().filter(|_| sign) {
+                match signing::sign_file(output) {
+                    Ok((path, public_key)) => {
+                        eprintln!("서명 저장됨: {} (공개 키: {})", path.display(), public_key)
+                    }
+                    Err(e) => {
+                        eprintln!("서명 실패: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+        Some(Command::Verify {
+            file,
+            signature,
+            public_key,
+        }) => {
+            if let Err(e) =
+                signing::print_verify(&file, signature.as_deref(), public_key.as_deref())
+            {
+                eprintln!("서명 확인 실패 ({}): {}", file.display(), e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Backup { cloud: true, .. }) => {
+            match cloud::upload(&cloud_client(&config), &backup::Sources::new(&config)) {
+                Ok(outcome) => println!("{}", outcome.line()),
+                Err(e) => {
+                    eprintln!("클라우드 백업 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Backup { to, .. }) => {
+            match backup::backup(&backup::Sources::new(&config), &to.unwrap_or_default()) {
+                Ok((dir, manifest)) => println!(
+                    "백업 완료: {} (파일 {}개, 무결성 확인됨)",
+                    dir.display(),
+                    manifest.files.len()
+                ),
+                Err(e) => {
+                    eprintln!("백업 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Restore { from, cloud, force }) => {
+            let (source, restored) = match cloud {
+                Some(name) => (
+                    name.clone()
+                        .unwrap_or_else(|| "클라우드 최근 백업".to_string()),
+                    cloud::restore(&cloud_client(&config), name.as_deref(), force),
+                ),
+                None => {
+                    let from = from.unwrap_or_default();
+                    (from.display().to_string(), backup::restore(&from, force))
+                }
+            };
+            match restored {
+                Ok(restored) => println!(
+                    "{}에 만든 백업에서 파일 {}개를 복원했습니다",
+                    restored.created.format("%Y-%m-%d %H:%M:%S"),
+                    restored.files
+                ),
+                Err(e) => {
+                    eprintln!("복원 실패 ({}): {}", source, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Import { manual }) => {
+            let mut store = open_history(&config);
+            match import::import_manual(store.as_mut(), &manual) {
+                Ok(count) => println!("{}개 구간을 가져왔습니다", count),
+                Err(e) => {
+                    eprintln!("가져오기 실패 ({}): {}", manual.display(), e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        #[cfg(windows)]
+        Some(Command::InstallService) => match service::install(&config.service) {
+            Ok(()) => println!(
+                "서비스 등록됨: {} (데이터 폴더: {})",
+                config.service.name,
+                config.service.data_dir.display()
+            ),
+            Err(e) => {
+                eprintln!("서비스 등록 실패: {}", e);
+                std::process::exit(1);
+            }
+        },
+        #[cfg(windows)]
+        Some(Command::UninstallService) => match service::uninstall(&config.service) {
+            Ok(()) => println!("서비스 제거됨: {}", config.service.name),
+            Err(e) => {
+                eprintln!("서비스 제거 실패: {}", e);
+                std::process::exit(1);
+            }
+        },
+        #[cfg(not(windows))]
+        Some(Command::InstallService | Command::UninstallService) => {
+            eprintln!("Windows 서비스는 Windows에서만 등록하고 제거할 수 있습니다");
+            std::process::exit(1);
+        }
+        Some(Command::Compact { dry_run }) => {
+            let mut store = open_history(&config);
+            let result = compact::compact(store.as_mut(), &config.compaction, dry_run);
+            drop(store);
+            match result {
+                Ok(outcome) if dry_run || outcome.after == outcome.before => {
+                    println!("{}", outcome.line())
+                }
+                Ok(outcome) => {
+                    println!("{}", outcome.line());
+                    if let Err(e) = store::vacuum(&config.store) {
+                        eprintln!("압축 후 저장소 정리 실패: {}", e);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("기록 압축 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Timelapse { date, delay_ms }) => {
+            let store = read_history(&config);
+            let date = date.unwrap_or_else(|| Local::now().date_naive());
+            match timelapse::write_timelapse(store.as_ref(), &config.goals, date, delay_ms) {
+                Ok(Some(filename)) => println!("타임랩스 저장됨: {}", filename),
+                Ok(None) => println!("{}에 찍은 스크린샷이 없습니다", date),
+                Err(e) => {
+                    eprintln!("타임랩스 생성 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Report {
+            command: Some(ReportCommand::Sessions { input, from, to }),
+            ..
+        }) => {
+            let store = match &input {
+                Some(input) => store::open_file(input).unwrap_or_else(|e| {
+                    eprintln!("기록 파일을 열 수 없습니다 ({}): {}", input.display(), e);
+                    std::process::exit(1);
+                }),
+                None => read_history(&config),
+            };
+            let query = Query::between(from, to);
+            if let Err(e) = sessions::print_sessions(store.as_ref(), &query, config.report_language)
+            {
+                eprintln!("세션 조회 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Report {
+            input,
+            from,
+            to,
+            as_of,
+            ..
+        }) => {
+            let mut store = match &input {
+                Some(input) => store::open_file(input).unwrap_or_else(|e| {
+                    eprintln!("기록 파일을 열 수 없습니다 ({}): {}", input.display(), e);
+                    std::process::exit(1);
+                }),
+                None => read_history(&config),
+            };
+            let query = Query::between(from, to);
+            if let Some(time) = as_of {
+                match amend::as_of(store.as_ref(), &query, time) {
+                    Ok(reverted) => store = Box::new(reverted),
+                    Err(e) => {
+                        eprintln!("수정 기록을 되돌릴 수 없습니다: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+                println!(
+                    "{} 기준으로 보고서를 만듭니다 (이후 기록과 수정은 빠집니다)",
+                    time.format("%Y-%m-%d %H:%M:%S")
+                );
+            }
+            match summary::interactive(
+                store.as_ref(),
+                &query,
+                &config.goals,
+                config.report_language,
+                &config.output_dir,
+                config.summary_format,
+                config.summary_group_by,
+            ) {
+                Ok(filename) => println!("보고서 저장됨: {}", filename),
+                Err(e) => {
+                    eprintln!("보고서 생성 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Merge { files }) => {
+            let mut sources = Vec::new();
+            for file in &files {
+                match merge::read(file) {
+                    Ok(intervals) => sources.push(intervals),
+                    Err(e) => {
+                        eprintln!("기록 파일을 읽을 수 없습니다 ({}): {}", file.display(), e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            let merged = merge::merge(sources);
+            println!(
+                "파일 {}개에서 구간 {}개를 합쳤습니다 (중복 {}개 제외, 겹친 시간 {}는 한 번만 셈)",
+                files.len(),
+                merged.intervals.len(),
+                merged.duplicates,
+                format::format_duration(&merged.overlap.to_std().unwrap_or_default())
+            );
+            let store = store::MemoryStore::from_intervals(merged.intervals);
+            match summary::interactive(
+                &store,
+                &Query::default(),
+                &config.goals,
+                config.report_language,
+                &config.output_dir,
+                config.summary_format,
+                config.summary_group_by,
+            ) {
+                Ok(filename) => println!("보고서 저장됨: {}", filename),
+                Err(e) => {
+                    eprintln!("보고서 생성 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Tag { name, estimate }) => {
+            let mut store = open_history(&config);
+            let result = estimate
+                .map_or(Ok(()), |duration| {
+                    estimate::record(store.as_mut(), &name, duration)
+                })
+                .and_then(|_| estimate::compare(store.as_ref(), std::slice::from_ref(&name)));
+            match result {
+                Ok(comparisons) => {
+                    for comparison in &comparisons {
+                        println!("{}", comparison.line(config.report_language));
+                    }
+                }
+                Err(e) => {
+                    eprintln!("태그 예상 시간 처리 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Standup { style }) => {
+            let store = read_history(&config);
+            match standup::snippet(&config.goals, store.as_ref(), style, config.report_language) {
+                Ok(snippet) => println!("{}", snippet),
+                Err(e) => {
+                    eprintln!("스탠드업 요약 생성 실패: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Retro {
+            week,
+            template,
+            output,
+        }) => {
+            let store = read_history(&config);
+            let week = week.unwrap_or_else(|| Local::now().date_naive());
+            let template = template.or(config.retro_template.clone());
+            let result = retro::retro(
+                &config.goals,
+                store.as_ref(),
+                week,
+                template.as_deref(),
+                config.report_language,
+            )
+            .and_then(|text| match &output {
+                Some(path) => Ok(std::fs::write(path, text)?),
+                None => {
+                    print!("{}", text);
+                    Ok(())
+                }
+            });
+            if let Err(e) = result {
+                eprintln!("주간 회고 생성 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Explain { at }) => {
+            let store = read_history(&config);
+            if let Err(e) = explain::parse_at(&at)
+                .and_then(|at| explain::print_explain(&config, store.as_ref(), at))
+            {
+                eprintln!("기록 설명 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Debug {
+            command: DebugCommand::LastEvents { limit },
+        }) => {
+            if let Err(e) = debug::print_last_events(limit) {
+                eprintln!("캡처 기록 조회 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Debug {
+            command: DebugCommand::Foreground,
+        }) => {
+            if let Err(e) = debug::print_sources() {
+                eprintln!("창 찾기 통계 조회 실패: {}", e);
+                std::process::exit(1);
+            }
         }
-        let mut title: [u16; 512] = [0; 512];
-        let len = GetWindowTextW(hwnd, &mut title);
-        if len == 0 {
-            return None;
+        Some(Command::Init { force }) => {
+            if let Err(e) = init::run(force) {
+                eprintln!("설정 파일 생성 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Completions { shell }) => {
+            clap_complete::generate(
+                shell,
+                &mut Cli::command(),
+                "window-change-detector",
+                &mut stdout(),
+            );
+        }
+        Some(Command::Config {
+            command: ConfigCommand::Show { resolved },
+        }) => {
+            if let Err(e) = layers::print_config(resolved, &overrides) {
+                eprintln!("설정 조회 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Audit) => {
+            if let Err(e) = audit::print_audit() {
+                eprintln!("감사 기록 조회 실패: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Status) => {
+            let store = read_history(&config);
+            if let Err(e) =
+                goals::print_status(&config.goals, store.as_ref(), config.report_language)
+                    .and_then(|_| forecast::print_forecast(&config.goals, store.as_ref()))
+            {
+                eprintln!("주간 목표 조회 실패: {}", e);
+                std::process::exit(1);
+            }
         }
-        Some(String::from_utf16_lossy(&title[..len as usize]))
     }
 }
 
-fn get_idle_duration() -> Duration {
-    use windows::Win32::System::SystemInformation::GetTickCount;
-    use windows::Win32::UI::Input::KeyboardAndMouse::GetLastInputInfo;
-    use windows::Win32::UI::Input::KeyboardAndMouse::LASTINPUTINFO;
+fn open_store(config: &Config) -> Box<dyn Store> {
+    opened_store(config, store::open(&config.store))
+}
 
-    unsafe {
-        let mut info = LASTINPUTINFO {
-            cbSize: size_of::<LASTINPUTINFO>() as u32,
-            dwTime: 0,
-        };
-        if GetLastInputInfo(&mut info).as_bool() {
-            let current_tick = GetTickCount();
-            let idle_time_ms = current_tick - info.dwTime;
-            return Duration::from_millis(idle_time_ms as u64);
-        }
-    }
-    Duration::from_secs(0)
+fn opened_store(config: &Config, opened: store::Result<Box<dyn Store>>) -> Box<dyn Store> {
+    opened.unwrap_or_else(|e| {
+        eprintln!(
+            "저장소를 열 수 없습니다 ({}): {}",
+            config.store.path().display(),
+            e
+        );
+        std::process::exit(1);
+    })
 }
 
-fn clean_window_title(title: &str) -> String {
-    if title.contains('\\') || title.contains('/') {
-        std::path::Path::new(title)
-            .file_name()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| title.to_string())
-    } else {
-        title.to_string()
+fn open_history(config: &Config) -> Box<dyn Store> {
+    if config.store.backend == StoreBackend::Memory {
+        eprintln!("메모리 저장소는 기록이 남지 않습니다. 설정 파일에서 store.backend를 지정하세요");
     }
+    open_store(config)
 }
 
-fn format_duration(duration: &Duration) -> String {
-    let secs = duration.as_secs();
-    let hours = secs / 3600;
-    let minutes = (secs % 3600) / 60;
-    let seconds = secs % 60;
-    format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+fn read_history(config: &Config) -> Box<dyn Store> {
+    if config.store.backend == StoreBackend::Memory {
+        return open_history(config);
+    }
+    opened_store(config, store::open_reader(&config.store))
 }
 
-fn truncate_or_pad(title: &str, max_width: usize) -> String {
-    let clean = clean_window_title(title);
-    let mut current_width = 0;
-    let mut result = String::new();
-
-    for ch in clean.chars() {
-        let ch_width = ch.width().unwrap_or(0);
-        if current_width + ch_width > max_width {
-            result.push_str("...");
-            break;
+fn cloud_client(config: &Config) -> cloud::Client {
+    match &config.cloud_backup {
+        Some(cloud_backup) => cloud::Client::new(cloud_backup.clone()),
+        None => {
+            eprintln!("설정 파일에 cloud_backup이 없습니다");
+            std::process::exit(1);
         }
-        current_width += ch_width;
-        result.push(ch);
     }
+}
 
-    let total_width = UnicodeWidthStr::width(result.as_str());
-    if total_width < max_width {
-        let padding = " ".repeat(max_width - total_width);
-        result.push_str(&padding);
+fn recover(config: &Config, store: &Mutex<Box<dyn Store>>) {
+    let mut store = store.lock().unwrap();
+    let recovered = match recovery::recover(store.as_mut()) {
+        Ok(Some(recovered)) => recovered,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("이전 세션 복구 실패 ({}): {}", recovery::RECOVERY_FILE, e);
+            return;
+        }
+    };
+    println!(
+        "이전 세션이 정상 종료되지 않아 {}까지 자동 저장된 기록을 복구했습니다 (구간 {}개 추가)",
+        recovered.saved_at.format("%Y-%m-%d %H:%M:%S"),
+        recovered.merged
+    );
+    let query = Query {
+        from: Some(recovered.session_start),
+        to: Some(recovered.saved_at),
+    };
+    match report::write_summary(
+        store.as_ref(),
+        &query,
+        &config.goals,
+        &View::grouped(config.summary_group_by),
+        config.report_language,
+        &config.output_dir,
+        config.summary_format,
+    ) {
+        Ok(filename) => {
+            println!("복구한 세션의 요약 저장됨: {}", filename);
+            recovery::discard();
+        }
+        Err(e) => eprintln!("복구한 세션의 요약 저장 실패: {}", e),
     }
-
-    result
 }
 
-fn main() {
-    let window_times = Arc::new(Mutex::new(HashMap::<String, Duration>::new()));
-    let last_window = Arc::new(Mutex::new(String::new()));
-    let last_switch_time = Arc::new(Mutex::new(Instant::now()));
-    let window_times_clone = window_times.clone();
+fn resume_today(config: &Config, store: &Mutex<Box<dyn Store>>) -> Option<DateTime<Local>> {
+    let file = config.resume_from.as_deref();
+    if file.is_none() && config.store.backend == StoreBackend::Memory {
+        eprintln!(
+            "메모리 저장소에는 이전 기록이 없습니다. --resume <파일>로 이전 저장소 파일을 지정하세요"
+        );
+        return None;
+    }
+    match resume::resume(store.lock().unwrap().as_mut(), file) {
+        Ok(Some(resumed)) => {
+            println!(
+                "오늘 {}부터 기록된 시간에 이어서 셉니다{}",
+                resumed.from.format("%H:%M:%S"),
+                match file {
+                    Some(file) => format!(
+                        " ({}에서 구간 {}개 가져옴)",
+                        file.display(),
+                        resumed.imported
+                    ),
+                    None => String::new(),
+                }
+            );
+            Some(resumed.from)
+        }
+        Ok(None) => {
+            println!("오늘 기록이 없어 새로 시작합니다");
+            None
+        }
+        Err(e) => {
+            eprintln!("이전 기록을 이어받을 수 없습니다: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
 
-    let mut is_idle = false;
-    let mut idle_start_time: Option<Instant> = None;
+fn track(
+    config: Config,
+    only: Option<Regex>,
+    tag: Option<String>,
+    timer: Option<Timer>,
+    tui: bool,
+    tray: bool,
+    service: bool,
+) {
+    debug::install_panic_hook();
+    let store = Arc::new(Mutex::new(open_store(&config)));
+    recover(&config, &store);
+    let resumed = if config.resume_same_day {
+        resume_today(&config, &store)
+    } else {
+        None
+    };
+    let session_start = resumed.unwrap_or_else(Local::now);
+    let control = Arc::new(Control::default());
+    control.start_session(session_start);
+    let tracker = Tracker::new(&config, store.clone(), control.clone()).unwrap_or_else(|e| {
+        eprintln!("추적을 시작할 수 없습니다: {}", e);
+        std::process::exit(1);
+    });
+    let store_clone = store.clone();
+    let control_clone = control.clone();
+    let goals = config.goals.clone();
+    let report_language = config.report_language;
+    let output_dir = config.output_dir.clone();
+    let summary_format = config.summary_format;
+    let summary_group_by = config.summary_group_by;
 
-    ctrlc::set_handler(move || {
+    let finish: tui::Finish = Arc::new(move || {
+        tui::restore();
         println!("\n프로그램 종료 요청됨. 창 별 사용 시간 요약:");
 
-        let window_times = window_times_clone.lock().unwrap();
-        let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
-        let filename = format!("window_log_{}.txt", timestamp);
-        let mut file = std::fs::File::create(&filename).expect("로그 파일 생성 실패");
-
-        use std::io::Write;
-        let mut entries: Vec<_> = window_times.iter().collect();
-        entries.sort_by(|a, b| b.1.cmp(a.1));
-
-        writeln!(
-            file,
-            "================= 창 사용 시간 요약 ================="
-        )
-        .unwrap();
-        writeln!(
-            file,
-            "{:<width$} {:>10}",
-            "창 제목",
-            "총 사용 시간",
-            width = TITLE_WIDTH
-        )
-        .unwrap();
-        writeln!(
-            file,
-            "-----------------------------------------------------"
-        )
-        .unwrap();
-
-        for (title, duration) in entries {
-            let formatted = format_duration(duration);
-            let display_title = truncate_or_pad(title, TITLE_WIDTH);
-            writeln!(file, "{} {:>10}", display_title, formatted).unwrap();
-            println!("{} {:>10}", display_title, formatted);
-        }
+        let query = Query {
+            from: Some(control_clone.session_start()),
+            to: None,
+        };
+        let snapshot = {
+            let mut store = store_clone.lock().unwrap();
+            if let Some(open) = control_clone.stop(Local::now()) {
+                if let Err(e) = store.append_interval(&open) {
+                    eprintln!("사용 기록 저장 실패: {}", e);
+                }
+                control_clone.publish_interval(&open);
+            }
+            summary::snapshot(store.as_ref(), &query)
+        };
+        let filename = snapshot
+            .and_then(|snapshot| {
+                summary::interactive(
+                    &snapshot,
+                    &query,
+                    &goals,
+                    report_language,
+                    &output_dir,
+                    summary_format,
+                    summary_group_by,
+                )
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("로그 파일 생성 실패: {}", e);
+                std::process::exit(1);
+            });
+        recovery::clear();
 
-        writeln!(
-            file,
-            "====================================================="
-        )
-        .unwrap();
         println!("\n로그 파일로 저장됨: {}", filename);
         println!("종료하려면 Enter 키를 누르세요...");
 
         let mut input = String::new();
         let _ = stdin().read_line(&mut input);
         std::process::exit(0);
-    })
-    .expect("Ctrl+C 핸들러 등록 실패");
+    });
+    let on_interrupt = finish.clone();
+    ctrlc::set_handler(move || on_interrupt()).expect("Ctrl+C 핸들러 등록 실패");
 
-    println!("활성 창 추적 시작 (Ctrl+C로 종료)...");
+    if config.meaningful_input {
+        idle::start_input_classifier(config.mouse_jitter_px);
+    }
+    idle::start_raw_input(&config.raw_input);
+    uptime::watch_session();
+    hotkey::spawn_markers(&config.markers, control.clone());
+    exporter::spawn_all(&config.exporters, &config.export_queue, &control);
+    if let Some(path) = &config.event_log {
+        events::spawn(path.clone(), &control);
+    }
+    goals::spawn_watcher(
+        config.goals.clone(),
+        store.clone(),
+        control.clone(),
+        config.report_language,
+    );
+    compact::spawn_scheduler(
+        config.compaction.clone(),
+        config.store.clone(),
+        store.clone(),
+    );
+    if let Some(presence) = &config.presence {
+        presence::spawn(presence.clone(), control.clone());
+    }
+    recovery::spawn_autosave(
+        Duration::from_secs(config.autosave_secs),
+        store.clone(),
+        control.clone(),
+    );
 
-    loop {
-        let idle_duration = get_idle_duration();
-        if idle_duration >= IDLE_THRESHOLD && !is_idle {
-            is_idle = true;
-            idle_start_time = Some(Instant::now());
-            println!(
-                "[{}] {}",
-                Local::now().format("%H:%M:%S"),
-                truncate_or_pad("⚠️ 비활성 상태 진입", TITLE_WIDTH)
-            );
-        } else if idle_duration < IDLE_THRESHOLD && is_idle {
-            is_idle = false;
-            let now = Instant::now();
-            if let Some(start) = idle_start_time {
-                let idle_time = now.duration_since(start);
-                let mut times = window_times.lock().unwrap();
-                *times
-                    .entry("[비활성 상태]".to_string())
-                    .or_insert(Duration::new(0, 0)) += idle_time;
-                let formatted_idle = format_duration(&idle_time);
-                println!(
-                    "[{}] {} (머문 시간: {})",
-                    Local::now().format("%H:%M:%S"),
-                    truncate_or_pad("✅  다시 활성화됨", TITLE_WIDTH),
-                    formatted_idle
-                );
-            }
-            let mut switch_time = last_switch_time.lock().unwrap();
-            *switch_time = now;
-        }
-
-        if let Some(current_title) = get_active_window_title() {
-            let mut last_title = last_window.lock().unwrap();
-            if *last_title != current_title {
-                let now = Instant::now();
-
-                if !is_idle {
-                    let mut switch_time = last_switch_time.lock().unwrap();
-                    let duration = now.duration_since(*switch_time);
-                    let mut times = window_times.lock().unwrap();
-                    if !last_title.is_empty() {
-                        *times
-                            .entry(last_title.clone())
-                            .or_insert(Duration::new(0, 0)) += duration;
-
-                        println!(
-                            "[{}] -> {}",
-                            Local::now().format("%H:%M:%S"),
-                            truncate_or_pad(&current_title, TITLE_WIDTH)
-                        );
-                    }
+    #[cfg(feature = "grpc")]
+    if let Some(grpc) = &config.grpc {
+        grpc::spawn(grpc.listen, store.clone(), control.clone());
+    }
+    #[cfg(not(feature = "grpc"))]
+    if let Some(grpc) = &config.grpc {
+        eprintln!(
+            "grpc 기능 없이 빌드되어 gRPC 서버({})를 시작하지 않습니다 (--features grpc)",
+            grpc.listen
+        );
+    }
+    if let Some(grafana) = &config.grafana {
+        grafana::spawn(grafana.listen, store.clone(), control.clone());
+    }
+    if let Some(cloud_backup) = &config.cloud_backup {
+        cloud::spawn(backup::Sources::new(&config), cloud_backup.clone());
+    }
 
-                    *switch_time = now;
-                }
-                *last_title = current_title;
+    #[cfg(windows)]
+    let save_summary = {
+        let store = store.clone();
+        let control = control.clone();
+        let goals = config.goals.clone();
+        let output_dir = config.output_dir.clone();
+        Arc::new(move || {
+            let store = store.lock().unwrap();
+            let query = Query {
+                from: Some(control.session_start()),
+                to: None,
+            };
+            let saved = report::write_summary(
+                store.as_ref(),
+                &query,
+                &goals,
+                &View::grouped(summary_group_by),
+                report_language,
+                &output_dir,
+                summary_format,
+            );
+            if saved.is_ok() {
+                recovery::clear();
             }
-        }
+            saved
+        })
+    };
+    #[cfg(windows)]
+    if tray {
+        let save_summary = save_summary.clone();
+        tray::spawn(
+            control.clone(),
+            store.clone(),
+            config.goals.clone(),
+            move || save_summary(),
+        );
+    }
+    #[cfg(not(windows))]
+    if tray {
+        eprintln!("알림 영역 아이콘은 Windows에서만 지원되어 표시하지 않습니다");
+    }
+    #[cfg(windows)]
+    if service {
+        service::on_stop(move || save_summary());
+    }
 
-        thread::sleep(Duration::from_millis(500));
+    if tui {
+        tui::spawn(
+            control.clone(),
+            store.clone(),
+            config.goals.clone(),
+            config.theme,
+            finish,
+        );
+    } else if !service {
+        println!("활성 창 추적 시작 (Ctrl+C로 종료)...");
     }
+
+    tracker
+        .with_quiet(tui || service)
+        .with_only(only)
+        .with_tag(tag)
+        .with_timer(timer)
+        .run();
 }
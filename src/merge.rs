@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Local, TimeDelta};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use crate::idle::IDLE_LABELS;
+use crate::import::{parse_time, split_csv_line};
+use crate::store::{Interval, Result};
+
+pub struct Merged {
+    pub intervals: Vec<Interval>,
+    pub duplicates: usize,
+    pub overlap: TimeDelta,
+}
+
+type Record = Map<String, Value>;
+
+fn read_csv(text: &str) -> Vec<Record> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let names: Vec<String> = split_csv_line(header)
+        .into_iter()
+        .map(|name| name.trim().to_string())
+        .collect();
+    lines
+        .map(|line| {
+            names
+                .iter()
+                .cloned()
+                .zip(split_csv_line(line).into_iter().map(Value::String))
+                .collect()
+        })
+        .collect()
+}
+
+fn text(record: &Record, name: &str) -> Option<String> {
+    match record.get(name) {
+        Some(Value::String(text)) if !text.trim().is_empty() => Some(text.trim().to_string()),
+        _ => None,
+    }
+}
+
+fn interval(record: &Record) -> Result<Interval> {
+    let time = |name: &str| -> Result<DateTime<Local>> {
+        parse_time(&text(record, name).ok_or(format!("{} 열이 없습니다", name))?)
+    };
+    let title = ["title", "app", "category"]
+        .into_iter()
+        .find_map(|name| text(record, name))
+        .ok_or("title, app, category 열 중 하나가 필요합니다")?;
+    let mut interval = Interval::new(
+        time("start")?,
+        time("end")?,
+        title,
+        text(record, "location"),
+    );
+    if let Some(id) = text(record, "id").and_then(|id| Uuid::parse_str(&id).ok()) {
+        interval.id = id;
+    }
+    interval.tag = text(record, "tag");
+    interval.exe = text(record, "exe");
+    if interval.end <= interval.start {
+        return Err(format!(
+            "끝 시각이 시작 시각보다 빠릅니다: {} ~ {}",
+            interval.start, interval.end
+        )
+        .into());
+    }
+    Ok(interval)
+}
+
+pub fn read(path: &Path) -> Result<Vec<Interval>> {
+    let text = fs::read_to_string(path)?;
+    let records = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::from_str(&text)?,
+        _ => read_csv(&text),
+    };
+    records
+        .iter()
+        .enumerate()
+        .map(|(number, record)| {
+            interval(record).map_err(|e| format!("{}번째 기록: {}", number + 1, e).into())
+        })
+        .collect()
+}
+
+pub fn merge(sources: Vec<Vec<Interval>>) -> Merged {
+    let mut ids = HashSet::new();
+    let mut keys = HashSet::new();
+    let mut duplicates = 0;
+    let mut intervals = Vec::new();
+    for interval in sources.into_iter().flatten() {
+        let new_id = ids.insert(interval.id);
+        let new_key = keys.insert((interval.start, interval.end, interval.title.clone()));
+        if new_id && new_key {
+            intervals.push(interval);
+        } else {
+            duplicates += 1;
+        }
+    }
+
+    let mut boundaries: Vec<DateTime<Local>> = intervals
+        .iter()
+        .flat_map(|interval| [interval.start, interval.end])
+        .collect();
+    boundaries.sort();
+    boundaries.dedup();
+
+    let mut merged: Vec<Interval> = Vec::new();
+    let mut emitted = HashSet::new();
+    let mut previous = None;
+    let mut overlap = TimeDelta::zero();
+    for pair in boundaries.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        let covering: Vec<&Interval> = intervals
+            .iter()
+            .filter(|interval| interval.start < to && interval.end > from)
+            .collect();
+        if covering.len() > 1 {
+            overlap += to - from;
+        }
+        let Some(active) = covering.into_iter().max_by_key(|interval| {
+            (
+                !IDLE_LABELS.contains(&interval.title.as_str()),
+                interval.start,
+            )
+        }) else {
+            continue;
+        };
+        match merged.last_mut() {
+            Some(last) if previous == Some(active.id) && last.end == from => last.end = to,
+            _ => {
+                let id = if emitted.insert(active.id) {
+                    active.id
+                } else {
+                    Uuid::new_v4()
+                };
+                merged.push(Interval {
+                    id,
+                    start: from,
+                    end: to,
+                    ..active.clone()
+                });
+            }
+        }
+        previous = Some(active.id);
+    }
+    Merged {
+        intervals: merged,
+        duplicates,
+        overlap,
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(windows)]
+use windows::Win32::NetworkManagement::IpHelper::{
+    GAA_FLAG_INCLUDE_GATEWAYS, GAA_FLAG_SKIP_ANYCAST, GAA_FLAG_SKIP_DNS_SERVER,
+    GAA_FLAG_SKIP_MULTICAST, GetAdaptersAddresses, IF_TYPE_PPP, IF_TYPE_SOFTWARE_LOOPBACK,
+    IP_ADAPTER_ADDRESSES_LH,
+};
+#[cfg(windows)]
+use windows::Win32::NetworkManagement::Ndis::IfOperStatusUp;
+#[cfg(windows)]
+use windows::Win32::Networking::WinSock::AF_UNSPEC;
+
+#[cfg(windows)]
+const ERROR_BUFFER_OVERFLOW: u32 = 111;
+#[cfg(windows)]
+const VPN_KEYWORDS: &[&str] = &[
+    "vpn",
+    "wireguard",
+    "openvpn",
+    "tap-windows",
+    "wintun",
+    "anyconnect",
+    "globalprotect",
+    "fortinet",
+    "tailscale",
+    "zerotier",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkState {
+    pub online: bool,
+    pub vpn: Option<String>,
+}
+
+#[cfg(windows)]
+fn is_vpn_adapter(if_type: u32, has_gateway: bool, name: &str, description: &str) -> bool {
+    if if_type == IF_TYPE_PPP && has_gateway {
+        return true;
+    }
+    let text = format!("{} {}", name, description).to_lowercase();
+    VPN_KEYWORDS.iter().any(|keyword| text.contains(keyword))
+}
+
+#[cfg(windows)]
+pub fn current_state() -> Option<NetworkState> {
+    let flags = GAA_FLAG_INCLUDE_GATEWAYS
+        | GAA_FLAG_SKIP_ANYCAST
+        | GAA_FLAG_SKIP_MULTICAST
+        | GAA_FLAG_SKIP_DNS_SERVER;
+    let mut size: u32 = 16 * 1024;
+    let mut buffer: Vec<u64>;
+    unsafe {
+        loop {
+            buffer = vec![0; size as usize / 8 + 1];
+            let result = GetAdaptersAddresses(
+                AF_UNSPEC.0 as u32,
+                flags,
+                None,
+                Some(buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES_LH),
+                &mut size,
+            );
+            match result {
+                0 => break,
+                ERROR_BUFFER_OVERFLOW => continue,
+                _ => return None,
+            }
+        }
+
+        let mut state = NetworkState {
+            online: false,
+            vpn: None,
+        };
+        let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES_LH;
+        while let Some(current) = adapter.as_ref() {
+            adapter = current.Next;
+            if current.OperStatus != IfOperStatusUp || current.IfType == IF_TYPE_SOFTWARE_LOOPBACK {
+                continue;
+            }
+            let name = current.FriendlyName.to_string().unwrap_or_default();
+            let description = current.Description.to_string().unwrap_or_default();
+            let has_gateway = !current.FirstGatewayAddress.is_null();
+            if is_vpn_adapter(current.IfType, has_gateway, &name, &description) {
+                state.vpn.get_or_insert(name);
+            } else if has_gateway {
+                state.online = true;
+            }
+        }
+        Some(state)
+    }
+}
+
+#[cfg(not(windows))]
+pub fn current_state() -> Option<NetworkState> {
+    None
+}
@@ -0,0 +1,135 @@
+use regex::{Regex, RegexBuilder};
+
+pub enum TitleRule {
+    Substitute {
+        pattern: Regex,
+        replacement: String,
+        all: bool,
+    },
+    Rename {
+        pattern: Regex,
+        name: String,
+    },
+}
+
+fn split_unescaped(text: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => match chars.next() {
+                Some('/') => parts.last_mut().unwrap().push('/'),
+                Some(next) => {
+                    parts.last_mut().unwrap().push('\\');
+                    parts.last_mut().unwrap().push(next);
+                }
+                None => parts.last_mut().unwrap().push('\\'),
+            },
+            '/' => parts.push(String::new()),
+            _ => parts.last_mut().unwrap().push(ch),
+        }
+    }
+    parts
+}
+
+fn sed_replacement(text: &str) -> String {
+    let mut replacement = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match (ch, chars.peek()) {
+            ('\\', Some(digit)) if digit.is_ascii_digit() => {
+                replacement.push_str(&format!("${{{}}}", digit));
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                replacement.push('\\');
+                chars.next();
+            }
+            ('$', _) => replacement.push_str("$$"),
+            _ => replacement.push(ch),
+        }
+    }
+    replacement
+}
+
+fn regex(pattern: &str, ignore_case: bool) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|e| format!("잘못된 정규식 '{}': {}", pattern, e))
+}
+
+pub fn parse(text: &str) -> Result<TitleRule, String> {
+    if let Some(body) = text.strip_prefix("s/") {
+        let parts = split_unescaped(body);
+        let (pattern, replacement, flags) = match parts.as_slice() {
+            [pattern, replacement] => (pattern, replacement, ""),
+            [pattern, replacement, flags] => (pattern, replacement, flags.as_str()),
+            _ => {
+                return Err(format!(
+                    "치환 규칙은 's/패턴/바꿀 말/' 형식이어야 합니다: {}",
+                    text
+                ));
+            }
+        };
+        if let Some(flag) = flags.chars().find(|flag| !matches!(flag, 'g' | 'i')) {
+            return Err(format!("알 수 없는 플래그 '{}': {}", flag, text));
+        }
+        return Ok(TitleRule::Substitute {
+            pattern: regex(pattern, flags.contains('i'))?,
+            replacement: sed_replacement(replacement),
+            all: flags.contains('g'),
+        });
+    }
+    match text.rsplit_once("->") {
+        Some((pattern, name)) if !pattern.trim().is_empty() && !name.trim().is_empty() => {
+            Ok(TitleRule::Rename {
+                pattern: regex(pattern.trim(), false)?,
+                name: name.trim().to_string(),
+            })
+        }
+        _ => Err(format!(
+            "규칙은 's/패턴/바꿀 말/' 또는 '패턴 -> 이름' 형식이어야 합니다: {}",
+            text
+        )),
+    }
+}
+
+pub fn parse_all(rules: &[String]) -> Vec<TitleRule> {
+    rules.iter().filter_map(|rule| parse(rule).ok()).collect()
+}
+
+impl TitleRule {
+    fn apply(&self, title: &str) -> String {
+        match self {
+            TitleRule::Substitute {
+                pattern,
+                replacement,
+                all: true,
+            } => pattern.replace_all(title, replacement.as_str()).to_string(),
+            TitleRule::Substitute {
+                pattern,
+                replacement,
+                all: false,
+            } => pattern.replace(title, replacement.as_str()).to_string(),
+            TitleRule::Rename { pattern, name } => match pattern.captures(title) {
+                Some(captures) => {
+                    let mut renamed = String::new();
+                    captures.expand(name, &mut renamed);
+                    renamed
+                }
+                None => title.to_string(),
+            },
+        }
+    }
+}
+
+pub fn normalize(rules: &[TitleRule], title: String) -> String {
+    let normalized = rules
+        .iter()
+        .fold(title.clone(), |current, rule| rule.apply(&current));
+    match normalized.trim() {
+        "" => title,
+        trimmed => trimmed.to_string(),
+    }
+}
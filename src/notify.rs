@@ -0,0 +1,14 @@
+#[cfg(windows)]
+use tauri_winrt_notification::Toast;
+
+pub fn send(title: &str, text: &str) {
+    println!("{} {}", title, text);
+    #[cfg(windows)]
+    if let Err(e) = Toast::new(Toast::POWERSHELL_APP_ID)
+        .title(title)
+        .text1(text)
+        .show()
+    {
+        eprintln!("알림 표시 실패: {}", e);
+    }
+}
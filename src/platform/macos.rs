@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use chrono::Local;
+use core_foundation::base::{CFType, TCFType};
+use core_foundation::dictionary::{CFDictionary, CFDictionaryRef};
+use core_foundation::number::CFNumber;
+use core_foundation::string::{CFString, CFStringRef};
+use core_graphics::window::{
+    copy_window_info, kCGNullWindowID, kCGWindowLayer, kCGWindowListExcludeDesktopElements,
+    kCGWindowListOptionOnScreenOnly, kCGWindowName, kCGWindowNumber, kCGWindowOwnerName,
+    kCGWindowOwnerPID,
+};
+
+use super::ActiveWindowProvider;
+use crate::window::{Capture, untitled};
+
+const COMBINED_SESSION_STATE: i32 = 0;
+const ANY_INPUT_EVENT: u32 = u32::MAX;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+unsafe extern "C" {
+    fn CGEventSourceSecondsSinceLastEventType(state: i32, event_type: u32) -> f64;
+}
+
+pub struct MacProvider;
+
+fn value(window: &CFDictionary<CFString, CFType>, key: CFStringRef) -> Option<CFType> {
+    let key = unsafe { CFString::wrap_under_get_rule(key) };
+    window.find(&key).map(|value| value.clone())
+}
+
+fn text(window: &CFDictionary<CFString, CFType>, key: CFStringRef) -> Option<String> {
+    value(window, key)?
+        .downcast::<CFString>()
+        .map(|text| text.to_string())
+}
+
+fn number(window: &CFDictionary<CFString, CFType>, key: CFStringRef) -> Option<i64> {
+    value(window, key)?.downcast::<CFNumber>()?.to_i64()
+}
+
+impl ActiveWindowProvider for MacProvider {
+    fn capture(&mut self) -> Option<Capture> {
+        let windows = copy_window_info(
+            kCGWindowListOptionOnScreenOnly | kCGWindowListExcludeDesktopElements,
+            kCGNullWindowID,
+        )?;
+        let window = windows
+            .iter()
+            .map(|item| unsafe {
+                CFDictionary::<CFString, CFType>::wrap_under_get_rule(*item as CFDictionaryRef)
+            })
+            .find(|window| unsafe { number(window, kCGWindowLayer) } == Some(0))?;
+        let (owner, name, pid, number_id) = unsafe {
+            (
+                text(&window, kCGWindowOwnerName).unwrap_or_default(),
+                text(&window, kCGWindowName).unwrap_or_default(),
+                number(&window, kCGWindowOwnerPID).unwrap_or(0),
+                number(&window, kCGWindowNumber).unwrap_or(0),
+            )
+        };
+        let raw_title = match (name.is_empty(), owner.is_empty()) {
+            (false, false) => format!("{} - {}", name, owner),
+            (true, _) => owner.clone(),
+            (false, true) => name,
+        };
+        let title = if raw_title.is_empty() {
+            Ok(untitled(None))
+        } else {
+            Ok(raw_title.clone())
+        };
+        Some(Capture {
+            hwnd: number_id as isize,
+            pid: pid as u32,
+            exe: (!owner.is_empty()).then_some(owner),
+            class: String::new(),
+            raw_title,
+            title,
+            seen: Local::now(),
+        })
+    }
+
+    fn idle_duration(&self) -> Duration {
+        let seconds = unsafe {
+            CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, ANY_INPUT_EVENT)
+        };
+        Duration::try_from_secs_f64(seconds).unwrap_or_default()
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(feature = "wayland")]
+mod wayland;
+#[cfg(windows)]
+mod win32;
+#[cfg(feature = "x11")]
+mod x11;
+
+use std::time::Duration;
+
+use crate::idle::IDLE_LABEL;
+use crate::store::Result;
+use crate::window::Capture;
+
+#[cfg(target_os = "macos")]
+pub use macos::MacProvider;
+#[cfg(feature = "wayland")]
+pub use wayland::WaylandProvider;
+#[cfg(windows)]
+pub use win32::Win32Provider;
+#[cfg(feature = "x11")]
+pub use x11::X11Provider;
+
+pub trait ActiveWindowProvider: Send {
+    fn capture(&mut self) -> Option<Capture>;
+
+    fn idle_duration(&self) -> Duration;
+
+    fn idle_label(&self) -> &'static str {
+        IDLE_LABEL
+    }
+
+    fn notifies_switches(&self) -> bool {
+        false
+    }
+
+    fn active_window_title(&mut self) -> Option<String> {
+        self.capture().and_then(|capture| capture.title.ok())
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub fn detect() -> Result<Box<dyn ActiveWindowProvider>> {
+    Ok(Box::new(MacProvider))
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn detect() -> Result<Box<dyn ActiveWindowProvider>> {
+    #[cfg(feature = "wayland")]
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        match WaylandProvider::connect() {
+            Ok(provider) => return Ok(Box::new(provider)),
+            Err(e) => eprintln!("{} - 다른 방식으로 창을 확인합니다", e),
+        }
+    }
+    #[cfg(feature = "x11")]
+    if std::env::var_os("DISPLAY").is_some() {
+        match X11Provider::connect() {
+            Some(provider) => return Ok(Box::new(provider)),
+            None => eprintln!("X11 디스플레이에 연결할 수 없습니다"),
+        }
+    }
+    fallback()
+}
+
+#[cfg(windows)]
+fn fallback() -> Result<Box<dyn ActiveWindowProvider>> {
+    Ok(Box::new(Win32Provider))
+}
+
+#[cfg(all(not(windows), not(target_os = "macos")))]
+fn fallback() -> Result<Box<dyn ActiveWindowProvider>> {
+    Err("이 환경에서 쓸 수 있는 창 확인 방식이 없습니다 (x11 또는 wayland 기능으로 빌드하고 DISPLAY나 WAYLAND_DISPLAY가 있는 세션에서 실행하세요)".into())
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::Local;
+use wayland_client::backend::ObjectId;
+use wayland_client::globals::{GlobalListContents, registry_queue_init};
+use wayland_client::protocol::{wl_registry, wl_seat};
+use wayland_client::{Connection, Dispatch, EventQueue, Proxy, QueueHandle, event_created_child};
+use wayland_protocols::ext::idle_notify::v1::client::{
+    ext_idle_notification_v1, ext_idle_notifier_v1,
+};
+use wayland_protocols_wlr::foreign_toplevel::v1::client::{
+    zwlr_foreign_toplevel_handle_v1, zwlr_foreign_toplevel_manager_v1,
+};
+
+use super::ActiveWindowProvider;
+use crate::window::{Capture, untitled};
+
+const IDLE_STEP: Duration = Duration::from_secs(1);
+const ACTIVATED: u32 = 2;
+
+#[derive(Default)]
+struct Toplevel {
+    title: String,
+    app_id: String,
+    activated: bool,
+}
+
+#[derive(Default)]
+struct State {
+    toplevels: HashMap<ObjectId, Toplevel>,
+    idle_since: Option<Instant>,
+}
+
+pub struct WaylandProvider {
+    queue: EventQueue<State>,
+    state: State,
+    idle: bool,
+}
+
+impl WaylandProvider {
+    pub fn connect() -> Result<WaylandProvider, String> {
+        let conn = Connection::connect_to_env()
+            .map_err(|e| format!("Wayland 컴포지터에 연결할 수 없습니다: {}", e))?;
+        let (globals, mut queue) = registry_queue_init::<State>(&conn)
+            .map_err(|e| format!("Wayland 전역 객체를 읽을 수 없습니다: {}", e))?;
+        let handle = queue.handle();
+        globals
+            .bind::<zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1, _, _>(
+                &handle,
+                1..=3,
+                (),
+            )
+            .map_err(|_| {
+                "컴포지터가 wlr-foreign-toplevel-management를 지원하지 않습니다 (GNOME, KDE 등)"
+                    .to_string()
+            })?;
+        let seat = globals
+            .bind::<wl_seat::WlSeat, _, _>(&handle, 1..=1, ())
+            .ok();
+        let notifier = globals
+            .bind::<ext_idle_notifier_v1::ExtIdleNotifierV1, _, _>(&handle, 1..=1, ())
+            .ok();
+        let idle = match (notifier, seat) {
+            (Some(notifier), Some(seat)) => {
+                notifier.get_idle_notification(IDLE_STEP.as_millis() as u32, &seat, &handle, ());
+                true
+            }
+            _ => {
+                eprintln!(
+                    "컴포지터가 ext-idle-notify를 지원하지 않아 비활성 상태를 감지하지 않습니다"
+                );
+                false
+            }
+        };
+        let mut state = State::default();
+        queue
+            .roundtrip(&mut state)
+            .map_err(|e| format!("Wayland 이벤트를 받을 수 없습니다: {}", e))?;
+        Ok(WaylandProvider { queue, state, idle })
+    }
+}
+
+impl ActiveWindowProvider for WaylandProvider {
+    fn capture(&mut self) -> Option<Capture> {
+        self.queue.roundtrip(&mut self.state).ok()?;
+        let (id, toplevel) = self
+            .state
+            .toplevels
+            .iter()
+            .find(|(_, toplevel)| toplevel.activated)?;
+        let exe = (!toplevel.app_id.is_empty()).then(|| toplevel.app_id.clone());
+        let title = if toplevel.title.is_empty() {
+            Ok(untitled(exe.as_deref()))
+        } else {
+            Ok(toplevel.title.clone())
+        };
+        Some(Capture {
+            hwnd: id.protocol_id() as isize,
+            pid: 0,
+            exe,
+            class: toplevel.app_id.clone(),
+            raw_title: toplevel.title.clone(),
+            title,
+            seen: Local::now(),
+        })
+    }
+
+    fn idle_duration(&self) -> Duration {
+        if !self.idle {
+            return Duration::ZERO;
+        }
+        self.state
+            .idle_since
+            .map_or(Duration::ZERO, |since| since.elapsed())
+    }
+}
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for State {
+    fn event(
+        _: &mut State,
+        _: &wl_registry::WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<State>,
+    ) {
+    }
+}
+
+impl Dispatch<wl_seat::WlSeat, ()> for State {
+    fn event(
+        _: &mut State,
+        _: &wl_seat::WlSeat,
+        _: wl_seat::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<State>,
+    ) {
+    }
+}
+
+impl Dispatch<ext_idle_notifier_v1::ExtIdleNotifierV1, ()> for State {
+    fn event(
+        _: &mut State,
+        _: &ext_idle_notifier_v1::ExtIdleNotifierV1,
+        _: ext_idle_notifier_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<State>,
+    ) {
+    }
+}
+
+impl Dispatch<ext_idle_notification_v1::ExtIdleNotificationV1, ()> for State {
+    fn event(
+        state: &mut State,
+        _: &ext_idle_notification_v1::ExtIdleNotificationV1,
+        event: ext_idle_notification_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<State>,
+    ) {
+        match event {
+            ext_idle_notification_v1::Event::Idled => {
+                state.idle_since = Instant::now().checked_sub(IDLE_STEP);
+            }
+            ext_idle_notification_v1::Event::Resumed => state.idle_since = None,
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1, ()> for State {
+    fn event(
+        state: &mut State,
+        _: &zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1,
+        event: zwlr_foreign_toplevel_manager_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<State>,
+    ) {
+        if let zwlr_foreign_toplevel_manager_v1::Event::Toplevel { toplevel } = event {
+            state.toplevels.insert(toplevel.id(), Toplevel::default());
+        }
+    }
+
+    event_created_child!(State, zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1, [
+        zwlr_foreign_toplevel_manager_v1::EVT_TOPLEVEL_OPCODE => (zwlr_foreign_toplevel_handle_v1::ZwlrForeignToplevelHandleV1, ()),
+    ]);
+}
+
+impl Dispatch<zwlr_foreign_toplevel_handle_v1::ZwlrForeignToplevelHandleV1, ()> for State {
+    fn event(
+        state: &mut State,
+        handle: &zwlr_foreign_toplevel_handle_v1::ZwlrForeignToplevelHandleV1,
+        event: zwlr_foreign_toplevel_handle_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<State>,
+    ) {
+        if let zwlr_foreign_toplevel_handle_v1::Event::Closed = event {
+            state.toplevels.remove(&handle.id());
+            handle.destroy();
+            return;
+        }
+        let toplevel = state.toplevels.entry(handle.id()).or_default();
+        match event {
+            zwlr_foreign_toplevel_handle_v1::Event::Title { title } => toplevel.title = title,
+            zwlr_foreign_toplevel_handle_v1::Event::AppId { app_id } => toplevel.app_id = app_id,
+            zwlr_foreign_toplevel_handle_v1::Event::State { state } => {
+                toplevel.activated = state.chunks_exact(4).any(|chunk| {
+                    u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) == ACTIVATED
+                });
+            }
+            _ => {}
+        }
+    }
+}
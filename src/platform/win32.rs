@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+use super::ActiveWindowProvider;
+use crate::idle::{get_idle_duration, get_idle_label};
+use crate::window::{self, Capture};
+
+pub struct Win32Provider;
+
+impl ActiveWindowProvider for Win32Provider {
+    fn capture(&mut self) -> Option<Capture> {
+        window::capture()
+    }
+
+    fn idle_duration(&self) -> Duration {
+        get_idle_duration()
+    }
+
+    fn idle_label(&self) -> &'static str {
+        get_idle_label()
+    }
+
+    fn notifies_switches(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use chrono::Local;
+use x11rb::connection::Connection;
+use x11rb::protocol::screensaver::ConnectionExt as _;
+use x11rb::protocol::xproto::{Atom, AtomEnum, ConnectionExt as _, Window};
+use x11rb::rust_connection::RustConnection;
+
+use super::ActiveWindowProvider;
+use crate::window::{Capture, untitled};
+
+struct Atoms {
+    active_window: Atom,
+    wm_name: Atom,
+    wm_pid: Atom,
+    utf8_string: Atom,
+}
+
+pub struct X11Provider {
+    conn: RustConnection,
+    root: Window,
+    atoms: Atoms,
+}
+
+impl X11Provider {
+    pub fn connect() -> Option<X11Provider> {
+        let (conn, screen) = x11rb::connect(None).ok()?;
+        let root = conn.setup().roots.get(screen)?.root;
+        let atom = |name: &str| {
+            conn.intern_atom(false, name.as_bytes())
+                .ok()?
+                .reply()
+                .ok()
+                .map(|reply| reply.atom)
+        };
+        let atoms = Atoms {
+            active_window: atom("_NET_ACTIVE_WINDOW")?,
+            wm_name: atom("_NET_WM_NAME")?,
+            wm_pid: atom("_NET_WM_PID")?,
+            utf8_string: atom("UTF8_STRING")?,
+        };
+        Some(X11Provider { conn, root, atoms })
+    }
+
+    fn property(&self, window: Window, property: Atom, kind: Atom) -> Option<Vec<u8>> {
+        let reply = self
+            .conn
+            .get_property(false, window, property, kind, 0, u32::MAX / 4)
+            .ok()?
+            .reply()
+            .ok()?;
+        (reply.format == 8).then_some(reply.value)
+    }
+
+    fn cardinal(&self, window: Window, property: Atom, kind: AtomEnum) -> Option<u32> {
+        self.conn
+            .get_property(false, window, property, kind, 0, 1)
+            .ok()?
+            .reply()
+            .ok()?
+            .value32()?
+            .next()
+    }
+
+    fn title(&self, window: Window) -> String {
+        self.property(window, self.atoms.wm_name, self.atoms.utf8_string)
+            .or_else(|| self.property(window, AtomEnum::WM_NAME.into(), AtomEnum::STRING.into()))
+            .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+            .unwrap_or_default()
+    }
+
+    fn class(&self, window: Window) -> String {
+        self.property(window, AtomEnum::WM_CLASS.into(), AtomEnum::STRING.into())
+            .map(|bytes| {
+                bytes
+                    .split(|&byte| byte == 0)
+                    .rfind(|part| !part.is_empty())
+                    .map(|part| String::from_utf8_lossy(part).to_string())
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn process_exe(pid: u32) -> Option<String> {
+    std::fs::read_link(format!("/proc/{}/exe", pid))
+        .ok()?
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+impl ActiveWindowProvider for X11Provider {
+    fn capture(&mut self) -> Option<Capture> {
+        let window = self
+            .cardinal(self.root, self.atoms.active_window, AtomEnum::WINDOW)
+            .filter(|&window| window != 0)?;
+        let raw_title = self.title(window);
+        let pid = self
+            .cardinal(window, self.atoms.wm_pid, AtomEnum::CARDINAL)
+            .unwrap_or(0);
+        let exe = (pid != 0).then(|| process_exe(pid)).flatten();
+        let title = if raw_title.is_empty() {
+            Ok(untitled(exe.as_deref()))
+        } else {
+            Ok(raw_title.clone())
+        };
+        Some(Capture {
+            hwnd: window as isize,
+            pid,
+            exe,
+            class: self.class(window),
+            raw_title,
+            title,
+            seen: Local::now(),
+        })
+    }
+
+    fn idle_duration(&self) -> Duration {
+        self.conn
+            .screensaver_query_info(self.root)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map_or(Duration::ZERO, |info| {
+                Duration::from_millis(info.ms_since_user_input.into())
+            })
+    }
+}
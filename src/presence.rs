@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, TimeDelta};
+use serde::{Deserialize, Serialize};
+use ureq::Agent;
+
+use crate::config::PresenceConfig;
+use crate::control::Control;
+use crate::stats::is_focus;
+use crate::store::Result;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum State {
+    Free,
+    Busy,
+    DeepWork,
+}
+
+impl State {
+    fn label(self) -> &'static str {
+        match self {
+            State::Free => "자리 비움/여유",
+            State::Busy => "작업 중",
+            State::DeepWork => "집중 작업 중",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Presence {
+    pub name: String,
+    pub state: State,
+    pub since: DateTime<Local>,
+    pub updated: DateTime<Local>,
+    pub expires: DateTime<Local>,
+}
+
+struct Publisher {
+    config: PresenceConfig,
+    agent: Agent,
+}
+
+impl Publisher {
+    fn publish(&self, presence: &Presence) -> Result<()> {
+        if let Some(url) = &self.config.url {
+            let mut request = self.agent.post(url);
+            for (name, value) in &self.config.headers {
+                request = request.header(name, value);
+            }
+            request.send_json(presence)?;
+        }
+        if let Some(dir) = &self.config.path {
+            fs::create_dir_all(dir)?;
+            let file = dir.join(format!("{}.json", presence.name));
+            let temp = dir.join(format!("{}.json.tmp", presence.name));
+            fs::write(&temp, serde_json::to_string_pretty(presence)?)?;
+            fs::rename(&temp, &file)?;
+        }
+        Ok(())
+    }
+}
+
+fn current_state(
+    control: &Control,
+    focus_since: &mut Option<DateTime<Local>>,
+    deep_work: TimeDelta,
+    now: DateTime<Local>,
+) -> (State, Option<DateTime<Local>>) {
+    let status = control.status();
+    let focused = !status.paused && status.title.as_deref().is_some_and(is_focus);
+    if !focused {
+        *focus_since = None;
+        return (State::Free, None);
+    }
+    let since = *focus_since.get_or_insert(status.since.unwrap_or(now));
+    if now - since >= deep_work {
+        (State::DeepWork, Some(since + deep_work))
+    } else {
+        (State::Busy, Some(since))
+    }
+}
+
+pub fn spawn(config: PresenceConfig, control: Arc<Control>) {
+    if config.url.is_none() && config.path.is_none() {
+        eprintln!("presence에 url 또는 path가 없어 상태를 공유하지 않습니다");
+        return;
+    }
+    let agent = Agent::config_builder()
+        .timeout_global(Some(Duration::from_secs(10)))
+        .build()
+        .into();
+    let heartbeat = TimeDelta::seconds(config.heartbeat_secs.max(1) as i64);
+    let deep_work = TimeDelta::minutes(config.deep_work_minutes as i64);
+    let publisher = Publisher { config, agent };
+    thread::spawn(move || {
+        let mut focus_since = None;
+        let mut published: Option<(State, DateTime<Local>, DateTime<Local>)> = None;
+        loop {
+            let now = Local::now();
+            let (state, started) = current_state(&control, &mut focus_since, deep_work, now);
+            let since = match published {
+                Some((previous, since, _)) if previous == state => since,
+                _ => started.unwrap_or(now),
+            };
+            let due = published.is_none_or(|(previous, _, updated)| {
+                previous != state || now - updated >= heartbeat
+            });
+            if due {
+                let presence = Presence {
+                    name: publisher.config.name.clone(),
+                    state,
+                    since,
+                    updated: now,
+                    expires: now + heartbeat * 3,
+                };
+                match publisher.publish(&presence) {
+                    Ok(()) => published = Some((state, since, now)),
+                    Err(e) => eprintln!("상태 공유 실패: {}", e),
+                }
+            }
+            thread::sleep(CHECK_INTERVAL);
+        }
+    });
+}
+
+fn read(path: &Path) -> Result<Presence> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+pub fn print_team(config: &PresenceConfig) -> Result<()> {
+    let dir = config
+        .path
+        .as_ref()
+        .ok_or("presence.path가 설정되어 있지 않습니다")?;
+    let mut team = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            match read(&path) {
+                Ok(presence) => team.push(presence),
+                Err(e) => eprintln!("{} 읽기 실패: {}", path.display(), e),
+            }
+        }
+    }
+    if team.is_empty() {
+        println!("공유된 상태가 없습니다 ({})", dir.display());
+    }
+    team.sort_by(|a, b| a.name.cmp(&b.name));
+    let now = Local::now();
+    for presence in &team {
+        let state = if presence.expires < now {
+            format!(
+                "오프라인 (마지막 갱신 {})",
+                presence.updated.format("%m-%d %H:%M")
+            )
+        } else {
+            format!(
+                "{} ({}부터)",
+                presence.state.label(),
+                presence.since.format("%H:%M")
+            )
+        };
+        println!("{:<20} {}", presence.name, state);
+    }
+    Ok(())
+}
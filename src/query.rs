@@ -0,0 +1,380 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDate, Timelike, Weekday};
+
+use crate::config::Goal;
+use crate::format::{format_duration, title_width, truncate_or_pad};
+use crate::goals::week_start;
+use crate::report::{app_name, category};
+use crate::store::{Interval, Query, Result, Store, summarize_intervals_by};
+
+const FIELDS: &[&str] = &[
+    "app", "title", "exe", "category", "tag", "location", "dow", "hour", "date", "duration",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum QueryGroup {
+    None,
+    Day,
+    Week,
+    Month,
+    Dow,
+    Hour,
+    App,
+    Title,
+    Category,
+    Tag,
+}
+
+impl QueryGroup {
+    fn is_time(self) -> bool {
+        matches!(
+            self,
+            QueryGroup::Day
+                | QueryGroup::Week
+                | QueryGroup::Month
+                | QueryGroup::Dow
+                | QueryGroup::Hour
+        )
+    }
+
+    fn key(self, interval: &Interval, goals: &[Goal]) -> Option<String> {
+        let start = interval.start;
+        Some(match self {
+            QueryGroup::None => "합계".to_string(),
+            QueryGroup::Day => start.format("%Y-%m-%d").to_string(),
+            QueryGroup::Week => week_start(start).format("%Y-%m-%d 주").to_string(),
+            QueryGroup::Month => start.format("%Y-%m").to_string(),
+            QueryGroup::Dow => format!(
+                "{} {}",
+                start.weekday().num_days_from_monday(),
+                start.weekday()
+            ),
+            QueryGroup::Hour => format!("{:02}시", start.hour()),
+            QueryGroup::App => app(interval),
+            QueryGroup::Title => interval.title.clone(),
+            QueryGroup::Category => category(&interval.title, interval.exe.as_deref(), goals),
+            QueryGroup::Tag => interval.tag.clone()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Cond(String, Op, String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Op(Op),
+    Open,
+    Close,
+}
+
+fn tokenize(text: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&ch) = chars.peek() {
+        match ch {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '(' | ')' => {
+                chars.next();
+                tokens.push(if ch == '(' { Token::Open } else { Token::Close });
+            }
+            '"' | '\'' => {
+                chars.next();
+                let word: String = chars.by_ref().take_while(|&c| c != ch).collect();
+                tokens.push(Token::Word(word));
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let equals = chars.next_if_eq(&'=').is_some();
+                tokens.push(Token::Op(match (ch, equals) {
+                    ('=', _) => Op::Eq,
+                    ('!', true) => Op::Ne,
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('>', false) => Op::Gt,
+                    ('>', true) => Op::Ge,
+                    _ => return Err("'!' 다음에는 '='가 와야 합니다".to_string()),
+                }));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(c) = chars.next_if(|c| !" \t()=!<>\"'".contains(*c)) {
+                    word.push(c);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        match self.tokens.get(self.pos) {
+            Some(Token::Word(word)) => word.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.peek_keyword("OR") {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.not()?;
+        while self.peek_keyword("AND") {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> std::result::Result<Expr, String> {
+        if self.peek_keyword("NOT") {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        match self.next() {
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err("닫는 괄호가 없습니다".to_string()),
+                }
+            }
+            Some(Token::Word(field)) => {
+                let field = field.to_lowercase();
+                if !FIELDS.contains(&field.as_str()) {
+                    return Err(format!(
+                        "알 수 없는 필드: {} (사용 가능: {})",
+                        field,
+                        FIELDS.join(", ")
+                    ));
+                }
+                let Some(Token::Op(op)) = self.next() else {
+                    return Err(format!("{} 다음에 비교 연산자가 필요합니다", field));
+                };
+                let Some(Token::Word(value)) = self.next() else {
+                    return Err(format!("{} 조건에 값이 없습니다", field));
+                };
+                check_value(&field, op, &value)?;
+                Ok(Expr::Cond(field, op, value))
+            }
+            _ => Err("조건이 필요한 자리입니다".to_string()),
+        }
+    }
+}
+
+fn app(interval: &Interval) -> String {
+    match &interval.exe {
+        Some(exe) => exe
+            .strip_suffix(".exe")
+            .or_else(|| exe.strip_suffix(".EXE"))
+            .unwrap_or(exe)
+            .to_string(),
+        None => app_name(&interval.title),
+    }
+}
+
+fn parse_weekday(text: &str) -> std::result::Result<u32, String> {
+    text.parse::<Weekday>()
+        .map(|weekday| weekday.num_days_from_monday())
+        .map_err(|_| format!("요일은 Mon..Sun 형식이어야 합니다: {}", text))
+}
+
+fn parse_date(text: &str) -> std::result::Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| format!("날짜는 YYYY-MM-DD 형식이어야 합니다: {}", text))
+}
+
+fn parse_hour(text: &str) -> std::result::Result<u32, String> {
+    text.parse::<u32>()
+        .ok()
+        .filter(|hour| *hour < 24)
+        .ok_or_else(|| format!("시간은 0..23이어야 합니다: {}", text))
+}
+
+fn parse_seconds(text: &str) -> std::result::Result<f64, String> {
+    let (number, unit) = text.split_at(text.trim_end_matches(char::is_alphabetic).len());
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("길이는 '90s', '5m', '2h' 형식이어야 합니다: {}", text))?;
+    Ok(number
+        * match unit {
+            "" | "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return Err(format!("알 수 없는 단위: {}", unit)),
+        })
+}
+
+fn bounds<T>(
+    value: &str,
+    parse: impl Fn(&str) -> std::result::Result<T, String>,
+) -> std::result::Result<(T, T), String> {
+    match value.split_once("..") {
+        Some((low, high)) => Ok((parse(low)?, parse(high)?)),
+        None => Ok((parse(value)?, parse(value)?)),
+    }
+}
+
+fn check_value(field: &str, op: Op, value: &str) -> std::result::Result<(), String> {
+    match field {
+        "dow" => bounds(value, parse_weekday).map(|_| ()),
+        "hour" => bounds(value, parse_hour).map(|_| ()),
+        "date" => bounds(value, parse_date).map(|_| ()),
+        "duration" => parse_seconds(value).map(|_| ()),
+        _ if !matches!(op, Op::Eq | Op::Ne) => {
+            Err(format!("{}에는 = 또는 !=만 쓸 수 있습니다", field))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn compare<T: PartialOrd>(op: Op, actual: T, (low, high): (T, T)) -> bool {
+    let inside = actual >= low && actual <= high;
+    match op {
+        Op::Eq => inside,
+        Op::Ne => !inside,
+        Op::Lt => actual < low,
+        Op::Le => actual <= high,
+        Op::Gt => actual > high,
+        Op::Ge => actual >= low,
+    }
+}
+
+fn contains(op: Op, actual: Option<&str>, value: &str) -> bool {
+    let found = actual.is_some_and(|actual| actual.to_lowercase().contains(&value.to_lowercase()));
+    if op == Op::Ne { !found } else { found }
+}
+
+impl Expr {
+    fn matches(&self, interval: &Interval, goals: &[Goal]) -> bool {
+        let start = interval.start;
+        match self {
+            Expr::And(a, b) => a.matches(interval, goals) && b.matches(interval, goals),
+            Expr::Or(a, b) => a.matches(interval, goals) || b.matches(interval, goals),
+            Expr::Not(expr) => !expr.matches(interval, goals),
+            Expr::Cond(field, op, value) => match field.as_str() {
+                "app" => contains(*op, Some(&app(interval)), value),
+                "title" => contains(*op, Some(&interval.title), value),
+                "exe" => contains(*op, interval.exe.as_deref(), value),
+                "category" => contains(
+                    *op,
+                    Some(&category(&interval.title, interval.exe.as_deref(), goals)),
+                    value,
+                ),
+                "tag" => contains(*op, interval.tag.as_deref(), value),
+                "location" => contains(*op, interval.location.as_deref(), value),
+                "dow" => bounds(value, parse_weekday)
+                    .is_ok_and(|range| compare(*op, start.weekday().num_days_from_monday(), range)),
+                "hour" => {
+                    bounds(value, parse_hour).is_ok_and(|range| compare(*op, start.hour(), range))
+                }
+                "date" => bounds(value, parse_date)
+                    .is_ok_and(|range| compare(*op, start.date_naive(), range)),
+                "duration" => parse_seconds(value).is_ok_and(|seconds| {
+                    compare(*op, interval.duration().as_secs_f64(), (seconds, seconds))
+                }),
+                _ => false,
+            },
+        }
+    }
+}
+
+pub struct Compiled {
+    expr: Expr,
+}
+
+impl Compiled {
+    pub fn parse(text: &str) -> std::result::Result<Compiled, String> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            pos: 0,
+        };
+        let expr = parser.or()?;
+        if parser.pos < parser.tokens.len() {
+            return Err("식 뒤에 남은 내용이 있습니다 (AND/OR로 이어 주세요)".to_string());
+        }
+        Ok(Compiled { expr })
+    }
+
+    pub fn matches(&self, interval: &Interval, goals: &[Goal]) -> bool {
+        self.expr.matches(interval, goals)
+    }
+}
+
+pub fn print_query(
+    store: &dyn Store,
+    goals: &[Goal],
+    text: &str,
+    group: QueryGroup,
+    query: &Query,
+) -> Result<()> {
+    let compiled = Compiled::parse(text)?;
+    let intervals: Vec<Interval> = store
+        .query(query)?
+        .into_iter()
+        .filter(|interval| compiled.matches(interval, goals))
+        .collect();
+    let mut totals = summarize_intervals_by(&intervals, |interval| group.key(interval, goals));
+    if group.is_time() {
+        totals = totals
+            .into_iter()
+            .collect::<BTreeMap<String, Duration>>()
+            .into_iter()
+            .collect();
+    }
+    if totals.is_empty() {
+        println!("일치하는 구간이 없습니다");
+    }
+    for (key, total) in &totals {
+        let key = match group {
+            QueryGroup::Dow => key.split_once(' ').map_or(key.as_str(), |(_, name)| name),
+            _ => key,
+        };
+        println!(
+            "{} {:>10}",
+            truncate_or_pad(key, title_width()),
+            format_duration(total)
+        );
+    }
+    println!("일치하는 구간 {}개", intervals.len());
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+use std::fs;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::control::Control;
+use crate::store::{Interval, Query, Result, Store};
+
+pub const RECOVERY_FILE: &str = "recovery.json";
+const TEMP_FILE: &str = "recovery.json.tmp";
+
+static FINISHED: Mutex<bool> = Mutex::new(false);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub session_start: DateTime<Local>,
+    pub saved_at: DateTime<Local>,
+    pub intervals: Vec<Interval>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open: Option<Interval>,
+}
+
+pub struct Recovered {
+    pub session_start: DateTime<Local>,
+    pub saved_at: DateTime<Local>,
+    pub merged: usize,
+}
+
+fn snapshot(store: &Mutex<Box<dyn Store>>, control: &Control) -> Result<Snapshot> {
+    let session_start = control.session_start();
+    let saved_at = Local::now();
+    let intervals = store.lock().unwrap().query(&Query {
+        from: Some(session_start),
+        to: None,
+    })?;
+    let status = control.status();
+    let open = match (status.title, status.since) {
+        (Some(title), Some(since)) if !status.paused && since < saved_at => {
+            Some(Interval::new(since, saved_at, title, None))
+        }
+        _ => None,
+    };
+    Ok(Snapshot {
+        session_start,
+        saved_at,
+        intervals,
+        open,
+    })
+}
+
+fn save(snapshot: &Snapshot) -> Result<()> {
+    let finished = FINISHED.lock().unwrap();
+    if *finished {
+        return Ok(());
+    }
+    fs::write(TEMP_FILE, serde_json::to_string(snapshot)?)?;
+    fs::rename(TEMP_FILE, RECOVERY_FILE)?;
+    Ok(())
+}
+
+pub fn discard() {
+    let _ = fs::remove_file(RECOVERY_FILE);
+}
+
+pub fn clear() {
+    let mut finished = FINISHED.lock().unwrap();
+    *finished = true;
+    discard();
+}
+
+fn load() -> Result<Option<Snapshot>> {
+    match fs::read_to_string(RECOVERY_FILE) {
+        Ok(text) => Ok(Some(serde_json::from_str(&text)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn recover(store: &mut dyn Store) -> Result<Option<Recovered>> {
+    let Some(snapshot) = load()? else {
+        return Ok(None);
+    };
+    let query = Query {
+        from: Some(snapshot.session_start),
+        to: Some(snapshot.saved_at),
+    };
+    let stored = store.query(&query)?;
+    let known: HashSet<_> = stored.iter().map(|interval| interval.id).collect();
+    let mut merged = 0;
+    for interval in &snapshot.intervals {
+        if !known.contains(&interval.id) {
+            store.append_interval(interval)?;
+            merged += 1;
+        }
+    }
+    if let Some(mut open) = snapshot.open {
+        let covered = stored
+            .iter()
+            .chain(&snapshot.intervals)
+            .filter(|interval| interval.end > open.start && interval.start < open.end)
+            .map(|interval| interval.end)
+            .max();
+        if let Some(covered) = covered {
+            open.start = open.start.max(covered);
+        }
+        if open.start < open.end {
+            store.append_interval(&open)?;
+            merged += 1;
+        }
+    }
+    Ok(Some(Recovered {
+        session_start: snapshot.session_start,
+        saved_at: snapshot.saved_at,
+        merged,
+    }))
+}
+
+pub fn spawn_autosave(every: Duration, store: Arc<Mutex<Box<dyn Store>>>, control: Arc<Control>) {
+    if every.is_zero() {
+        return;
+    }
+    thread::spawn(move || {
+        loop {
+            thread::sleep(every);
+            if let Err(e) = snapshot(&store, &control).and_then(|s| save(&s)) {
+                eprintln!("자동 저장 실패 ({}): {}", RECOVERY_FILE, e);
+            }
+        }
+    });
+}
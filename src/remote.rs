@@ -0,0 +1,68 @@
+#[cfg(windows)]
+use windows::Win32::Foundation::CloseHandle;
+#[cfg(windows)]
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW, TH32CS_SNAPPROCESS,
+};
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_REMOTECONTROL};
+
+pub const REMOTE_LABEL: &str = "[원격 제어]";
+pub const REMOTE_KIND: &str = "remote";
+#[cfg(windows)]
+const REMOTE_TOOLS: &[(&str, &str)] = &[
+    ("teamviewer_desktop.exe", "TeamViewer"),
+    ("quickassist.exe", "빠른 지원"),
+    ("anydesk.exe", "AnyDesk"),
+];
+
+#[cfg(windows)]
+fn running_exes() -> Vec<String> {
+    unsafe {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+            return Vec::new();
+        };
+        let mut entry = PROCESSENTRY32W {
+            dwSize: size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut exes = Vec::new();
+        let mut found = Process32FirstW(snapshot, &mut entry).is_ok();
+        while found {
+            let len = entry
+                .szExeFile
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(entry.szExeFile.len());
+            exes.push(String::from_utf16_lossy(&entry.szExeFile[..len]).to_lowercase());
+            found = Process32NextW(snapshot, &mut entry).is_ok();
+        }
+        let _ = CloseHandle(snapshot);
+        exes
+    }
+}
+
+#[cfg(windows)]
+fn display_name(exe: &str) -> String {
+    REMOTE_TOOLS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(exe))
+        .map_or_else(|| exe.to_string(), |(_, name)| name.to_string())
+}
+
+#[cfg(windows)]
+pub fn controlling_tool(tools: &[String]) -> Option<String> {
+    if unsafe { GetSystemMetrics(SM_REMOTECONTROL) } != 0 {
+        return Some("원격 데스크톱 섀도".to_string());
+    }
+    let exes = running_exes();
+    tools
+        .iter()
+        .find(|tool| exes.contains(&tool.to_lowercase()))
+        .map(|tool| display_name(tool))
+}
+
+#[cfg(not(windows))]
+pub fn controlling_tool(_tools: &[String]) -> Option<String> {
+    None
+}
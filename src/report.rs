@@ -0,0 +1,723 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::Local;
+use serde_json::json;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::anomaly;
+use crate::categories::{self, UNCATEGORIZED};
+use crate::compact::MERGE_KIND;
+use crate::config::{Goal, GroupBy, Language, SummaryFormat};
+use crate::estimate;
+use crate::export::csv_value;
+use crate::format::{clean_window_title, format_duration, is_a11y, table_row, title_width};
+use crate::gaps;
+use crate::goals;
+use crate::html;
+use crate::idle::IDLE_LABELS;
+use crate::screenshot::SCREENSHOT_KIND;
+use crate::stats;
+use crate::store::{Interval, Query, Result, Store, summarize_intervals_by};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortBy {
+    #[default]
+    Time,
+    Name,
+}
+
+impl GroupBy {
+    pub fn next(self) -> GroupBy {
+        match self {
+            GroupBy::Title => GroupBy::App,
+            GroupBy::App => GroupBy::Both,
+            GroupBy::Both => GroupBy::Category,
+            GroupBy::Category => GroupBy::Title,
+        }
+    }
+
+    pub fn label(self, language: Language) -> &'static str {
+        match self {
+            GroupBy::Title => language.pick("창 제목", "Window title"),
+            GroupBy::App => language.pick("앱", "App"),
+            GroupBy::Both => language.pick("앱과 창 제목", "App and window title"),
+            GroupBy::Category => language.pick("분류", "Category"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct View {
+    pub sort: SortBy,
+    pub group: GroupBy,
+    pub filter: Option<String>,
+}
+
+pub fn app_name(title: &str) -> String {
+    title
+        .rsplit(" - ")
+        .next()
+        .unwrap_or(title)
+        .trim()
+        .to_string()
+}
+
+pub fn category(title: &str, exe: Option<&str>, goals: &[Goal]) -> String {
+    categories::classify(title, exe)
+        .map(str::to_string)
+        .or_else(|| {
+            goals
+                .iter()
+                .find(|goal| goal.matches(title))
+                .map(|goal| goal.name.clone())
+        })
+        .unwrap_or_else(|| UNCATEGORIZED.to_string())
+}
+
+pub fn has_categories(goals: &[Goal]) -> bool {
+    !categories::rules().is_empty() || !goals.is_empty()
+}
+
+pub fn category_totals(intervals: &[Interval], goals: &[Goal]) -> Vec<(String, Duration)> {
+    summarize_intervals_by(intervals, |interval| {
+        (!IDLE_LABELS.contains(&interval.title.as_str()))
+            .then(|| category(&interval.title, interval.exe.as_deref(), goals))
+    })
+}
+
+impl View {
+    pub fn grouped(group: GroupBy) -> View {
+        View {
+            group,
+            ..View::default()
+        }
+    }
+
+    fn by_app_and_title(
+        &self,
+        store: &dyn Store,
+        query: &Query,
+    ) -> Result<Vec<(String, Duration)>> {
+        let rows: Vec<(String, String, Duration)> =
+            if self.filter.is_none() && query.is_whole_days() {
+                store
+                    .daily_totals(query)?
+                    .into_iter()
+                    .map(|total| {
+                        let app = total.exe.unwrap_or_else(|| app_name(&total.title));
+                        (app, total.title, total.duration)
+                    })
+                    .collect()
+            } else {
+                self.intervals(store, query)?
+                    .into_iter()
+                    .map(|interval| {
+                        let app = interval
+                            .exe
+                            .clone()
+                            .unwrap_or_else(|| app_name(&interval.title));
+                        let duration = interval.duration();
+                        (app, interval.title, duration)
+                    })
+                    .collect()
+            };
+        let mut apps = HashMap::<String, HashMap<String, Duration>>::new();
+        for (app, title, duration) in rows {
+            *apps.entry(app).or_default().entry(title).or_default() += duration;
+        }
+        let sort = |entries: &mut Vec<(String, Duration)>| match self.sort {
+            SortBy::Time => entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))),
+            SortBy::Name => entries.sort_by(|a, b| a.0.cmp(&b.0)),
+        };
+        let mut totals: Vec<(String, Duration)> = apps
+            .iter()
+            .map(|(app, titles)| (app.clone(), titles.values().sum()))
+            .collect();
+        sort(&mut totals);
+        let mut entries = Vec::new();
+        for (app, total) in totals {
+            let mut titles: Vec<_> = apps.remove(&app).unwrap_or_default().into_iter().collect();
+            sort(&mut titles);
+            entries.push((app, total));
+            entries.extend(titles.into_iter().map(|(title, duration)| {
+                (format!("  - {}", clean_window_title(&title)), duration)
+            }));
+        }
+        Ok(entries)
+    }
+
+    pub fn intervals(&self, store: &dyn Store, query: &Query) -> Result<Vec<Interval>> {
+        let filter = self.filter.as_ref().map(|text| text.to_lowercase());
+        Ok(store
+            .query(query)?
+            .into_iter()
+            .filter(|interval| {
+                filter
+                    .as_ref()
+                    .is_none_or(|text| interval.title.to_lowercase().contains(text))
+            })
+            .collect())
+    }
+
+    pub fn summarize(
+        &self,
+        store: &dyn Store,
+        query: &Query,
+        goals: &[Goal],
+    ) -> Result<Vec<(String, Duration)>> {
+        if self.group == GroupBy::Both {
+            return self.by_app_and_title(store, query);
+        }
+        let mut entries = if self.group == GroupBy::Title && self.filter.is_none() {
+            store.summarize(query)?
+        } else if self.filter.is_none() && query.is_whole_days() {
+            let mut totals = HashMap::<String, Duration>::new();
+            for total in store.daily_totals(query)? {
+                let key = match self.group {
+                    GroupBy::Category => category(&total.title, total.exe.as_deref(), goals),
+                    _ => total.exe.unwrap_or_else(|| app_name(&total.title)),
+                };
+                *totals.entry(key).or_default() += total.duration;
+            }
+            let mut entries: Vec<_> = totals.into_iter().collect();
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+            entries
+        } else {
+            let intervals = self.intervals(store, query)?;
+            summarize_intervals_by(&intervals, |interval| {
+                Some(match self.group {
+                    GroupBy::Title => interval.title.clone(),
+                    GroupBy::App | GroupBy::Both => interval
+                        .exe
+                        .clone()
+                        .unwrap_or_else(|| app_name(&interval.title)),
+                    GroupBy::Category => category(&interval.title, interval.exe.as_deref(), goals),
+                })
+            })
+        };
+        if self.sort == SortBy::Name {
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        Ok(entries)
+    }
+}
+
+pub fn print_entries(entries: &[(String, Duration)]) {
+    for (title, duration) in entries {
+        println!("{}", table_row(title, "사용 시간", duration));
+    }
+}
+
+const RULE_WIDTH: usize = 53;
+pub const HISTORY_DAYS: u64 = 90;
+
+fn heading(title: &str) -> String {
+    if is_a11y() {
+        return title.to_string();
+    }
+    let title = format!(" {} ", title);
+    let fill = RULE_WIDTH.saturating_sub(title.width());
+    format!(
+        "{}{}{}",
+        "=".repeat(fill / 2),
+        title,
+        "=".repeat(fill - fill / 2)
+    )
+}
+
+type WindowTotal = ((String, Option<String>), Duration);
+
+fn window_totals(intervals: &[Interval], sort: SortBy) -> Vec<WindowTotal> {
+    let mut totals = HashMap::<(String, Option<String>), Duration>::new();
+    for interval in intervals {
+        *totals
+            .entry((interval.title.clone(), interval.exe.clone()))
+            .or_default() += interval.duration();
+    }
+    let mut rows: Vec<_> = totals.into_iter().collect();
+    match sort {
+        SortBy::Time => rows.sort_by_key(|row| std::cmp::Reverse(row.1)),
+        SortBy::Name => rows.sort_by(|a, b| a.0.cmp(&b.0)),
+    }
+    rows
+}
+
+fn write_summary_json(
+    store: &dyn Store,
+    query: &Query,
+    goals: &[Goal],
+    view: &View,
+    path: &Path,
+) -> Result<()> {
+    let intervals = view.intervals(store, query)?;
+    let start = query
+        .from
+        .or_else(|| intervals.iter().map(|interval| interval.start).min());
+    let end = intervals.iter().map(|interval| interval.end).max();
+    let idle: Duration = intervals
+        .iter()
+        .filter(|interval| IDLE_LABELS.contains(&interval.title.as_str()))
+        .map(Interval::duration)
+        .sum();
+    let windows: Vec<_> = window_totals(&intervals, view.sort)
+        .into_iter()
+        .map(|((title, exe), duration)| {
+            json!({
+                "title": title,
+                "exe": exe,
+                "total_seconds": duration.as_secs_f64(),
+            })
+        })
+        .collect();
+    let categories: Vec<_> = category_totals(&intervals, goals)
+        .into_iter()
+        .map(|(name, duration)| {
+            json!({
+                "name": name,
+                "total_seconds": duration.as_secs_f64(),
+            })
+        })
+        .collect();
+    let summary = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "session": {
+            "start": start.map(|time| time.to_rfc3339()),
+            "end": end.map(|time| time.to_rfc3339()),
+        },
+        "idle_total_seconds": idle.as_secs_f64(),
+        "windows": windows,
+        "categories": categories,
+    });
+    fs::write(path, serde_json::to_string_pretty(&summary)?)?;
+    Ok(())
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+fn write_md_table(
+    file: &mut File,
+    label: &str,
+    rows: &[(String, Duration)],
+    total: Duration,
+    language: Language,
+) -> Result<()> {
+    writeln!(
+        file,
+        "| {} | {} | {} |",
+        label,
+        language.pick("사용 시간", "Time"),
+        language.pick("비율", "Share")
+    )?;
+    writeln!(file, "| --- | ---: | ---: |")?;
+    for (name, duration) in rows {
+        writeln!(
+            file,
+            "| {} | {} | {:.0}% |",
+            markdown_cell(name),
+            format_duration(duration),
+            duration.as_secs_f64() / total.as_secs_f64().max(1.0) * 100.0
+        )?;
+    }
+    Ok(())
+}
+
+fn write_summary_md(
+    store: &dyn Store,
+    query: &Query,
+    goals: &[Goal],
+    view: &View,
+    language: Language,
+    path: &Path,
+) -> Result<()> {
+    let intervals = view.intervals(store, query)?;
+    let (idle, active): (Vec<Interval>, Vec<Interval>) = intervals
+        .into_iter()
+        .partition(|interval| IDLE_LABELS.contains(&interval.title.as_str()));
+    let idle: Duration = idle.iter().map(Interval::duration).sum();
+    let mut apps = summarize_intervals_by(&active, |interval| {
+        Some(
+            interval
+                .exe
+                .clone()
+                .unwrap_or_else(|| app_name(&interval.title)),
+        )
+    });
+    let total: Duration = apps.iter().map(|(_, duration)| *duration).sum();
+
+    let mut file = File::create(path)?;
+    writeln!(
+        file,
+        "# {}",
+        language.pick("창 사용 시간 요약", "Window usage summary")
+    )?;
+    writeln!(file)?;
+    let first = active.iter().map(|interval| interval.start).min();
+    let last = active.iter().map(|interval| interval.end).max();
+    if let (Some(first), Some(last)) = (first, last) {
+        writeln!(
+            file,
+            "- {}: {}",
+            language.pick("기간", "Period"),
+            language.period(first.date_naive(), last.date_naive())
+        )?;
+    }
+    writeln!(
+        file,
+        "- {}: {}",
+        language.pick("총 기록 시간", "Total tracked"),
+        format_duration(&(total + idle))
+    )?;
+    writeln!(
+        file,
+        "- {}: {}",
+        language.pick("자리 비움", "Idle"),
+        format_duration(&idle)
+    )?;
+    if !apps.is_empty() {
+        let top: Vec<String> = apps
+            .iter()
+            .take(3)
+            .map(|(app, duration)| {
+                format!("{} ({})", markdown_cell(app), format_duration(duration))
+            })
+            .collect();
+        writeln!(
+            file,
+            "- {}: {}",
+            language.pick("많이 쓴 앱", "Top apps"),
+            top.join(", ")
+        )?;
+    }
+    writeln!(file)?;
+    if view.sort == SortBy::Name {
+        apps.sort_by(|a, b| a.0.cmp(&b.0));
+    }
+    write_md_table(
+        &mut file,
+        language.pick("앱", "App"),
+        &apps,
+        total,
+        language,
+    )?;
+    if has_categories(goals) {
+        let mut categories = category_totals(&active, goals);
+        if view.sort == SortBy::Name {
+            categories.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        writeln!(file)?;
+        write_md_table(
+            &mut file,
+            language.pick("분류", "Category"),
+            &categories,
+            total,
+            language,
+        )?;
+    }
+    Ok(())
+}
+
+fn write_summary_csv(store: &dyn Store, query: &Query, view: &View, path: &Path) -> Result<()> {
+    let rows = window_totals(&view.intervals(store, query)?, view.sort);
+    let mut file = File::create(path)?;
+    writeln!(file, "title,exe,total_seconds,formatted_duration")?;
+    for ((title, exe), duration) in &rows {
+        writeln!(
+            file,
+            "{},{},{:.3},{}",
+            csv_value(&title.as_str().into()),
+            csv_value(&exe.as_deref().into()),
+            duration.as_secs_f64(),
+            format_duration(duration)
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_summary(
+    store: &dyn Store,
+    query: &Query,
+    goals: &[Goal],
+    view: &View,
+    language: Language,
+    output_dir: &Path,
+    format: SummaryFormat,
+) -> Result<String> {
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let path = summary_path(output_dir, &timestamp, format)?;
+    write_summary_to(store, query, goals, view, language, &path, format)
+}
+
+pub fn summary_path(output_dir: &Path, stamp: &str, format: SummaryFormat) -> Result<PathBuf> {
+    let extension = match format {
+        SummaryFormat::Text => "txt",
+        SummaryFormat::Csv => "csv",
+        SummaryFormat::Json => "json",
+        SummaryFormat::Html => "html",
+        SummaryFormat::Md => "md",
+    };
+    fs::create_dir_all(output_dir)?;
+    Ok(output_dir.join(format!("window_log_{}.{}", stamp, extension)))
+}
+
+pub fn write_summary_to(
+    store: &dyn Store,
+    query: &Query,
+    goals: &[Goal],
+    view: &View,
+    language: Language,
+    path: &Path,
+    format: SummaryFormat,
+) -> Result<String> {
+    let filename = path.display().to_string();
+    match format {
+        SummaryFormat::Text => {}
+        SummaryFormat::Csv => {
+            write_summary_csv(store, query, view, Path::new(&filename))?;
+            return Ok(filename);
+        }
+        SummaryFormat::Json => {
+            write_summary_json(store, query, goals, view, Path::new(&filename))?;
+            return Ok(filename);
+        }
+        SummaryFormat::Md => {
+            write_summary_md(store, query, goals, view, language, Path::new(&filename))?;
+            return Ok(filename);
+        }
+        SummaryFormat::Html => {
+            html::write(store, query, goals, view, language, Path::new(&filename))?;
+            return Ok(filename);
+        }
+    }
+    let entries = view.summarize(store, query, goals)?;
+    let mut file = File::create(&filename)?;
+
+    writeln!(
+        file,
+        "{}",
+        heading(language.pick("창 사용 시간 요약", "Window usage summary"))
+    )?;
+    let intervals = store.query(query)?;
+    let first = intervals.iter().map(|interval| interval.start).min();
+    let last = intervals.iter().map(|interval| interval.end).max();
+    if let (Some(first), Some(last)) = (first, last) {
+        writeln!(
+            file,
+            "{}: {}",
+            language.pick("기간", "Period"),
+            language.period(first.date_naive(), last.date_naive())
+        )?;
+    }
+    let total_label = language.pick("총 사용 시간", "Total time");
+    if !is_a11y() {
+        writeln!(
+            file,
+            "{:<width$} {:>10}",
+            view.group.label(language),
+            total_label,
+            width = title_width()
+        )?;
+        writeln!(file, "{}", "-".repeat(RULE_WIDTH))?;
+    }
+
+    for (title, duration) in &entries {
+        writeln!(file, "{}", table_row(title, total_label, duration))?;
+    }
+
+    if !is_a11y() {
+        writeln!(file, "{}", "=".repeat(RULE_WIDTH))?;
+    }
+
+    let locations = summarize_intervals_by(&intervals, |interval| interval.location.clone());
+    if !locations.is_empty() {
+        writeln!(file)?;
+        writeln!(
+            file,
+            "{}",
+            heading(language.pick("위치 별 요약", "By location"))
+        )?;
+        for (location, duration) in &locations {
+            writeln!(file, "{}", table_row(location, total_label, duration))?;
+        }
+    }
+
+    if has_categories(goals) {
+        writeln!(file)?;
+        writeln!(
+            file,
+            "{}",
+            heading(language.pick("분류 별 요약", "By category"))
+        )?;
+        for (name, duration) in &category_totals(&intervals, goals) {
+            writeln!(file, "{}", table_row(name, total_label, duration))?;
+        }
+    }
+
+    if !goals.is_empty() {
+        writeln!(file)?;
+        writeln!(
+            file,
+            "{}",
+            heading(language.pick("목표와 예산", "Goals and budgets"))
+        )?;
+        for progress in goals::current_progress(goals, store)? {
+            writeln!(file, "{}", goals::format_progress(&progress, language))?;
+        }
+    }
+
+    let tags = estimate::tags_in(store, query)?;
+    if !tags.is_empty() {
+        writeln!(file)?;
+        writeln!(
+            file,
+            "{}",
+            heading(language.pick("태그별 예상과 실제", "Estimate vs actual by tag"))
+        )?;
+        for comparison in estimate::compare(store, &tags)? {
+            writeln!(file, "{}", comparison.line(language))?;
+        }
+    }
+
+    let history = store.query(&Query::last_days(HISTORY_DAYS))?;
+    let lines = stats::compute(goals, &history, Local::now()).lines(language);
+    if !lines.is_empty() {
+        writeln!(file)?;
+        writeln!(
+            file,
+            "{}",
+            heading(language.pick("기록과 배지", "Records and badges"))
+        )?;
+        for line in &lines {
+            writeln!(file, "{}", line)?;
+        }
+    }
+
+    let anomalies = anomaly::detect(goals, &history, Local::now(), language);
+    if !anomalies.is_empty() {
+        writeln!(file)?;
+        writeln!(
+            file,
+            "{}",
+            heading(language.pick("지난 7일 이상 패턴", "Unusual days (last 7 days)"))
+        )?;
+        for anomaly in &anomalies {
+            writeln!(file, "{}", anomaly.line(language))?;
+        }
+    }
+
+    let coverage = gaps::today(store)?;
+    writeln!(file)?;
+    writeln!(
+        file,
+        "{}",
+        heading(language.pick("오늘 추적 범위 (추정)", "Today's coverage (estimate)"))
+    )?;
+    for line in coverage.lines(language) {
+        writeln!(file, "{}", line)?;
+    }
+
+    let (screenshots, annotations): (Vec<_>, Vec<_>) = store
+        .annotations(query)?
+        .into_iter()
+        .filter(|annotation| !gaps::is_internal(annotation) && annotation.kind != MERGE_KIND)
+        .partition(|annotation| annotation.kind == SCREENSHOT_KIND);
+    if !screenshots.is_empty() {
+        writeln!(file)?;
+        writeln!(
+            file,
+            "{}",
+            heading(language.pick("스크린샷", "Screenshots"))
+        )?;
+        for screenshot in &screenshots {
+            writeln!(
+                file,
+                "[{}] {}",
+                language.datetime(screenshot.time),
+                screenshot.text
+            )?;
+        }
+    }
+
+    if !annotations.is_empty() {
+        writeln!(file)?;
+        writeln!(file, "{}", heading(language.pick("기록", "Notes")))?;
+        for annotation in &annotations {
+            writeln!(
+                file,
+                "[{}] {}",
+                language.datetime(annotation.time),
+                goals::describe_note(annotation, language)
+            )?;
+        }
+    }
+    Ok(filename)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::store::SqliteStore;
+
+    fn at(hour: u32, minute: u32) -> chrono::DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 6, 5, hour, minute, 0).unwrap()
+    }
+
+    fn total(store: &dyn Store, group: GroupBy, query: &Query) -> Duration {
+        View::grouped(group)
+            .summarize(store, query, &[])
+            .unwrap()
+            .into_iter()
+            .filter(|(name, _)| !name.starts_with("  - "))
+            .map(|(_, duration)| duration)
+            .sum()
+    }
+
+    #[test]
+    fn app_and_title_totals_match_for_mid_day_and_whole_day_ranges() {
+        let path = std::env::temp_dir().join(format!("wcd_report_{}.db", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut store = SqliteStore::open(&path).unwrap();
+        for (start, end, title) in [
+            (at(9, 0), at(10, 0), "notes.txt - Editor"),
+            (at(14, 0), at(14, 30), "notes.txt - Editor"),
+            (at(14, 30), at(14, 45), "inbox - Mail"),
+        ] {
+            let mut interval = Interval::new(start, end, title.to_string(), None);
+            interval.exe = Some(app_name(title));
+            store.append_interval(&interval).unwrap();
+        }
+        let mid_day = Query {
+            from: Some(at(12, 0)),
+            to: None,
+        };
+        let whole_day = Query::between(Some(at(0, 0).date_naive()), None);
+
+        let groups = [
+            GroupBy::Title,
+            GroupBy::App,
+            GroupBy::Category,
+            GroupBy::Both,
+        ];
+        let mid_day: Vec<Duration> = groups
+            .iter()
+            .map(|group| total(&store, *group, &mid_day))
+            .collect();
+        let whole_day: Vec<Duration> = groups
+            .iter()
+            .map(|group| total(&store, *group, &whole_day))
+            .collect();
+        drop(store);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(mid_day, [Duration::from_secs(45 * 60); 4]);
+        assert_eq!(whole_day, [Duration::from_secs(105 * 60); 4]);
+    }
+}
@@ -0,0 +1,40 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use chrono::{DateTime, Local};
+
+use crate::store::{self, Query, Result, Store};
+
+pub struct Resumed {
+    pub from: DateTime<Local>,
+    pub imported: usize,
+}
+
+pub fn resume(store: &mut dyn Store, file: Option<&Path>) -> Result<Option<Resumed>> {
+    let today = Query::between(Some(Local::now().date_naive()), None);
+    let mut imported = 0;
+    if let Some(file) = file {
+        let earlier = store::open_file(file)?.query(&today)?;
+        let known: HashSet<_> = store
+            .query(&today)?
+            .iter()
+            .map(|interval| interval.id)
+            .collect();
+        for interval in earlier
+            .iter()
+            .filter(|interval| !known.contains(&interval.id))
+        {
+            store.append_interval(interval)?;
+            imported += 1;
+        }
+    }
+    let first = store
+        .query(&today)?
+        .iter()
+        .map(|interval| interval.start)
+        .min();
+    Ok(first.map(|first| Resumed {
+        from: today.from.map_or(first, |midnight| first.max(midnight)),
+        imported,
+    }))
+}
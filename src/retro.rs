@@ -0,0 +1,298 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, Timelike};
+
+use crate::config::{Goal, Language};
+use crate::format::{shade, truncate_or_pad};
+use crate::goals;
+use crate::report::app_name;
+use crate::stats::is_focus;
+use crate::store::{Interval, Query, Result, Store, summarize_intervals_by};
+
+const DEEP_WORK: Duration = Duration::from_secs(45 * 60);
+const DEEP_WORK_GAP: Duration = Duration::from_secs(2 * 60);
+const TOP_DISTRACTIONS: usize = 5;
+const LABEL_WIDTH: usize = 4;
+
+const TEMPLATE_KO: &str = "# {week} 주간 회고
+
+## 집중 히트맵
+{heatmap}
+
+## 딥워크
+{deep_work}
+
+## 주요 방해 요소
+{distractions}
+
+## 지난주 대비
+{deltas}
+
+## 잘한 점
+
+## 아쉬운 점
+
+## 다음 주에 해 볼 것
+";
+
+const TEMPLATE_EN: &str = "# Weekly retro: {week}
+
+## Focus heatmap
+{heatmap}
+
+## Deep work
+{deep_work}
+
+## Top distractions
+{distractions}
+
+## Compared with last week
+{deltas}
+
+## What went well
+
+## What didn't
+
+## What to try next week
+";
+
+struct Week {
+    start: DateTime<Local>,
+    intervals: Vec<Interval>,
+}
+
+fn is_distraction(interval: &Interval, goals: &[Goal]) -> bool {
+    goals
+        .iter()
+        .any(|goal| goal.max_hours.is_some() && goal.matches(&interval.title))
+}
+
+fn hours(duration: Duration) -> f64 {
+    duration.as_secs_f64() / 3600.0
+}
+
+fn signed_hours(current: Duration, previous: Duration) -> String {
+    format!("{:+.1}h", hours(current) - hours(previous))
+}
+
+impl Week {
+    fn load(store: &dyn Store, start: DateTime<Local>) -> Result<Week> {
+        let query = Query {
+            from: Some(start),
+            to: Some(start + Days::new(7)),
+        };
+        let mut intervals = store.query(&query)?;
+        intervals.sort_by_key(|interval| interval.start);
+        Ok(Week { start, intervals })
+    }
+
+    fn focus(&self) -> Duration {
+        self.intervals
+            .iter()
+            .filter(|interval| is_focus(&interval.title))
+            .map(Interval::duration)
+            .sum()
+    }
+
+    fn distractions(&self, goals: &[Goal]) -> Vec<(String, Duration)> {
+        let intervals: Vec<Interval> = self
+            .intervals
+            .iter()
+            .filter(|interval| is_distraction(interval, goals))
+            .cloned()
+            .collect();
+        summarize_intervals_by(&intervals, |interval| Some(app_name(&interval.title)))
+    }
+
+    fn deep_work(&self, goals: &[Goal]) -> Vec<Duration> {
+        let mut blocks = Vec::new();
+        let mut current: Option<(DateTime<Local>, DateTime<Local>)> = None;
+        for interval in &self.intervals {
+            if !is_focus(&interval.title) || is_distraction(interval, goals) {
+                blocks.extend(current.take());
+                continue;
+            }
+            current = match current {
+                Some((start, end))
+                    if (interval.start - end).to_std().unwrap_or_default() <= DEEP_WORK_GAP =>
+                {
+                    Some((start, end.max(interval.end)))
+                }
+                previous => {
+                    blocks.extend(previous);
+                    Some((interval.start, interval.end))
+                }
+            };
+        }
+        blocks.extend(current);
+        blocks
+            .into_iter()
+            .map(|(start, end)| (end - start).to_std().unwrap_or_default())
+            .filter(|duration| *duration >= DEEP_WORK)
+            .collect()
+    }
+
+    fn heatmap(&self, language: Language) -> String {
+        let first_day = self.start.date_naive();
+        let mut grid = [[Duration::ZERO; 24]; 7];
+        for interval in self
+            .intervals
+            .iter()
+            .filter(|interval| is_focus(&interval.title))
+        {
+            let end = interval.end.naive_local();
+            let mut time = interval.start.naive_local();
+            while time < end {
+                let hour_start = time.date().and_hms_opt(time.hour(), 0, 0).unwrap();
+                let next = (hour_start + chrono::Duration::hours(1)).min(end);
+                let day = (time.date() - first_day).num_days();
+                if (0..7).contains(&day) {
+                    grid[day as usize][time.hour() as usize] +=
+                        (next - time).to_std().unwrap_or_default();
+                }
+                time = next;
+            }
+        }
+
+        let mut header: Vec<char> = vec![' '; 24];
+        for hour in [0, 6, 12, 18] {
+            for (offset, ch) in hour.to_string().chars().enumerate() {
+                header[hour + offset] = ch;
+            }
+        }
+        let mut lines = vec![format!(
+            "{} {}",
+            " ".repeat(LABEL_WIDTH),
+            header.into_iter().collect::<String>()
+        )];
+        for (day, hours) in grid.iter().enumerate() {
+            let date = first_day + Days::new(day as u64);
+            let cells: String = hours
+                .iter()
+                .map(|spent| shade((spent.as_secs() as usize).div_ceil(15 * 60)))
+                .collect();
+            lines.push(format!(
+                "{}|{}|",
+                truncate_or_pad(language.weekday(date.weekday()), LABEL_WIDTH),
+                cells
+            ));
+        }
+        format!("```\n{}\n```", lines.join("\n"))
+    }
+}
+
+fn week_label(start: DateTime<Local>, language: Language) -> String {
+    let first = start.date_naive();
+    language.period(first, first + Days::new(6))
+}
+
+pub fn retro(
+    goals: &[Goal],
+    store: &dyn Store,
+    date: NaiveDate,
+    template: Option<&Path>,
+    language: Language,
+) -> Result<String> {
+    let now = Local::now();
+    let anchor = date
+        .and_hms_opt(12, 0, 0)
+        .and_then(|time| time.and_local_timezone(Local).earliest())
+        .unwrap_or(now);
+    let start = goals::week_start(anchor);
+    let week = Week::load(store, start)?;
+    let previous = Week::load(store, goals::week_start(start - Days::new(1)))?;
+
+    let deep_work = week.deep_work(goals);
+    let previous_deep_work = previous.deep_work(goals);
+    let deep_work_text = format!(
+        "- {}: {} ({:.1}h, {})",
+        language.pick("45분 이상 몰입", "Blocks of 45+ minutes"),
+        deep_work.len(),
+        hours(deep_work.iter().sum()),
+        match deep_work.iter().max() {
+            Some(longest) => format!(
+                "{} {:.1}h",
+                language.pick("최장", "longest"),
+                hours(*longest)
+            ),
+            None => "-".to_string(),
+        }
+    );
+
+    let distractions = week.distractions(goals);
+    let previous_distractions = previous.distractions(goals);
+    let distractions_text = if distractions.is_empty() {
+        format!("- {}", language.pick("없음", "none"))
+    } else {
+        distractions
+            .iter()
+            .take(TOP_DISTRACTIONS)
+            .map(|(app, spent)| {
+                let before = previous_distractions
+                    .iter()
+                    .find(|(name, _)| name == app)
+                    .map(|(_, spent)| *spent)
+                    .unwrap_or_default();
+                format!(
+                    "- {} {:.1}h ({})",
+                    app,
+                    hours(*spent),
+                    signed_hours(*spent, before)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    let distraction_total: Duration = distractions.iter().map(|(_, spent)| *spent).sum();
+    let previous_distraction_total: Duration =
+        previous_distractions.iter().map(|(_, spent)| *spent).sum();
+    let mut deltas = vec![
+        format!(
+            "- {}: {:.1}h ({})",
+            language.pick("집중 시간", "Focus time"),
+            hours(week.focus()),
+            signed_hours(week.focus(), previous.focus())
+        ),
+        format!(
+            "- {}: {} ({:+})",
+            language.pick("딥워크", "Deep work"),
+            deep_work.len(),
+            deep_work.len() as i64 - previous_deep_work.len() as i64
+        ),
+        format!(
+            "- {}: {:.1}h ({})",
+            language.pick("방해 요소", "Distractions"),
+            hours(distraction_total),
+            signed_hours(distraction_total, previous_distraction_total)
+        ),
+    ];
+    for goal in goals {
+        let spent = |week: &Week| -> Duration {
+            week.intervals
+                .iter()
+                .filter(|interval| goal.matches(&interval.title))
+                .map(Interval::duration)
+                .sum()
+        };
+        deltas.push(format!(
+            "- {}: {:.1}h ({})",
+            goal.name,
+            hours(spent(&week)),
+            signed_hours(spent(&week), spent(&previous))
+        ));
+    }
+
+    let template = match template {
+        Some(path) => fs::read_to_string(path)?,
+        None => language.pick(TEMPLATE_KO, TEMPLATE_EN).to_string(),
+    };
+    Ok(template
+        .replace("{week}", &week_label(start, language))
+        .replace("{heatmap}", &week.heatmap(language))
+        .replace("{deep_work}", &deep_work_text)
+        .replace("{distractions}", &distractions_text)
+        .replace("{deltas}", &deltas.join("\n")))
+}
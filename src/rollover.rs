@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, TimeZone};
+
+use crate::config::{Config, Goal, GroupBy, Language, SummaryFormat};
+use crate::report::{self, View};
+use crate::store::{Query, Result, Store};
+
+pub struct Rollover {
+    day_start_hour: u32,
+    goals: Vec<Goal>,
+    language: Language,
+    output_dir: PathBuf,
+    format: SummaryFormat,
+    group: GroupBy,
+    day: Option<NaiveDate>,
+}
+
+impl Rollover {
+    pub fn new(config: &Config) -> Option<Rollover> {
+        config.daily_rollover.then(|| Rollover {
+            day_start_hour: config.day_start_hour.min(23),
+            goals: config.goals.clone(),
+            language: config.report_language,
+            output_dir: config.output_dir.clone(),
+            format: config.summary_format,
+            group: config.summary_group_by,
+            day: None,
+        })
+    }
+
+    fn day_of(&self, time: DateTime<Local>) -> NaiveDate {
+        (time - TimeDelta::hours(self.day_start_hour as i64)).date_naive()
+    }
+
+    pub fn due(&mut self, now: DateTime<Local>) -> Option<(NaiveDate, DateTime<Local>)> {
+        let today = self.day_of(now);
+        let previous = self.day.replace(today)?;
+        if previous == today {
+            return None;
+        }
+        let start = today.and_hms_opt(self.day_start_hour, 0, 0)?;
+        let boundary = Local
+            .from_local_datetime(&start)
+            .earliest()
+            .unwrap_or(now)
+            .min(now);
+        Some((previous, boundary))
+    }
+
+    pub fn write(
+        &self,
+        store: &dyn Store,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+        day: NaiveDate,
+    ) -> Result<String> {
+        let stamp = day.format("%Y%m%d").to_string();
+        let path = report::summary_path(&self.output_dir, &stamp, self.format)?;
+        let query = Query {
+            from: Some(from),
+            to: Some(to),
+        };
+        report::write_summary_to(
+            store,
+            &query,
+            &self.goals,
+            &View::grouped(self.group),
+            self.language,
+            &path,
+            self.format,
+        )
+    }
+}
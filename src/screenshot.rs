@@ -0,0 +1,357 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{DateTime, Local};
+#[cfg(windows)]
+use windows::Win32::Foundation::COLORREF;
+#[cfg(windows)]
+use windows::Win32::Graphics::Gdi::{
+    BI_RGB, BITMAPINFO, BITMAPINFOHEADER, CreateCompatibleBitmap, CreateCompatibleDC,
+    DIB_RGB_COLORS, DeleteDC, DeleteObject, GetDC, GetDIBits, HALFTONE, OPAQUE, ReleaseDC, SRCCOPY,
+    SelectObject, SetBkColor, SetBkMode, SetDIBits, SetStretchBltMode, SetTextColor, StretchBlt,
+    TextOutW,
+};
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN,
+};
+
+use crate::config::{RedactRegion, ScreenshotConfig};
+use crate::store::Result;
+use crate::window::get_active_window_title;
+
+pub const SCREENSHOT_KIND: &str = "screenshot";
+const EXTENSION: &str = "bmp";
+const REDACT_RADIUS: u32 = 12;
+
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+    pub origin: (i32, i32),
+    pub scale: f64,
+}
+
+impl Thumbnail {
+    fn region(&self, region: &RedactRegion) -> (usize, usize, usize, usize) {
+        let to_x = |x: i32| ((x - self.origin.0) as f64 * self.scale).clamp(0.0, self.width as f64);
+        let to_y =
+            |y: i32| ((y - self.origin.1) as f64 * self.scale).clamp(0.0, self.height as f64);
+        (
+            to_x(region.x) as usize,
+            to_y(region.y) as usize,
+            to_x(region.x + region.width as i32).ceil() as usize,
+            to_y(region.y + region.height as i32).ceil() as usize,
+        )
+    }
+}
+
+#[cfg(windows)]
+fn bitmap_info(width: i32, height: i32) -> BITMAPINFO {
+    BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(windows)]
+pub fn capture(width: u32) -> Option<Thumbnail> {
+    unsafe {
+        let x = GetSystemMetrics(SM_XVIRTUALSCREEN);
+        let y = GetSystemMetrics(SM_YVIRTUALSCREEN);
+        let screen_width = GetSystemMetrics(SM_CXVIRTUALSCREEN);
+        let screen_height = GetSystemMetrics(SM_CYVIRTUALSCREEN);
+        if screen_width <= 0 || screen_height <= 0 {
+            return None;
+        }
+        let width = (width as i32).clamp(1, screen_width);
+        let height = (screen_height as i64 * width as i64 / screen_width as i64).max(1) as i32;
+
+        let screen = GetDC(None);
+        let memory = CreateCompatibleDC(Some(screen));
+        let bitmap = CreateCompatibleBitmap(screen, width, height);
+        let previous = SelectObject(memory, bitmap.into());
+        SetStretchBltMode(memory, HALFTONE);
+        let copied = StretchBlt(
+            memory,
+            0,
+            0,
+            width,
+            height,
+            Some(screen),
+            x,
+            y,
+            screen_width,
+            screen_height,
+            SRCCOPY,
+        )
+        .as_bool();
+
+        let mut info = bitmap_info(width, height);
+        let mut pixels = vec![0u8; width as usize * height as usize * 4];
+        SelectObject(memory, previous);
+        let lines = if copied {
+            GetDIBits(
+                memory,
+                bitmap,
+                0,
+                height as u32,
+                Some(pixels.as_mut_ptr() as *mut std::ffi::c_void),
+                &mut info,
+                DIB_RGB_COLORS,
+            )
+        } else {
+            0
+        };
+
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(memory);
+        ReleaseDC(None, screen);
+
+        (lines == height).then_some(Thumbnail {
+            width: width as u32,
+            height: height as u32,
+            pixels,
+            origin: (x, y),
+            scale: width as f64 / screen_width as f64,
+        })
+    }
+}
+
+pub fn blur(thumbnail: &mut Thumbnail, radius: u32) {
+    let (width, height) = (thumbnail.width as usize, thumbnail.height as usize);
+    blur_area(thumbnail, (0, 0, width, height), radius);
+}
+
+fn blur_area(thumbnail: &mut Thumbnail, area: (usize, usize, usize, usize), radius: u32) {
+    if radius == 0 {
+        return;
+    }
+    let (width, height) = (thumbnail.width as usize, thumbnail.height as usize);
+    let (left, top, right, bottom) = area;
+    let radius = radius as usize;
+    let source = thumbnail.pixels.clone();
+    for y in top..bottom.min(height) {
+        for x in left..right.min(width) {
+            let mut sum = [0u32; 3];
+            let mut count = 0;
+            for ny in y.saturating_sub(radius)..(y + radius + 1).min(height) {
+                for nx in x.saturating_sub(radius)..(x + radius + 1).min(width) {
+                    let i = (ny * width + nx) * 4;
+                    for (c, total) in sum.iter_mut().enumerate() {
+                        *total += source[i + c] as u32;
+                    }
+                    count += 1;
+                }
+            }
+            let i = (y * width + x) * 4;
+            for (c, total) in sum.iter().enumerate() {
+                thumbnail.pixels[i + c] = (total / count) as u8;
+            }
+        }
+    }
+}
+
+pub fn redact(thumbnail: &mut Thumbnail, regions: &[RedactRegion]) {
+    for region in regions {
+        let area = thumbnail.region(region);
+        blur_area(thumbnail, area, REDACT_RADIUS);
+    }
+}
+
+#[cfg(not(windows))]
+pub fn capture(_width: u32) -> Option<Thumbnail> {
+    None
+}
+
+#[cfg(windows)]
+pub fn draw_label(thumbnail: &mut Thumbnail, label: &str) {
+    let (width, height) = (thumbnail.width as i32, thumbnail.height as i32);
+    let text: Vec<u16> = label.encode_utf16().collect();
+    unsafe {
+        let screen = GetDC(None);
+        let memory = CreateCompatibleDC(Some(screen));
+        let bitmap = CreateCompatibleBitmap(screen, width, height);
+        let mut info = bitmap_info(width, height);
+        SetDIBits(
+            Some(memory),
+            bitmap,
+            0,
+            height as u32,
+            thumbnail.pixels.as_ptr() as *const std::ffi::c_void,
+            &info,
+            DIB_RGB_COLORS,
+        );
+        let previous = SelectObject(memory, bitmap.into());
+        SetBkMode(memory, OPAQUE);
+        SetBkColor(memory, COLORREF(0x000000));
+        SetTextColor(memory, COLORREF(0xFFFFFF));
+        let _ = TextOutW(memory, 4, 4, &text);
+        SelectObject(memory, previous);
+        GetDIBits(
+            memory,
+            bitmap,
+            0,
+            height as u32,
+            Some(thumbnail.pixels.as_mut_ptr() as *mut std::ffi::c_void),
+            &mut info,
+            DIB_RGB_COLORS,
+        );
+        let _ = DeleteObject(bitmap.into());
+        let _ = DeleteDC(memory);
+        ReleaseDC(None, screen);
+    }
+}
+
+#[cfg(not(windows))]
+pub fn draw_label(_thumbnail: &mut Thumbnail, _label: &str) {}
+
+pub fn read_bmp(path: &Path) -> Result<Thumbnail> {
+    let data = fs::read(path)?;
+    let field = |offset: usize| -> Result<i32> {
+        let bytes = data
+            .get(offset..offset + 4)
+            .ok_or("BMP 헤더가 잘렸습니다")?;
+        Ok(i32::from_le_bytes(bytes.try_into()?))
+    };
+    if !data.starts_with(b"BM") {
+        return Err(format!("BMP 파일이 아닙니다: {}", path.display()).into());
+    }
+    let offset = field(10)? as usize;
+    let width = field(18)?;
+    let height = field(22)?;
+    let bits = data
+        .get(28..30)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]));
+    if bits != Some(32) || height >= 0 {
+        return Err(format!("지원하지 않는 BMP 형식입니다: {}", path.display()).into());
+    }
+    let size = width as usize * (-height) as usize * 4;
+    let pixels = data
+        .get(offset..offset + size)
+        .ok_or("BMP 데이터가 잘렸습니다")?
+        .to_vec();
+    Ok(Thumbnail {
+        width: width as u32,
+        height: (-height) as u32,
+        pixels,
+        origin: (0, 0),
+        scale: 1.0,
+    })
+}
+
+pub fn write_bmp(path: &Path, thumbnail: &Thumbnail) -> Result<()> {
+    let image_size = thumbnail.pixels.len() as u32;
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(b"BM")?;
+    file.write_all(&(54 + image_size).to_le_bytes())?;
+    file.write_all(&0u32.to_le_bytes())?;
+    file.write_all(&54u32.to_le_bytes())?;
+    file.write_all(&40u32.to_le_bytes())?;
+    file.write_all(&(thumbnail.width as i32).to_le_bytes())?;
+    file.write_all(&(-(thumbnail.height as i32)).to_le_bytes())?;
+    file.write_all(&1u16.to_le_bytes())?;
+    file.write_all(&32u16.to_le_bytes())?;
+    file.write_all(&0u32.to_le_bytes())?;
+    file.write_all(&image_size.to_le_bytes())?;
+    file.write_all(&[0u8; 16])?;
+    file.write_all(&thumbnail.pixels)?;
+    file.flush()?;
+    Ok(())
+}
+
+pub struct Screenshots {
+    config: ScreenshotConfig,
+    last_capture: Option<Instant>,
+}
+
+impl Screenshots {
+    pub fn new(config: &ScreenshotConfig) -> Option<Screenshots> {
+        if config.enabled && !cfg!(windows) {
+            eprintln!("화면 캡처는 Windows에서만 지원되어 스크린샷을 남기지 않습니다");
+            return None;
+        }
+        config.enabled.then(|| Screenshots {
+            config: config.clone(),
+            last_capture: None,
+        })
+    }
+
+    pub fn on_switch(&self) -> bool {
+        self.config.on_switch
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.config.every_minutes.is_some_and(|minutes| {
+            self.last_capture
+                .is_none_or(|last| last.elapsed() >= Duration::from_secs(minutes * 60))
+        })
+    }
+
+    fn is_private(&self, title: &str) -> bool {
+        let title = title.to_lowercase();
+        self.config
+            .private_titles
+            .iter()
+            .any(|pattern| title.contains(&pattern.to_lowercase()))
+    }
+
+    pub fn capture(&mut self, now: DateTime<Local>) -> Result<Option<PathBuf>> {
+        self.last_capture = Some(Instant::now());
+        if get_active_window_title().is_some_and(|title| self.is_private(&title)) {
+            return Ok(None);
+        }
+        let mut thumbnail = capture(self.config.width).ok_or("화면을 캡처할 수 없습니다")?;
+        redact(&mut thumbnail, &self.config.redact);
+        blur(&mut thumbnail, self.config.blur);
+
+        fs::create_dir_all(&self.config.dir)?;
+        let path =
+            self.config
+                .dir
+                .join(format!("{}.{}", now.format("%Y%m%d_%H%M%S_%3f"), EXTENSION));
+        write_bmp(&path, &thumbnail)?;
+        self.prune()?;
+        Ok(Some(path))
+    }
+
+    fn prune(&self) -> Result<()> {
+        let max_age = Duration::from_secs(self.config.max_days * 24 * 3600);
+        let mut files: Vec<PathBuf> = Vec::new();
+        for entry in fs::read_dir(&self.config.dir)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_none_or(|extension| extension != EXTENSION)
+            {
+                continue;
+            }
+            let age = fs::metadata(&path)?
+                .modified()
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .unwrap_or_default();
+            if age > max_age {
+                fs::remove_file(&path)?;
+            } else {
+                files.push(path);
+            }
+        }
+        files.sort();
+        let excess = files.len().saturating_sub(self.config.max_files);
+        for path in &files[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use crate::compact::MERGE_KIND;
+use crate::config::Language;
+use crate::format::{format_duration, title_width, truncate_or_pad};
+use crate::gaps;
+use crate::goals;
+use crate::store::{Query, Result, Store};
+
+pub fn print_search(store: &dyn Store, text: &str, language: Language) -> Result<()> {
+    let needle = text.to_lowercase();
+    let matches = |value: &str| value.to_lowercase().contains(&needle);
+    let query = Query::default();
+
+    let intervals: Vec<_> = store
+        .query(&query)?
+        .into_iter()
+        .filter(|interval| {
+            matches(&interval.title) || interval.location.as_deref().is_some_and(matches)
+        })
+        .collect();
+    for interval in &intervals {
+        println!(
+            "{} ~ {} {} {:>10}",
+            interval.start.format("%Y-%m-%d %H:%M:%S"),
+            interval.end.format("%H:%M:%S"),
+            truncate_or_pad(&interval.title, title_width()),
+            format_duration(&interval.duration())
+        );
+    }
+
+    let annotations: Vec<_> = store
+        .annotations(&query)?
+        .into_iter()
+        .filter(|annotation| {
+            !gaps::is_internal(annotation)
+                && annotation.kind != MERGE_KIND
+                && matches(&annotation.text)
+        })
+        .collect();
+    for annotation in &annotations {
+        println!(
+            "{} {}",
+            annotation.time.format("%Y-%m-%d %H:%M:%S"),
+            goals::describe_note(annotation, language)
+        );
+    }
+
+    let total: Duration = intervals.iter().map(|interval| interval.duration()).sum();
+    println!(
+        "일치하는 구간 {}개, 기록 {}개, 총 {}",
+        intervals.len(),
+        annotations.len(),
+        format_duration(&total)
+    );
+    Ok(())
+}
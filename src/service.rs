@@ -0,0 +1,178 @@
+use std::fs;
+use std::sync::{Mutex, OnceLock};
+
+use windows::Win32::Foundation::ERROR_SERVICE_SPECIFIC_ERROR;
+use windows::Win32::System::Services::{
+    ChangeServiceConfig2W, CloseServiceHandle, ControlService, CreateServiceW, DeleteService,
+    OpenSCManagerW, OpenServiceW, RegisterServiceCtrlHandlerExW, SC_HANDLE, SC_MANAGER_CONNECT,
+    SC_MANAGER_CREATE_SERVICE, SERVICE_ACCEPT_SHUTDOWN, SERVICE_ACCEPT_STOP, SERVICE_ALL_ACCESS,
+    SERVICE_AUTO_START, SERVICE_CONFIG_DESCRIPTION, SERVICE_CONTROL_SHUTDOWN, SERVICE_CONTROL_STOP,
+    SERVICE_DESCRIPTIONW, SERVICE_ERROR_NORMAL, SERVICE_RUNNING, SERVICE_STATUS,
+    SERVICE_STATUS_CURRENT_STATE, SERVICE_STATUS_HANDLE, SERVICE_STOP_PENDING, SERVICE_STOPPED,
+    SERVICE_TABLE_ENTRYW, SERVICE_WIN32_OWN_PROCESS, SetServiceStatus, StartServiceCtrlDispatcherW,
+    StartServiceW,
+};
+use windows::core::{HSTRING, PCWSTR, PWSTR};
+
+use crate::config::{self, ServiceConfig};
+use crate::store::Result;
+
+type Start = Box<dyn FnOnce() + Send>;
+type Stop = Box<dyn Fn() -> Result<String> + Send + Sync>;
+
+static NAME: OnceLock<HSTRING> = OnceLock::new();
+static START: Mutex<Option<Start>> = Mutex::new(None);
+static STOP: OnceLock<Stop> = OnceLock::new();
+static STATUS: OnceLock<usize> = OnceLock::new();
+
+fn report(state: SERVICE_STATUS_CURRENT_STATE, failed: bool) {
+    let Some(&handle) = STATUS.get() else {
+        return;
+    };
+    let status = SERVICE_STATUS {
+        dwServiceType: SERVICE_WIN32_OWN_PROCESS,
+        dwCurrentState: state,
+        dwControlsAccepted: if state == SERVICE_RUNNING {
+            SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN
+        } else {
+            0
+        },
+        dwWin32ExitCode: if failed {
+            ERROR_SERVICE_SPECIFIC_ERROR.0
+        } else {
+            0
+        },
+        dwServiceSpecificExitCode: u32::from(failed),
+        ..Default::default()
+    };
+    unsafe {
+        let _ = SetServiceStatus(SERVICE_STATUS_HANDLE(handle as *mut _), &status);
+    }
+}
+
+unsafe extern "system" fn control_handler(
+    control: u32,
+    _event: u32,
+    _data: *mut core::ffi::c_void,
+    _context: *mut core::ffi::c_void,
+) -> u32 {
+    if control == SERVICE_CONTROL_STOP || control == SERVICE_CONTROL_SHUTDOWN {
+        report(SERVICE_STOP_PENDING, false);
+        let failed = STOP.get().is_some_and(|stop| stop().is_err());
+        report(SERVICE_STOPPED, failed);
+        std::process::exit(i32::from(failed));
+    }
+    0
+}
+
+unsafe extern "system" fn service_main(_argc: u32, _argv: *mut PWSTR) {
+    let Some(name) = NAME.get() else {
+        return;
+    };
+    let Ok(handle) = (unsafe { RegisterServiceCtrlHandlerExW(name, Some(control_handler), None) })
+    else {
+        return;
+    };
+    let _ = STATUS.set(handle.0 as usize);
+    report(SERVICE_RUNNING, false);
+    if let Some(start) = START.lock().unwrap().take() {
+        start();
+    }
+    report(SERVICE_STOPPED, false);
+}
+
+pub fn on_stop(stop: impl Fn() -> Result<String> + Send + Sync + 'static) {
+    let _ = STOP.set(Box::new(stop));
+}
+
+pub fn run(name: &str, start: impl FnOnce() + Send + 'static) -> Result<()> {
+    let name = NAME.get_or_init(|| HSTRING::from(name));
+    *START.lock().unwrap() = Some(Box::new(start));
+    let table = [
+        SERVICE_TABLE_ENTRYW {
+            lpServiceName: PWSTR(name.as_ptr() as *mut u16),
+            lpServiceProc: Some(service_main),
+        },
+        SERVICE_TABLE_ENTRYW::default(),
+    ];
+    unsafe { StartServiceCtrlDispatcherW(table.as_ptr()) }?;
+    Ok(())
+}
+
+struct Handle(SC_HANDLE);
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseServiceHandle(self.0);
+        }
+    }
+}
+
+pub fn install(config: &ServiceConfig) -> Result<()> {
+    let exe = std::env::current_exe()?;
+    let config_path = std::path::absolute(config::path())?;
+    let command = format!(
+        "\"{}\" --config \"{}\" --service",
+        exe.display(),
+        config_path.display()
+    );
+    fs::create_dir_all(&config.data_dir)?;
+    unsafe {
+        let manager = Handle(OpenSCManagerW(
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SC_MANAGER_CREATE_SERVICE,
+        )?);
+        let service = Handle(CreateServiceW(
+            manager.0,
+            &HSTRING::from(config.name.as_str()),
+            &HSTRING::from("window-change-detector"),
+            SERVICE_ALL_ACCESS,
+            SERVICE_WIN32_OWN_PROCESS,
+            SERVICE_AUTO_START,
+            SERVICE_ERROR_NORMAL,
+            &HSTRING::from(command),
+            PCWSTR::null(),
+            None,
+            PCWSTR::null(),
+            PCWSTR::null(),
+            PCWSTR::null(),
+        )?);
+        let mut description: Vec<u16> = "활성 창 변경을 감지하고 창 별 사용 시간을 기록합니다"
+            .encode_utf16()
+            .chain([0])
+            .collect();
+        let description = SERVICE_DESCRIPTIONW {
+            lpDescription: PWSTR(description.as_mut_ptr()),
+        };
+        let _ = ChangeServiceConfig2W(
+            service.0,
+            SERVICE_CONFIG_DESCRIPTION,
+            Some(&description as *const _ as *const _),
+        );
+        if let Err(e) = StartServiceW(service.0, None) {
+            eprintln!("서비스를 등록했지만 시작하지 못했습니다: {}", e);
+        }
+    }
+    Ok(())
+}
+
+pub fn uninstall(config: &ServiceConfig) -> Result<()> {
+    unsafe {
+        let manager = Handle(OpenSCManagerW(
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SC_MANAGER_CONNECT,
+        )?);
+        let service = Handle(OpenServiceW(
+            manager.0,
+            &HSTRING::from(config.name.as_str()),
+            SERVICE_ALL_ACCESS,
+        )?);
+        let mut status = SERVICE_STATUS::default();
+        let _ = ControlService(service.0, SERVICE_CONTROL_STOP, &mut status);
+        DeleteService(service.0)?;
+    }
+    Ok(())
+}
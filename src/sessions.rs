@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::config::Language;
+use crate::format::format_duration;
+use crate::gaps::{BOOT_KIND, LOGON_KIND, UNLOCK_KIND, WAKE_KIND, parse_time};
+use crate::report::app_name;
+use crate::stats::is_focus;
+use crate::store::{Interval, Query, Result, Store, summarize_intervals_by};
+
+pub struct Session {
+    pub kind: Option<String>,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub active: Duration,
+    pub top_app: Option<(String, Duration)>,
+}
+
+impl Session {
+    fn new(kind: Option<String>, intervals: &[Interval]) -> Option<Session> {
+        let start = intervals.iter().map(|interval| interval.start).min()?;
+        let end = intervals.iter().map(|interval| interval.end).max()?;
+        let active: Vec<Interval> = intervals
+            .iter()
+            .filter(|interval| is_focus(&interval.title))
+            .cloned()
+            .collect();
+        Some(Session {
+            kind,
+            start,
+            end,
+            active: active.iter().map(Interval::duration).sum(),
+            top_app: summarize_intervals_by(&active, |interval| {
+                Some(
+                    interval
+                        .exe
+                        .clone()
+                        .unwrap_or_else(|| app_name(&interval.title)),
+                )
+            })
+            .into_iter()
+            .next(),
+        })
+    }
+
+    pub fn line(&self, language: Language) -> String {
+        let kind = match self.kind.as_deref() {
+            Some(BOOT_KIND) => language.pick("부팅", "boot"),
+            Some(LOGON_KIND) => language.pick("로그온", "logon"),
+            Some(UNLOCK_KIND) => language.pick("잠금 해제", "unlock"),
+            Some(WAKE_KIND) => language.pick("절전 해제", "wake"),
+            _ => language.pick("기록 시작", "first record"),
+        };
+        let top_app = match &self.top_app {
+            Some((app, spent)) => format!("{} ({})", app, format_duration(spent)),
+            None => "-".to_string(),
+        };
+        format!(
+            "{} ~ {} [{}] {} {} / {} {}",
+            language.datetime(self.start),
+            language.time(self.end),
+            kind,
+            language.pick("활동", "active"),
+            format_duration(&self.active),
+            language.pick("주 사용 앱", "top app"),
+            top_app
+        )
+    }
+}
+
+fn boundaries(store: &dyn Store, query: &Query) -> Result<Vec<(DateTime<Local>, String)>> {
+    let annotations = store.annotations(&Query {
+        from: None,
+        to: query.to,
+    })?;
+    let mut boundaries: Vec<(DateTime<Local>, String)> = annotations
+        .into_iter()
+        .filter_map(|annotation| match annotation.kind.as_str() {
+            BOOT_KIND | LOGON_KIND | UNLOCK_KIND | WAKE_KIND => {
+                Some((parse_time(&annotation.text)?, annotation.kind))
+            }
+            _ => None,
+        })
+        .collect();
+    boundaries.sort();
+    boundaries.dedup_by(|a, b| a.0 == b.0);
+    Ok(boundaries)
+}
+
+pub fn sessions(store: &dyn Store, query: &Query) -> Result<Vec<Session>> {
+    let boundaries = boundaries(store, query)?;
+    let mut intervals = store.query(query)?;
+    intervals.sort_by_key(|interval| interval.start);
+
+    let mut sessions = Vec::new();
+    let mut current: Option<String> = None;
+    let mut members: Vec<Interval> = Vec::new();
+    let mut next = 0;
+    for interval in intervals {
+        let mut started = None;
+        while next < boundaries.len() && boundaries[next].0 <= interval.start {
+            started = Some(boundaries[next].1.clone());
+            next += 1;
+        }
+        if let Some(kind) = started {
+            sessions.extend(Session::new(current.take(), &members));
+            members.clear();
+            current = Some(kind);
+        }
+        members.push(interval);
+    }
+    sessions.extend(Session::new(current, &members));
+    Ok(sessions)
+}
+
+pub fn print_sessions(store: &dyn Store, query: &Query, language: Language) -> Result<()> {
+    let sessions = sessions(store, query)?;
+    if sessions.is_empty() {
+        println!("기록된 세션이 없습니다");
+    }
+    for session in &sessions {
+        println!("{}", session.line(language));
+    }
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::store::Result;
+
+pub const KEY_FILE: &str = "signing.key";
+pub const PUBLIC_KEY_FILE: &str = "signing.pub";
+const ALGORITHM: &str = "ed25519";
+
+#[derive(Serialize, Deserialize)]
+struct SignatureFile {
+    algorithm: String,
+    public_key: String,
+    signature: String,
+}
+
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+fn decode_key(text: &str) -> Result<[u8; 32]> {
+    STANDARD
+        .decode(text.trim())?
+        .try_into()
+        .map_err(|_| "키 길이가 올바르지 않습니다".into())
+}
+
+fn load_or_create_key() -> Result<SigningKey> {
+    let path = Path::new(KEY_FILE);
+    if path.exists() {
+        let text = fs::read_to_string(path)?;
+        return Ok(SigningKey::from_bytes(&decode_key(&text)?));
+    }
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).map_err(|e| e.to_string())?;
+    let key = SigningKey::from_bytes(&seed);
+    fs::write(path, STANDARD.encode(seed))?;
+    fs::write(
+        PUBLIC_KEY_FILE,
+        STANDARD.encode(key.verifying_key().as_bytes()),
+    )?;
+    Ok(key)
+}
+
+pub fn sign_file(path: &Path) -> Result<(PathBuf, String)> {
+    let key = load_or_create_key()?;
+    let signature = key.sign(&fs::read(path)?);
+    let public_key = STANDARD.encode(key.verifying_key().as_bytes());
+    let file = SignatureFile {
+        algorithm: ALGORITHM.to_string(),
+        public_key: public_key.clone(),
+        signature: STANDARD.encode(signature.to_bytes()),
+    };
+    let signature_path = signature_path(path);
+    fs::write(&signature_path, serde_json::to_string_pretty(&file)?)?;
+    Ok((signature_path, public_key))
+}
+
+pub fn verify_file(path: &Path, signature: &Path, public_key: Option<&str>) -> Result<String> {
+    let file: SignatureFile = serde_json::from_str(&fs::read_to_string(signature)?)?;
+    if file.algorithm != ALGORITHM {
+        return Err(format!("지원하지 않는 서명 방식입니다: {}", file.algorithm).into());
+    }
+    if let Some(expected) = public_key {
+        let expected = match fs::read_to_string(expected) {
+            Ok(text) => text,
+            Err(_) => expected.to_string(),
+        };
+        if decode_key(&expected)? != decode_key(&file.public_key)? {
+            return Err("서명한 공개 키가 지정한 키와 다릅니다".into());
+        }
+    }
+    let key = VerifyingKey::from_bytes(&decode_key(&file.public_key)?)?;
+    let signature = Signature::from_slice(&STANDARD.decode(file.signature.trim())?)?;
+    key.verify_strict(&fs::read(path)?, &signature)
+        .map_err(|_| "서명이 일치하지 않습니다. 파일이 서명 후 변경되었을 수 있습니다")?;
+    Ok(file.public_key)
+}
+
+pub fn print_verify(path: &Path, signature: Option<&Path>, public_key: Option<&str>) -> Result<()> {
+    let signature = signature
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| signature_path(path));
+    let signer = verify_file(path, &signature, public_key)?;
+    println!("서명 확인됨: {}", path.display());
+    println!("서명한 공개 키: {}", signer);
+    if public_key.is_none() {
+        println!("서명한 사람까지 확인하려면 --public-key로 상대의 공개 키를 지정하세요");
+    }
+    Ok(())
+}
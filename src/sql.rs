@@ -0,0 +1,71 @@
+use std::path::Path;
+
+use serde_json::Value;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::export::csv_value;
+use crate::format::title_width;
+use crate::store::{Result, SqliteStore};
+
+fn pad(text: &str, width: usize) -> String {
+    let mut used = 0;
+    let mut result: String = text
+        .chars()
+        .map(|ch| if ch.is_control() { ' ' } else { ch })
+        .take_while(|ch| {
+            used += ch.width().unwrap_or(0);
+            used <= width
+        })
+        .collect();
+    result.push_str(&" ".repeat(width.saturating_sub(result.width())));
+    result
+}
+
+pub fn print_sql(path: &Path, sql: &str, csv: bool) -> Result<()> {
+    if !path.is_file() {
+        return Err(format!("SQLite 저장소 파일이 없습니다: {}", path.display()).into());
+    }
+    let (names, rows) = SqliteStore::open_read_only(path)?.select(sql)?;
+    if csv {
+        for row in std::iter::once(&names).chain(&rows) {
+            let cells: Vec<String> = row
+                .iter()
+                .map(|cell| csv_value(&Value::String(cell.clone())))
+                .collect();
+            println!("{}", cells.join(","));
+        }
+        return Ok(());
+    }
+
+    let widths: Vec<usize> = (0..names.len())
+        .map(|i| {
+            std::iter::once(&names[i])
+                .chain(rows.iter().map(|row| &row[i]))
+                .map(|cell| cell.width())
+                .max()
+                .unwrap_or(0)
+                .min(title_width())
+        })
+        .collect();
+    let line = |row: &[String]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| pad(cell, *width))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+    println!("{}", line(&names));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("-+-")
+    );
+    for row in &rows {
+        println!("{}", line(row));
+    }
+    println!("{}개 행", rows.len());
+    Ok(())
+}
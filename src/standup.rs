@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{Days, Local, NaiveDate};
+
+use crate::categories::UNCATEGORIZED;
+use crate::config::{Goal, Language};
+use crate::report::{app_name, category};
+use crate::stats::is_focus;
+use crate::store::{Interval, Query, Result, Store, summarize_intervals_by};
+
+const MAX_ITEMS: usize = 4;
+const LOOKBACK_DAYS: u64 = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Style {
+    Markdown,
+    Slack,
+}
+
+impl Style {
+    fn bold(self, text: &str) -> String {
+        match self {
+            Style::Markdown => format!("**{}**", text),
+            Style::Slack => format!("*{}*", text),
+        }
+    }
+}
+
+fn group(interval: &Interval, goals: &[Goal]) -> (String, bool) {
+    match category(&interval.title, interval.exe.as_deref(), goals) {
+        name if name == UNCATEGORIZED => (app_name(&interval.title), false),
+        name => (name, true),
+    }
+}
+
+fn items(intervals: &[&Interval], goals: &[Goal], language: Language) -> Vec<String> {
+    let mut groups = BTreeMap::<String, (bool, Vec<Interval>)>::new();
+    for interval in intervals {
+        let (name, is_category) = group(interval, goals);
+        let entry = groups.entry(name).or_insert((is_category, Vec::new()));
+        entry.1.push((*interval).clone());
+    }
+    let mut groups: Vec<_> = groups
+        .into_iter()
+        .map(|(name, (is_category, intervals))| {
+            let total: Duration = intervals.iter().map(Interval::duration).sum();
+            (name, is_category, intervals, total)
+        })
+        .collect();
+    groups.sort_by_key(|group| std::cmp::Reverse(group.3));
+
+    let mut items: Vec<String> = groups
+        .iter()
+        .take(MAX_ITEMS)
+        .map(|(name, is_category, intervals, total)| {
+            let hours = format!("{:.1}h {}", total.as_secs_f64() / 3600.0, name);
+            let top_app =
+                summarize_intervals_by(intervals, |interval| Some(app_name(&interval.title)))
+                    .into_iter()
+                    .next();
+            match top_app {
+                Some((app, _)) if *is_category => {
+                    format!("{} ({} {})", hours, language.pick("주로", "mostly"), app)
+                }
+                _ => hours,
+            }
+        })
+        .collect();
+    let rest: Duration = groups.iter().skip(MAX_ITEMS).map(|group| group.3).sum();
+    if !rest.is_zero() {
+        items.push(format!(
+            "{:.1}h {}",
+            rest.as_secs_f64() / 3600.0,
+            language.pick("기타", "other")
+        ));
+    }
+    items
+}
+
+fn day_heading(day: NaiveDate, today: NaiveDate, language: Language) -> String {
+    if today.checked_sub_days(Days::new(1)) == Some(day) {
+        language.pick("어제", "Yesterday").to_string()
+    } else {
+        language.date(day)
+    }
+}
+
+pub fn snippet(
+    goals: &[Goal],
+    store: &dyn Store,
+    style: Style,
+    language: Language,
+) -> Result<String> {
+    let today = Local::now().date_naive();
+    let history = store.query(&Query::last_days(LOOKBACK_DAYS))?;
+    let focused: Vec<&Interval> = history
+        .iter()
+        .filter(|interval| is_focus(&interval.title))
+        .collect();
+    let previous_day = focused
+        .iter()
+        .map(|interval| interval.start.date_naive())
+        .filter(|day| *day < today)
+        .max();
+
+    let mut lines = Vec::new();
+    let mut section = |heading: String, day: NaiveDate| {
+        let intervals: Vec<&Interval> = focused
+            .iter()
+            .copied()
+            .filter(|interval| interval.start.date_naive() == day)
+            .collect();
+        let total: Duration = intervals.iter().map(|interval| interval.duration()).sum();
+        lines.push(format!(
+            "{} ({:.1}h)",
+            style.bold(&heading),
+            total.as_secs_f64() / 3600.0
+        ));
+        let entries = items(&intervals, goals, language);
+        if entries.is_empty() {
+            lines.push(format!(
+                "- {}",
+                language.pick("기록 없음", "nothing tracked")
+            ));
+        }
+        lines.extend(entries.into_iter().map(|item| format!("- {}", item)));
+    };
+    if let Some(day) = previous_day {
+        section(day_heading(day, today, language), day);
+    }
+    section(
+        language.pick("오늘 지금까지", "Today so far").to_string(),
+        today,
+    );
+    Ok(lines.join("\n"))
+}
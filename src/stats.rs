@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Days, Local, NaiveDate};
+
+use crate::config::{Goal, Language};
+use crate::format::{Icon, format_duration, icon};
+use crate::goals;
+use crate::idle::{
+    DISPLAY_REQUIRED_LABEL, IDLE_LABEL, LOCKED_LABEL, SCREENSAVER_LABEL, SLEEP_LABEL,
+};
+use crate::remote::REMOTE_LABEL;
+use crate::store::Interval;
+use crate::tracker::OFF_HOURS_LABEL;
+use crate::window::DESKTOP_LABEL;
+
+const NON_FOCUS_LABELS: &[&str] = &[
+    IDLE_LABEL,
+    SCREENSAVER_LABEL,
+    DISPLAY_REQUIRED_LABEL,
+    LOCKED_LABEL,
+    SLEEP_LABEL,
+    OFF_HOURS_LABEL,
+    REMOTE_LABEL,
+    DESKTOP_LABEL,
+];
+
+pub fn is_focus(title: &str) -> bool {
+    !NON_FOCUS_LABELS.contains(&title)
+}
+
+pub struct Streak<'a> {
+    pub goal: &'a Goal,
+    pub current: u32,
+    pub best: u32,
+}
+
+pub enum Badge {
+    Streak(u32),
+    Focus(u64),
+    WithinLimit(String),
+    GoalMet(String),
+}
+
+impl Badge {
+    pub fn text(&self, language: Language) -> String {
+        match self {
+            Badge::Streak(days) => {
+                let badge = match *days {
+                    30.. => Icon::Trophy,
+                    7.. => Icon::Streak,
+                    _ => Icon::ShortStreak,
+                };
+                format!(
+                    "{}{} {}",
+                    icon(badge),
+                    language.days(*days),
+                    language.pick("연속", "streak")
+                )
+            }
+            Badge::Focus(hours) => {
+                let badge = if *hours >= 4 {
+                    Icon::DeepFocus
+                } else {
+                    Icon::Focus
+                };
+                match language {
+                    Language::Ko => format!("{}{}시간 몰입", icon(badge), hours),
+                    Language::En => format!("{}{}h deep focus", icon(badge), hours),
+                }
+            }
+            Badge::WithinLimit(name) => format!(
+                "{}{}: {}",
+                icon(Icon::WithinLimit),
+                language.pick("한도 준수", "Within limit"),
+                name
+            ),
+            Badge::GoalMet(name) => format!(
+                "{}{}: {}",
+                icon(Icon::Target),
+                language.pick("목표 달성", "Goal met"),
+                name
+            ),
+        }
+    }
+}
+
+pub struct Stats<'a> {
+    pub streaks: Vec<Streak<'a>>,
+    pub longest_focus: Option<&'a Interval>,
+    pub best_day: Option<(NaiveDate, Duration)>,
+    pub badges: Vec<Badge>,
+}
+
+fn daily_target(goal: &Goal) -> Option<Duration> {
+    let total = goal.min_hours?;
+    let hours = goal.daily_hours.unwrap_or(total / goal.period.days());
+    Some(Duration::from_secs_f64(hours.max(0.0) * 3600.0))
+}
+
+pub fn totals_by_day(
+    intervals: &[Interval],
+    matches: impl Fn(&Interval) -> bool,
+) -> BTreeMap<NaiveDate, Duration> {
+    let mut totals = BTreeMap::new();
+    for interval in intervals.iter().filter(|interval| matches(interval)) {
+        *totals
+            .entry(interval.start.date_naive())
+            .or_insert(Duration::ZERO) += interval.duration();
+    }
+    totals
+}
+
+fn daily_totals(goal: &Goal, intervals: &[Interval]) -> BTreeMap<NaiveDate, Duration> {
+    totals_by_day(intervals, |interval| goal.matches(&interval.title))
+}
+
+fn streak<'a>(goal: &'a Goal, intervals: &[Interval], today: NaiveDate) -> Option<Streak<'a>> {
+    let target = daily_target(goal)?;
+    let days: Vec<NaiveDate> = daily_totals(goal, intervals)
+        .into_iter()
+        .filter(|(_, total)| *total >= target)
+        .map(|(day, _)| day)
+        .collect();
+
+    let mut best = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in &days {
+        run = match previous {
+            Some(prev) if prev + Days::new(1) == *day => run + 1,
+            _ => 1,
+        };
+        best = best.max(run);
+        previous = Some(*day);
+    }
+
+    let mut current = 0;
+    let mut day = if days.last() == Some(&today) {
+        today
+    } else {
+        today - Days::new(1)
+    };
+    while days.binary_search(&day).is_ok() {
+        current += 1;
+        day = day - Days::new(1);
+    }
+
+    Some(Streak {
+        goal,
+        current,
+        best,
+    })
+}
+
+pub fn compute<'a>(
+    goals: &'a [Goal],
+    intervals: &'a [Interval],
+    now: DateTime<Local>,
+) -> Stats<'a> {
+    let today = now.date_naive();
+    let streaks: Vec<Streak> = goals
+        .iter()
+        .filter_map(|goal| streak(goal, intervals, today))
+        .collect();
+
+    let longest_focus = intervals
+        .iter()
+        .filter(|interval| is_focus(&interval.title))
+        .max_by_key(|interval| interval.duration());
+
+    let best_day = totals_by_day(intervals, |interval| is_focus(&interval.title))
+        .into_iter()
+        .max_by_key(|(_, total)| *total);
+
+    let mut badges = Vec::new();
+    let best = streaks.iter().map(|streak| streak.best).max();
+    if let Some(days) = best.and_then(|best| [30, 7, 3].into_iter().find(|days| best >= *days)) {
+        badges.push(Badge::Streak(days));
+    }
+    if let Some(hours) = longest_focus.and_then(|interval| {
+        [4, 2]
+            .into_iter()
+            .find(|hours| interval.duration() >= Duration::from_secs(hours * 3600))
+    }) {
+        badges.push(Badge::Focus(hours));
+    }
+    for progress in goals::progress(goals, intervals, now) {
+        if progress.is_limit() && progress.percent() < 100 {
+            badges.push(Badge::WithinLimit(progress.goal.name.clone()));
+        } else if !progress.is_limit() && progress.percent() >= 100 {
+            badges.push(Badge::GoalMet(progress.goal.name.clone()));
+        }
+    }
+
+    Stats {
+        streaks,
+        longest_focus,
+        best_day,
+        badges,
+    }
+}
+
+impl Stats<'_> {
+    pub fn lines(&self, language: Language) -> Vec<String> {
+        let mut lines = Vec::new();
+        for streak in &self.streaks {
+            lines.push(format!(
+                "{} - {}: {} {} / {} {}",
+                language.pick("연속 달성", "Streak"),
+                streak.goal.name,
+                language.pick("현재", "current"),
+                language.days(streak.current),
+                language.pick("최고", "best"),
+                language.days(streak.best)
+            ));
+        }
+        if let Some(interval) = self.longest_focus {
+            lines.push(format!(
+                "{}: {} ({}, {})",
+                language.pick("최장 몰입", "Longest focus"),
+                format_duration(&interval.duration()),
+                language.short_datetime(interval.start),
+                interval.title
+            ));
+        }
+        if let Some((day, total)) = &self.best_day {
+            lines.push(format!(
+                "{}: {} ({})",
+                language.pick("최고의 하루", "Best day"),
+                language.date(*day),
+                format_duration(total)
+            ));
+        }
+        if !self.badges.is_empty() {
+            let badges: Vec<String> = self
+                .badges
+                .iter()
+                .map(|badge| badge.text(language))
+                .collect();
+            lines.push(format!(
+                "{}: {}",
+                language.pick("배지", "Badges"),
+                badges.join(", ")
+            ));
+        }
+        lines
+    }
+}
@@ -0,0 +1,196 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Annotation, Interval, Query, Result, Store};
+use crate::ids;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Record {
+    Interval(Interval),
+    Annotation(Annotation),
+}
+
+pub struct JsonlStore {
+    path: PathBuf,
+    file: Option<File>,
+    snapshot: Option<Vec<Record>>,
+}
+
+impl JsonlStore {
+    pub fn open(path: &Path) -> Result<JsonlStore> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(JsonlStore {
+            path: path.to_path_buf(),
+            file: Some(file),
+            snapshot: None,
+        })
+    }
+
+    pub fn open_read_only(path: &Path) -> Result<JsonlStore> {
+        let mut store = JsonlStore {
+            path: path.to_path_buf(),
+            file: None,
+            snapshot: None,
+        };
+        store.snapshot = Some(store.read_records()?);
+        Ok(store)
+    }
+}
+
+impl JsonlStore {
+    fn append(&mut self, record: &Record) -> Result<()> {
+        let file = self.file.as_mut().ok_or("읽기 전용 저장소입니다")?;
+        let line = serde_json::to_string(record)?;
+        writeln!(file, "{}", line)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    fn records(&self) -> Result<Vec<Record>> {
+        match &self.snapshot {
+            Some(records) => Ok(records.clone()),
+            None => self.read_records(),
+        }
+    }
+
+    fn read_records(&self) -> Result<Vec<Record>> {
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut records = Vec::new();
+        let mut lines = reader.lines().peekable();
+        while let Some(line) = lines.next() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(record) => records.push(record),
+                Err(_) if lines.peek().is_none() => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(records)
+    }
+}
+
+impl Store for JsonlStore {
+    fn append_interval(&mut self, interval: &Interval) -> Result<()> {
+        self.append(&Record::Interval(interval.clone()))
+    }
+
+    fn query(&self, query: &Query) -> Result<Vec<Interval>> {
+        Ok(self
+            .records()?
+            .into_iter()
+            .filter_map(|record| match record {
+                Record::Interval(mut interval) if query.matches(&interval) => {
+                    if interval.id.is_nil() {
+                        interval.id =
+                            ids::legacy_interval_id(interval.start, interval.end, &interval.title);
+                    }
+                    Some(interval)
+                }
+                _ => None,
+            })
+            .collect())
+    }
+
+    fn replace_intervals(&mut self, query: &Query, intervals: &[Interval]) -> Result<()> {
+        if self.file.is_none() {
+            return Err("읽기 전용 저장소입니다".into());
+        }
+        let mut text = String::new();
+        let kept = self.records()?.into_iter().filter(|record| match record {
+            Record::Interval(interval) => !query.matches(interval),
+            Record::Annotation(_) => true,
+        });
+        let added = intervals
+            .iter()
+            .map(|interval| Record::Interval(interval.clone()));
+        for record in kept.chain(added) {
+            text.push_str(&serde_json::to_string(&record)?);
+            text.push('\n');
+        }
+        let temp = self.path.with_extension("jsonl.tmp");
+        fs::write(&temp, text)?;
+        self.file = None;
+        fs::rename(&temp, &self.path)?;
+        self.file = Some(OpenOptions::new().append(true).open(&self.path)?);
+        Ok(())
+    }
+
+    fn append_annotation(&mut self, annotation: &Annotation) -> Result<()> {
+        self.append(&Record::Annotation(annotation.clone()))
+    }
+
+    fn annotations(&self, query: &Query) -> Result<Vec<Annotation>> {
+        Ok(self
+            .records()?
+            .into_iter()
+            .filter_map(|record| match record {
+                Record::Annotation(annotation) if query.contains(annotation.time) => {
+                    Some(annotation)
+                }
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeDelta};
+
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("wcd_{}_{}.jsonl", name, std::process::id()))
+    }
+
+    fn line(title: &str) -> String {
+        let end = Local::now();
+        let interval = Interval::new(end - TimeDelta::seconds(10), end, title.to_string(), None);
+        serde_json::to_string(&Record::Interval(interval)).unwrap()
+    }
+
+    #[test]
+    fn read_only_store_skips_a_truncated_last_line() {
+        let path = temp_path("truncated");
+        let partial = line("c");
+        let text = format!(
+            "{}\n{}\n{}",
+            line("a"),
+            line("b"),
+            &partial[..partial.len() / 2]
+        );
+        fs::write(&path, text).unwrap();
+        let titles: Vec<String> = JsonlStore::open_read_only(&path)
+            .unwrap()
+            .query(&Query::default())
+            .unwrap()
+            .into_iter()
+            .map(|interval| interval.title)
+            .collect();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(titles, ["a", "b"]);
+    }
+
+    #[test]
+    fn read_only_store_rejects_a_broken_line_in_the_middle() {
+        let path = temp_path("broken");
+        let partial = line("b");
+        let text = format!(
+            "{}\n{}\n{}\n",
+            line("a"),
+            &partial[..partial.len() / 2],
+            line("c")
+        );
+        fs::write(&path, text).unwrap();
+        let opened = JsonlStore::open_read_only(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(opened.is_err());
+    }
+}
@@ -0,0 +1,59 @@
+use super::{Annotation, Interval, Query, Result, Store};
+
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    intervals: Vec<Interval>,
+    annotations: Vec<Annotation>,
+}
+
+impl MemoryStore {
+    pub fn from_intervals(intervals: Vec<Interval>) -> MemoryStore {
+        MemoryStore {
+            intervals,
+            annotations: Vec::new(),
+        }
+    }
+
+    pub fn snapshot(store: &dyn Store, query: &Query) -> Result<MemoryStore> {
+        Ok(MemoryStore {
+            intervals: store.query(query)?,
+            annotations: store.annotations(query)?,
+        })
+    }
+}
+
+impl Store for MemoryStore {
+    fn append_interval(&mut self, interval: &Interval) -> Result<()> {
+        self.intervals.push(interval.clone());
+        Ok(())
+    }
+
+    fn query(&self, query: &Query) -> Result<Vec<Interval>> {
+        Ok(self
+            .intervals
+            .iter()
+            .filter(|interval| query.matches(interval))
+            .cloned()
+            .collect())
+    }
+
+    fn replace_intervals(&mut self, query: &Query, intervals: &[Interval]) -> Result<()> {
+        self.intervals.retain(|interval| !query.matches(interval));
+        self.intervals.extend_from_slice(intervals);
+        Ok(())
+    }
+
+    fn append_annotation(&mut self, annotation: &Annotation) -> Result<()> {
+        self.annotations.push(annotation.clone());
+        Ok(())
+    }
+
+    fn annotations(&self, query: &Query) -> Result<Vec<Annotation>> {
+        Ok(self
+            .annotations
+            .iter()
+            .filter(|annotation| query.contains(annotation.time))
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,265 @@
+mod jsonl;
+mod memory;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod sqlite;
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Days, Local, NaiveDate, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::{StoreBackend, StoreConfig};
+
+pub use jsonl::JsonlStore;
+pub use memory::MemoryStore;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresStore;
+pub use sqlite::SqliteStore;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interval {
+    #[serde(default)]
+    pub id: Uuid,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exe: Option<String>,
+}
+
+impl Interval {
+    pub fn new(
+        start: DateTime<Local>,
+        end: DateTime<Local>,
+        title: String,
+        location: Option<String>,
+    ) -> Interval {
+        Interval {
+            id: Uuid::new_v4(),
+            start,
+            end,
+            title,
+            location,
+            tag: None,
+            exe: None,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        (self.end - self.start).to_std().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Annotation {
+    pub time: DateTime<Local>,
+    pub kind: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DailyTotal {
+    pub date: NaiveDate,
+    pub title: String,
+    pub exe: Option<String>,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    pub from: Option<DateTime<Local>>,
+    pub to: Option<DateTime<Local>>,
+}
+
+impl Query {
+    pub fn between(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Query {
+        let start = |date: NaiveDate| {
+            Local
+                .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+                .earliest()
+        };
+        Query {
+            from: from.and_then(start),
+            to: to.and_then(|to| to.succ_opt()).and_then(start),
+        }
+    }
+
+    pub fn last_days(days: u64) -> Query {
+        Query::between(
+            Local::now().date_naive().checked_sub_days(Days::new(days)),
+            None,
+        )
+    }
+
+    pub fn matches(&self, interval: &Interval) -> bool {
+        self.from.is_none_or(|from| interval.end > from)
+            && self.to.is_none_or(|to| interval.start < to)
+    }
+
+    pub fn contains(&self, time: DateTime<Local>) -> bool {
+        self.from.is_none_or(|from| time >= from) && self.to.is_none_or(|to| time < to)
+    }
+
+    pub fn is_whole_days(&self) -> bool {
+        [self.from, self.to]
+            .into_iter()
+            .flatten()
+            .all(|time| time.time() == NaiveTime::MIN)
+    }
+
+    pub fn contains_day(&self, date: NaiveDate) -> bool {
+        self.from.is_none_or(|from| date >= from.date_naive())
+            && self.to.is_none_or(|to| {
+                date < to.date_naive() || (date == to.date_naive() && to.time() != NaiveTime::MIN)
+            })
+    }
+}
+
+pub trait Store: Send {
+    fn append_interval(&mut self, interval: &Interval) -> Result<()>;
+
+    fn query(&self, query: &Query) -> Result<Vec<Interval>>;
+
+    fn append_annotation(&mut self, annotation: &Annotation) -> Result<()>;
+
+    fn annotations(&self, query: &Query) -> Result<Vec<Annotation>>;
+
+    fn summarize(&self, query: &Query) -> Result<Vec<(String, Duration)>> {
+        Ok(summarize_intervals(&self.query(query)?))
+    }
+
+    fn daily_totals(&self, query: &Query) -> Result<Vec<DailyTotal>> {
+        Ok(daily_totals(&self.query(query)?, query))
+    }
+
+    fn replace_intervals(&mut self, _query: &Query, _intervals: &[Interval]) -> Result<()> {
+        Err("이 저장소는 기록 압축을 지원하지 않습니다".into())
+    }
+}
+
+pub fn summarize_intervals(intervals: &[Interval]) -> Vec<(String, Duration)> {
+    summarize_intervals_by(intervals, |interval| Some(interval.title.clone()))
+}
+
+pub fn summarize_intervals_by(
+    intervals: &[Interval],
+    key: impl Fn(&Interval) -> Option<String>,
+) -> Vec<(String, Duration)> {
+    let mut totals = HashMap::<String, Duration>::new();
+    for interval in intervals {
+        let Some(key) = key(interval) else {
+            continue;
+        };
+        *totals.entry(key).or_insert(Duration::new(0, 0)) += interval.duration();
+    }
+    let mut entries: Vec<_> = totals.into_iter().collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+    entries
+}
+
+pub fn split_by_day(interval: &Interval) -> Vec<(NaiveDate, Duration)> {
+    let mut parts = Vec::new();
+    let mut start = interval.start;
+    while start < interval.end {
+        let next = start
+            .date_naive()
+            .succ_opt()
+            .and_then(|date| {
+                Local
+                    .from_local_datetime(&date.and_time(NaiveTime::MIN))
+                    .earliest()
+            })
+            .filter(|midnight| *midnight < interval.end)
+            .unwrap_or(interval.end);
+        parts.push((
+            start.date_naive(),
+            (next - start).to_std().unwrap_or_default(),
+        ));
+        start = next;
+    }
+    parts
+}
+
+pub fn daily_totals(intervals: &[Interval], query: &Query) -> Vec<DailyTotal> {
+    let mut totals = HashMap::<(NaiveDate, String, Option<String>), Duration>::new();
+    for interval in intervals {
+        for (date, spent) in split_by_day(interval) {
+            if query.contains_day(date) {
+                *totals
+                    .entry((date, interval.title.clone(), interval.exe.clone()))
+                    .or_default() += spent;
+            }
+        }
+    }
+    let mut totals: Vec<DailyTotal> = totals
+        .into_iter()
+        .map(|((date, title, exe), duration)| DailyTotal {
+            date,
+            title,
+            exe,
+            duration,
+        })
+        .collect();
+    totals.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.title.cmp(&b.title)));
+    totals
+}
+
+pub fn open(config: &StoreConfig) -> Result<Box<dyn Store>> {
+    Ok(match config.backend {
+        StoreBackend::Memory => Box::new(MemoryStore::default()),
+        StoreBackend::Jsonl => Box::new(JsonlStore::open(config.path())?),
+        StoreBackend::Sqlite => Box::new(SqliteStore::open(config.path())?),
+        #[cfg(feature = "postgres")]
+        StoreBackend::Postgres => {
+            let url = config.url.as_deref().ok_or("store.url 설정이 필요합니다")?;
+            Box::new(PostgresStore::open(url, &config.user())?)
+        }
+        #[cfg(not(feature = "postgres"))]
+        StoreBackend::Postgres => {
+            return Err("postgres 기능 없이 빌드되었습니다 (--features postgres)".into());
+        }
+    })
+}
+
+pub fn vacuum(config: &StoreConfig) -> Result<()> {
+    match config.backend {
+        StoreBackend::Sqlite if config.path().is_file() => SqliteStore::vacuum_file(config.path()),
+        _ => Ok(()),
+    }
+}
+
+pub fn open_reader(config: &StoreConfig) -> Result<Box<dyn Store>> {
+    let path = config.path();
+    Ok(match config.backend {
+        StoreBackend::Jsonl if path.is_file() => Box::new(JsonlStore::open_read_only(path)?),
+        StoreBackend::Sqlite if path.is_file() => Box::new(SqliteStore::open_read_only(path)?),
+        _ => return open(config),
+    })
+}
+
+pub fn open_file(path: &Path) -> Result<Box<dyn Store>> {
+    if !path.is_file() {
+        return Err("파일이 없습니다".into());
+    }
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    Ok(match extension.as_str() {
+        "jsonl" => Box::new(JsonlStore::open_read_only(path)?),
+        "db" | "sqlite" | "sqlite3" => Box::new(SqliteStore::open_read_only(path)?),
+        _ => return Err("지원하지 않는 파일 형식입니다 (.db, .sqlite, .jsonl)".into()),
+    })
+}
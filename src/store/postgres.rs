@@ -0,0 +1,228 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use postgres::{Client, GenericClient, NoTls};
+use sha2::{Digest, Sha256};
+
+use super::{Annotation, Interval, Query, Result, Store};
+use crate::ids;
+
+pub struct PostgresStore {
+    client: Mutex<Client>,
+    schema: String,
+}
+
+fn schema_name(user: &str) -> String {
+    if !user.is_empty() && user.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return format!("wcd_{}", user.to_ascii_lowercase());
+    }
+    let name: String = user
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(32)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let hash: String = Sha256::digest(user.to_lowercase().as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("wcd_{}_{}", name, hash)
+}
+
+fn claim_schema(client: &mut Client, schema: &str, user: &str) -> Result<()> {
+    client.execute(
+        &format!(
+            "INSERT INTO {schema}.owner (name) SELECT $1
+             WHERE NOT EXISTS (SELECT 1 FROM {schema}.owner)"
+        ),
+        &[&user],
+    )?;
+    let owner: String = client
+        .query_one(&format!("SELECT name FROM {}.owner LIMIT 1", schema), &[])?
+        .get(0);
+    if owner.to_lowercase() != user.to_lowercase() {
+        return Err(format!(
+            "스키마 {}는 다른 사용자({})가 사용 중입니다. store.user를 바꿔 주세요",
+            schema, owner
+        )
+        .into());
+    }
+    Ok(())
+}
+
+const INTERVAL_RANGE: &str =
+    "($1::timestamptz IS NULL OR end_time > $1) AND ($2::timestamptz IS NULL OR start_time < $2)";
+const ANNOTATION_RANGE: &str =
+    "($1::timestamptz IS NULL OR time >= $1) AND ($2::timestamptz IS NULL OR time < $2)";
+
+fn range_params(query: &Query) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    (
+        query.from.map(|from| from.with_timezone(&Utc)),
+        query.to.map(|to| to.with_timezone(&Utc)),
+    )
+}
+
+fn insert_interval(
+    client: &mut impl GenericClient,
+    schema: &str,
+    interval: &Interval,
+) -> Result<()> {
+    client.execute(
+        &format!(
+            "INSERT INTO {}.sessions (title, start_time, end_time, duration, location, uuid, tag, exe)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+            schema
+        ),
+        &[
+            &interval.title,
+            &interval.start.with_timezone(&Utc),
+            &interval.end.with_timezone(&Utc),
+            &interval.duration().as_secs_f64(),
+            &interval.location,
+            &interval.id.to_string(),
+            &interval.tag,
+            &interval.exe,
+        ],
+    )?;
+    Ok(())
+}
+
+impl PostgresStore {
+    pub fn open(url: &str, user: &str) -> Result<PostgresStore> {
+        let mut client = Client::connect(url, NoTls)?;
+        let schema = schema_name(user);
+        client.batch_execute(&format!(
+            "CREATE SCHEMA IF NOT EXISTS {schema};
+            CREATE TABLE IF NOT EXISTS {schema}.sessions (
+                id BIGSERIAL PRIMARY KEY,
+                title TEXT NOT NULL,
+                start_time TIMESTAMPTZ NOT NULL,
+                end_time TIMESTAMPTZ NOT NULL,
+                duration DOUBLE PRECISION NOT NULL
+            );
+            ALTER TABLE {schema}.sessions ADD COLUMN IF NOT EXISTS location TEXT;
+            ALTER TABLE {schema}.sessions ADD COLUMN IF NOT EXISTS uuid TEXT;
+            ALTER TABLE {schema}.sessions ADD COLUMN IF NOT EXISTS tag TEXT;
+            ALTER TABLE {schema}.sessions ADD COLUMN IF NOT EXISTS exe TEXT;
+            CREATE INDEX IF NOT EXISTS sessions_start ON {schema}.sessions (start_time);
+            CREATE TABLE IF NOT EXISTS {schema}.annotations (
+                id BIGSERIAL PRIMARY KEY,
+                time TIMESTAMPTZ NOT NULL,
+                kind TEXT NOT NULL,
+                text TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS annotations_time ON {schema}.annotations (time);
+            CREATE TABLE IF NOT EXISTS {schema}.owner (name TEXT NOT NULL);"
+        ))?;
+        claim_schema(&mut client, &schema, user)?;
+        Ok(PostgresStore {
+            client: Mutex::new(client),
+            schema,
+        })
+    }
+}
+
+impl Store for PostgresStore {
+    fn append_interval(&mut self, interval: &Interval) -> Result<()> {
+        insert_interval(self.client.get_mut().unwrap(), &self.schema, interval)
+    }
+
+    fn query(&self, query: &Query) -> Result<Vec<Interval>> {
+        let (from, to) = range_params(query);
+        let rows = self.client.lock().unwrap().query(
+            &format!(
+                "SELECT title, start_time, end_time, location, uuid, tag, exe FROM {}.sessions
+                 WHERE {} ORDER BY start_time",
+                self.schema, INTERVAL_RANGE
+            ),
+            &[&from, &to],
+        )?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let title: String = row.get(0);
+                let start = row.get::<_, DateTime<Utc>>(1).with_timezone(&Local);
+                let end = row.get::<_, DateTime<Utc>>(2).with_timezone(&Local);
+                Interval {
+                    id: ids::parse_or_legacy(row.get(4), start, end, &title),
+                    start,
+                    end,
+                    title,
+                    location: row.get(3),
+                    tag: row.get(5),
+                    exe: row.get(6),
+                }
+            })
+            .collect())
+    }
+
+    fn summarize(&self, query: &Query) -> Result<Vec<(String, Duration)>> {
+        let (from, to) = range_params(query);
+        let rows = self.client.lock().unwrap().query(
+            &format!(
+                "SELECT title, SUM(duration) FROM {}.sessions
+                 WHERE {} GROUP BY title ORDER BY 2 DESC",
+                self.schema, INTERVAL_RANGE
+            ),
+            &[&from, &to],
+        )?;
+        Ok(rows
+            .iter()
+            .map(|row| (row.get(0), Duration::from_secs_f64(row.get(1))))
+            .collect())
+    }
+
+    fn replace_intervals(&mut self, query: &Query, intervals: &[Interval]) -> Result<()> {
+        let (from, to) = range_params(query);
+        let mut tx = self.client.get_mut().unwrap().transaction()?;
+        tx.execute(
+            &format!(
+                "DELETE FROM {}.sessions WHERE {}",
+                self.schema, INTERVAL_RANGE
+            ),
+            &[&from, &to],
+        )?;
+        for interval in intervals {
+            insert_interval(&mut tx, &self.schema, interval)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn append_annotation(&mut self, annotation: &Annotation) -> Result<()> {
+        let client = self.client.get_mut().unwrap();
+        client.execute(
+            &format!(
+                "INSERT INTO {}.annotations (time, kind, text) VALUES ($1, $2, $3)",
+                self.schema
+            ),
+            &[
+                &annotation.time.with_timezone(&Utc),
+                &annotation.kind,
+                &annotation.text,
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn annotations(&self, query: &Query) -> Result<Vec<Annotation>> {
+        let (from, to) = range_params(query);
+        let rows = self.client.lock().unwrap().query(
+            &format!(
+                "SELECT time, kind, text FROM {}.annotations
+                 WHERE {} ORDER BY time",
+                self.schema, ANNOTATION_RANGE
+            ),
+            &[&from, &to],
+        )?;
+        Ok(rows
+            .iter()
+            .map(|row| Annotation {
+                time: row.get::<_, DateTime<Utc>>(0).with_timezone(&Local),
+                kind: row.get(1),
+                text: row.get(2),
+            })
+            .collect())
+    }
+}
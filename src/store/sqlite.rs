@@ -0,0 +1,385 @@
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags, params};
+
+use super::{Annotation, DailyTotal, Interval, Query, Result, Store, split_by_day};
+use crate::ids;
+
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct SqliteStore {
+    conn: Connection,
+    columns: String,
+    daily: bool,
+}
+
+fn to_sql_time(time: &DateTime<Local>) -> String {
+    time.with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn from_sql_time(text: &str) -> rusqlite::Result<DateTime<Local>> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|time| time.with_timezone(&Local))
+        .map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, e.into())
+        })
+}
+
+fn range_params(query: &Query) -> (String, String) {
+    (
+        query
+            .from
+            .map(|from| to_sql_time(&from))
+            .unwrap_or_default(),
+        query
+            .to
+            .map(|to| to_sql_time(&to))
+            .unwrap_or_else(|| "9999".to_string()),
+    )
+}
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    Ok(conn
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1",
+            table
+        ))?
+        .exists(params![column])?)
+}
+
+fn ensure_column(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    if !has_column(conn, table, column)? {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, decl
+        ))?;
+    }
+    Ok(())
+}
+
+fn has_table(conn: &Connection, table: &str) -> Result<bool> {
+    Ok(conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1")?
+        .exists(params![table])?)
+}
+
+fn add_daily(conn: &Connection, interval: &Interval, days: &Query) -> Result<()> {
+    for (date, spent) in split_by_day(interval) {
+        if !days.contains_day(date) {
+            continue;
+        }
+        conn.execute(
+            "INSERT INTO daily (day, title, exe, duration) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (day, title, exe) DO UPDATE SET duration = duration + excluded.duration",
+            params![
+                date.to_string(),
+                interval.title,
+                interval.exe.as_deref().unwrap_or_default(),
+                spent.as_secs_f64()
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+fn rebuild_daily(conn: &Connection, query: &Query) -> Result<()> {
+    let days = Query::between(
+        query.from.map(|from| from.date_naive()),
+        query.to.map(|to| to.date_naive()),
+    );
+    let (from_day, to_day) = (
+        query
+            .from
+            .map(|from| from.date_naive().to_string())
+            .unwrap_or_default(),
+        query
+            .to
+            .map(|to| to.date_naive().to_string())
+            .unwrap_or_else(|| "9999".to_string()),
+    );
+    conn.execute(
+        "DELETE FROM daily WHERE day >= ?1 AND day <= ?2",
+        params![from_day, to_day],
+    )?;
+    let (from, to) = range_params(&days);
+    let mut stmt =
+        conn.prepare("SELECT title, start, end, exe FROM sessions WHERE end > ?1 AND start < ?2")?;
+    let rows = stmt.query_map(params![from, to], |row| {
+        let mut interval = Interval::new(
+            from_sql_time(&row.get::<_, String>(1)?)?,
+            from_sql_time(&row.get::<_, String>(2)?)?,
+            row.get(0)?,
+            None,
+        );
+        interval.exe = row.get(3)?;
+        Ok(interval)
+    })?;
+    for interval in rows {
+        add_daily(conn, &interval?, &days)?;
+    }
+    Ok(())
+}
+
+fn insert_interval(conn: &Connection, interval: &Interval) -> Result<()> {
+    conn.execute(
+        "INSERT INTO sessions (title, start, end, duration, location, uuid, tag, exe)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            interval.title,
+            to_sql_time(&interval.start),
+            to_sql_time(&interval.end),
+            interval.duration().as_secs_f64(),
+            interval.location,
+            interval.id.to_string(),
+            interval.tag,
+            interval.exe
+        ],
+    )?;
+    add_daily(conn, interval, &Query::default())
+}
+
+impl SqliteStore {
+    pub fn open(path: &Path) -> Result<SqliteStore> {
+        let mut conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+            PRAGMA synchronous = NORMAL;
+            CREATE TABLE IF NOT EXISTS sessions (
+                id INTEGER PRIMARY KEY,
+                title TEXT NOT NULL,
+                start TEXT NOT NULL,
+                end TEXT NOT NULL,
+                duration REAL NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS sessions_start ON sessions (start);
+            CREATE TABLE IF NOT EXISTS annotations (
+                id INTEGER PRIMARY KEY,
+                time TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                text TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS annotations_time ON annotations (time);",
+        )?;
+        ensure_column(&conn, "sessions", "location", "TEXT")?;
+        ensure_column(&conn, "sessions", "uuid", "TEXT")?;
+        ensure_column(&conn, "sessions", "tag", "TEXT")?;
+        ensure_column(&conn, "sessions", "exe", "TEXT")?;
+        if !has_table(&conn, "daily")? {
+            let tx = conn.transaction()?;
+            tx.execute_batch(
+                "CREATE TABLE daily (
+                    day TEXT NOT NULL,
+                    title TEXT NOT NULL,
+                    exe TEXT NOT NULL DEFAULT '',
+                    duration REAL NOT NULL,
+                    PRIMARY KEY (day, title, exe)
+                );",
+            )?;
+            rebuild_daily(&tx, &Query::default())?;
+            tx.commit()?;
+        }
+        Ok(SqliteStore {
+            conn,
+            columns: "location, uuid, tag, exe".to_string(),
+            daily: true,
+        })
+    }
+
+    pub fn open_read_only(path: &Path) -> Result<SqliteStore> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.execute_batch("BEGIN")?;
+        let mut columns = Vec::new();
+        for column in ["location", "uuid", "tag", "exe"] {
+            columns.push(if has_column(&conn, "sessions", column)? {
+                column
+            } else {
+                "NULL"
+            });
+        }
+        let daily = has_table(&conn, "daily")?;
+        Ok(SqliteStore {
+            conn,
+            columns: columns.join(", "),
+            daily,
+        })
+    }
+
+    pub fn snapshot(path: &Path, to: &Path) -> Result<()> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.execute("VACUUM INTO ?1", params![to.to_string_lossy()])?;
+        Ok(())
+    }
+
+    pub fn vacuum_file(path: &Path) -> Result<()> {
+        let conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")?;
+        Ok(())
+    }
+
+    pub fn check_integrity(path: &Path) -> Result<()> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+        if result != "ok" {
+            return Err(format!("SQLite 무결성 검사 실패: {}", result).into());
+        }
+        Ok(())
+    }
+
+    pub fn select(&self, sql: &str) -> Result<(Vec<String>, Vec<Vec<String>>)> {
+        self.conn.pragma_update(None, "query_only", true)?;
+        let mut stmt = self.conn.prepare(sql)?;
+        if !stmt.readonly() {
+            return Err("읽기 전용 SQL(SELECT, WITH, EXPLAIN)만 실행할 수 있습니다".into());
+        }
+        let names: Vec<String> = stmt.column_names().into_iter().map(String::from).collect();
+        let count = names.len();
+        let rows = stmt.query_map([], |row| {
+            (0..count)
+                .map(|i| {
+                    Ok(match row.get_ref(i)? {
+                        ValueRef::Null => String::new(),
+                        ValueRef::Integer(value) => value.to_string(),
+                        ValueRef::Real(value) => value.to_string(),
+                        ValueRef::Text(text) => String::from_utf8_lossy(text).to_string(),
+                        ValueRef::Blob(blob) => format!("<{} bytes>", blob.len()),
+                    })
+                })
+                .collect()
+        })?;
+        Ok((names, rows.collect::<rusqlite::Result<_>>()?))
+    }
+}
+
+impl Store for SqliteStore {
+    fn append_interval(&mut self, interval: &Interval) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        insert_interval(&tx, interval)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn query(&self, query: &Query) -> Result<Vec<Interval>> {
+        let (from, to) = range_params(query);
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT title, start, end, {} FROM sessions
+             WHERE end > ?1 AND start < ?2 ORDER BY start",
+            self.columns
+        ))?;
+        let rows = stmt.query_map(params![from, to], |row| {
+            let title: String = row.get(0)?;
+            let start = from_sql_time(&row.get::<_, String>(1)?)?;
+            let end = from_sql_time(&row.get::<_, String>(2)?)?;
+            Ok(Interval {
+                id: ids::parse_or_legacy(row.get(4)?, start, end, &title),
+                start,
+                end,
+                title,
+                location: row.get(3)?,
+                tag: row.get(5)?,
+                exe: row.get(6)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn summarize(&self, query: &Query) -> Result<Vec<(String, Duration)>> {
+        let (from, to) = range_params(query);
+        let mut stmt = self.conn.prepare(
+            "SELECT title, SUM(duration) FROM sessions
+             WHERE end > ?1 AND start < ?2 GROUP BY title ORDER BY 2 DESC",
+        )?;
+        let rows = stmt.query_map(params![from, to], |row| {
+            Ok((row.get(0)?, Duration::from_secs_f64(row.get(1)?)))
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn daily_totals(&self, query: &Query) -> Result<Vec<DailyTotal>> {
+        if !self.daily {
+            return Ok(super::daily_totals(&self.query(query)?, query));
+        }
+        let from = query
+            .from
+            .map(|from| from.date_naive().to_string())
+            .unwrap_or_default();
+        let to = query
+            .to
+            .map(|to| to.date_naive().to_string())
+            .unwrap_or_else(|| "9999".to_string());
+        let mut stmt = self.conn.prepare(
+            "SELECT day, title, exe, duration FROM daily
+             WHERE day >= ?1 AND day <= ?2 ORDER BY day, title",
+        )?;
+        let rows = stmt.query_map(params![from, to], |row| {
+            let exe: String = row.get(2)?;
+            Ok(DailyTotal {
+                date: row.get::<_, String>(0)?.parse().map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        rusqlite::types::Type::Text,
+                        Box::new(e),
+                    )
+                })?,
+                title: row.get(1)?,
+                exe: (!exe.is_empty()).then_some(exe),
+                duration: Duration::from_secs_f64(row.get(3)?),
+            })
+        })?;
+        let totals: Vec<DailyTotal> = rows.collect::<rusqlite::Result<_>>()?;
+        Ok(totals
+            .into_iter()
+            .filter(|total| query.contains_day(total.date))
+            .collect())
+    }
+
+    fn replace_intervals(&mut self, query: &Query, intervals: &[Interval]) -> Result<()> {
+        let (from, to) = range_params(query);
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM sessions WHERE end > ?1 AND start < ?2",
+            params![from, to],
+        )?;
+        for interval in intervals {
+            insert_interval(&tx, interval)?;
+        }
+        rebuild_daily(&tx, query)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn append_annotation(&mut self, annotation: &Annotation) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO annotations (time, kind, text) VALUES (?1, ?2, ?3)",
+            params![
+                to_sql_time(&annotation.time),
+                annotation.kind,
+                annotation.text
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn annotations(&self, query: &Query) -> Result<Vec<Annotation>> {
+        let (from, to) = range_params(query);
+        let mut stmt = self.conn.prepare(
+            "SELECT time, kind, text FROM annotations
+             WHERE time >= ?1 AND time < ?2 ORDER BY time",
+        )?;
+        let rows = stmt.query_map(params![from, to], |row| {
+            Ok(Annotation {
+                time: from_sql_time(&row.get::<_, String>(0)?)?,
+                kind: row.get(1)?,
+                text: row.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+}
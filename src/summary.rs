@@ -0,0 +1,89 @@
+use std::io::{IsTerminal, stdin};
+use std::path::Path;
+
+use crate::config::{Goal, GroupBy, Language, SummaryFormat};
+use crate::forecast;
+use crate::format::is_a11y;
+use crate::report::{self, SortBy, View};
+use crate::store::{MemoryStore, Query, Result, Store};
+
+fn print_view(store: &dyn Store, query: &Query, goals: &[Goal], view: &View) -> Result<()> {
+    println!();
+    println!(
+        "정렬: {} | 묶음: {} | 필터: {}",
+        match view.sort {
+            SortBy::Time => "시간순",
+            SortBy::Name => "이름순",
+        },
+        view.group.label(Language::Ko),
+        view.filter.as_deref().unwrap_or("-")
+    );
+    let rule = if is_a11y() {
+        ""
+    } else {
+        "-----------------------------------------------------"
+    };
+    println!("{}", rule);
+    report::print_entries(&view.summarize(store, query, goals)?);
+    println!("{}", rule);
+    for line in forecast::lines(goals, store)? {
+        println!("{}", line);
+    }
+    println!(
+        "t: 시간순  n: 이름순  g: 묶음 전환  f <텍스트>: 필터 (f만 입력하면 해제)  Enter: 저장"
+    );
+    Ok(())
+}
+
+pub fn snapshot(store: &dyn Store, query: &Query) -> Result<MemoryStore> {
+    let history = Query::last_days(report::HISTORY_DAYS);
+    MemoryStore::snapshot(
+        store,
+        &Query {
+            from: query
+                .from
+                .zip(history.from)
+                .map(|(from, since)| from.min(since)),
+            to: None,
+        },
+    )
+}
+
+pub fn interactive(
+    store: &dyn Store,
+    query: &Query,
+    goals: &[Goal],
+    language: Language,
+    output_dir: &Path,
+    format: SummaryFormat,
+    group: GroupBy,
+) -> Result<String> {
+    let mut view = View::grouped(group);
+    if !stdin().is_terminal() {
+        report::print_entries(&view.summarize(store, query, goals)?);
+        return report::write_summary(store, query, goals, &view, language, output_dir, format);
+    }
+
+    loop {
+        print_view(store, query, goals, &view)?;
+        let mut input = String::new();
+        if stdin().read_line(&mut input)? == 0 {
+            break;
+        }
+        let input = input.trim();
+        match input {
+            "" | "s" => break,
+            "t" => view.sort = SortBy::Time,
+            "n" => view.sort = SortBy::Name,
+            "g" => view.group = view.group.next(),
+            "f" => view.filter = None,
+            _ => match input.strip_prefix("f ") {
+                Some(text) if !text.trim().is_empty() => {
+                    view.filter = Some(text.trim().to_string())
+                }
+                _ => println!("알 수 없는 명령: {}", input),
+            },
+        }
+    }
+    report::write_summary(store, query, goals, &view, language, output_dir, format)
+}
@@ -0,0 +1,56 @@
+use std::fs;
+
+use crate::store::Result;
+
+pub const TASK_STACK_FILE: &str = "task_stack.json";
+
+pub fn load() -> Result<Vec<String>> {
+    match fs::read_to_string(TASK_STACK_FILE) {
+        Ok(text) => Ok(serde_json::from_str(&text)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn save(stack: &[String]) -> Result<()> {
+    fs::write(TASK_STACK_FILE, serde_json::to_string_pretty(stack)?)?;
+    Ok(())
+}
+
+pub fn top() -> Option<String> {
+    load().ok().and_then(|mut stack| stack.pop())
+}
+
+fn print_stack(stack: &[String]) {
+    if stack.is_empty() {
+        println!("작업 스택이 비어 있습니다");
+    }
+    for (depth, task) in stack.iter().rev().enumerate() {
+        let marker = if depth == 0 { "*" } else { " " };
+        println!("{} {}", marker, task);
+    }
+}
+
+pub fn push(task: &str) -> Result<()> {
+    let mut stack = load()?;
+    stack.push(task.to_string());
+    save(&stack)?;
+    print_stack(&stack);
+    Ok(())
+}
+
+pub fn pop() -> Result<()> {
+    let mut stack = load()?;
+    let Some(task) = stack.pop() else {
+        return Err("작업 스택이 비어 있습니다".into());
+    };
+    save(&stack)?;
+    println!("끝낸 작업: {}", task);
+    print_stack(&stack);
+    Ok(())
+}
+
+pub fn show() -> Result<()> {
+    print_stack(&load()?);
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::fs::File;
+use std::path::Path;
+
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use gif::{Encoder, Frame, Repeat};
+
+use crate::config::Goal;
+use crate::report::app_name;
+use crate::screenshot::{self, SCREENSHOT_KIND};
+use crate::store::{Interval, Query, Result, Store};
+
+fn label(time: DateTime<Local>, intervals: &[Interval], goals: &[Goal]) -> String {
+    let title = intervals
+        .iter()
+        .find(|interval| interval.start <= time && time < interval.end)
+        .map(|interval| interval.title.as_str());
+    let name = match title {
+        Some(title) => goals
+            .iter()
+            .find(|goal| goal.matches(title))
+            .map(|goal| goal.name.clone())
+            .unwrap_or_else(|| app_name(title)),
+        None => "-".to_string(),
+    };
+    format!("{} {}", time.format("%H:%M"), name)
+}
+
+pub fn write_timelapse(
+    store: &dyn Store,
+    goals: &[Goal],
+    date: NaiveDate,
+    delay_ms: u64,
+) -> Result<Option<String>> {
+    let start = |date: NaiveDate| {
+        Local
+            .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+            .earliest()
+    };
+    let query = Query {
+        from: start(date),
+        to: start(date.succ_opt().ok_or("잘못된 날짜입니다")?),
+    };
+    let intervals = store.query(&query)?;
+    let mut shots: Vec<_> = store
+        .annotations(&query)?
+        .into_iter()
+        .filter(|annotation| annotation.kind == SCREENSHOT_KIND)
+        .collect();
+    shots.sort_by_key(|shot| shot.time);
+
+    let filename = format!("timelapse_{}.gif", date.format("%Y%m%d"));
+    let mut encoder: Option<Encoder<File>> = None;
+    let mut size = (0, 0);
+    let mut frames = 0;
+    for shot in &shots {
+        let mut thumbnail = match screenshot::read_bmp(Path::new(&shot.text)) {
+            Ok(thumbnail) => thumbnail,
+            Err(e) => {
+                eprintln!("스크린샷을 건너뜁니다 ({}): {}", shot.text, e);
+                continue;
+            }
+        };
+        let frame_size = (thumbnail.width as u16, thumbnail.height as u16);
+        if encoder.is_none() {
+            size = frame_size;
+            let mut created = Encoder::new(File::create(&filename)?, size.0, size.1, &[])?;
+            created.set_repeat(Repeat::Infinite)?;
+            encoder = Some(created);
+        } else if frame_size != size {
+            eprintln!("크기가 다른 스크린샷을 건너뜁니다: {}", shot.text);
+            continue;
+        }
+
+        screenshot::draw_label(&mut thumbnail, &label(shot.time, &intervals, goals));
+        let mut rgba = thumbnail.pixels;
+        for pixel in rgba.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+            pixel[3] = 255;
+        }
+        let mut frame = Frame::from_rgba_speed(size.0, size.1, &mut rgba, 10);
+        frame.delay = (delay_ms / 10).min(u16::MAX as u64) as u16;
+        if let Some(encoder) = &mut encoder {
+            encoder.write_frame(&frame)?;
+        }
+        frames += 1;
+    }
+    Ok((frames > 0).then_some(filename))
+}
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+
+use crate::categories;
+use crate::config::Goal;
+use crate::format::format_duration;
+use crate::report::category;
+use crate::store::{Interval, Query, Result, Store};
+
+pub const TIMER_KIND: &str = "timer";
+
+pub struct Timer {
+    pub label: String,
+    category: String,
+    goals: Vec<Goal>,
+    duration: Duration,
+    started: DateTime<Local>,
+}
+
+impl Timer {
+    pub fn new(
+        label: String,
+        category: Option<String>,
+        duration: Duration,
+        goals: Vec<Goal>,
+    ) -> Timer {
+        Timer {
+            category: category.unwrap_or_else(|| label.clone()),
+            label,
+            goals,
+            duration,
+            started: Local::now(),
+        }
+    }
+
+    pub fn ends_at(&self) -> DateTime<Local> {
+        self.started + chrono::Duration::from_std(self.duration).unwrap_or_default()
+    }
+
+    pub fn start_text(&self) -> String {
+        format!(
+            "{} 타이머 시작: {} 동안, {}에 집중 ({} 종료)",
+            self.label,
+            format_duration(&self.duration),
+            self.category,
+            self.ends_at().format("%H:%M:%S")
+        )
+    }
+
+    fn is_on_target(&self, interval: &Interval) -> bool {
+        let known = self
+            .goals
+            .iter()
+            .map(|goal| goal.name.as_str())
+            .chain(categories::names())
+            .any(|name| name == self.category);
+        if known {
+            category(&interval.title, interval.exe.as_deref(), &self.goals) == self.category
+        } else {
+            interval
+                .title
+                .to_lowercase()
+                .contains(&self.category.to_lowercase())
+        }
+    }
+
+    pub fn summary(&self, store: &dyn Store) -> Result<String> {
+        let query = Query {
+            from: Some(self.started),
+            to: Some(self.ends_at()),
+        };
+        let focused: Duration = store
+            .query(&query)?
+            .iter()
+            .filter(|interval| interval.tag.as_deref() == Some(self.label.as_str()))
+            .filter(|interval| self.is_on_target(interval))
+            .map(Interval::duration)
+            .sum();
+        let percent = focused.as_secs_f64() / self.duration.as_secs_f64().max(1.0) * 100.0;
+        Ok(format!(
+            "{} 타이머 종료: {}에 {} / {} 집중 ({:.0}%)",
+            self.label,
+            self.category,
+            format_duration(&focused),
+            format_duration(&self.duration),
+            percent
+        ))
+    }
+}
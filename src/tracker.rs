@@ -0,0 +1,893 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use regex::Regex;
+
+use crate::binding;
+use crate::config::{Config, LocationProfile, OffHours};
+use crate::control::{Control, Event, EventKind};
+use crate::debug;
+use crate::format::{Icon, format_duration, icon, title_width, truncate_or_pad};
+use crate::gaps::{
+    BOOT_KIND, HEARTBEAT_INTERVAL, HEARTBEAT_KIND, LOGON_KIND, UNLOCK_KIND, WAKE_KIND,
+};
+use crate::hotkey::MARKER_KIND;
+use crate::idle::{
+    DISPLAY_REQUIRED_LABEL, IDLE_LABEL, LOCKED_LABEL, SCREENSAVER_LABEL, SLEEP_LABEL,
+    idle_threshold,
+};
+use crate::location::LocationInfo;
+use crate::network::{self, NetworkState};
+use crate::normalize::{self, TitleRule};
+use crate::notify;
+use crate::platform::{self, ActiveWindowProvider};
+use crate::remote::{self, REMOTE_KIND, REMOTE_LABEL};
+use crate::rollover::Rollover;
+use crate::screenshot::{SCREENSHOT_KIND, Screenshots};
+use crate::store::{Annotation, Interval, Result, Store};
+use crate::tasks;
+use crate::timer::{TIMER_KIND, Timer};
+use crate::uptime;
+use crate::window::{Capture, DESKTOP_LABEL, Watcher};
+
+pub const OFF_HOURS_LABEL: &str = "[업무 외 시간]";
+pub const OTHER_LABEL: &str = "[기타]";
+pub const PSEUDO_LABELS: &[&str] = &[
+    IDLE_LABEL,
+    SCREENSAVER_LABEL,
+    DISPLAY_REQUIRED_LABEL,
+    LOCKED_LABEL,
+    SLEEP_LABEL,
+    OFF_HOURS_LABEL,
+    REMOTE_LABEL,
+    OTHER_LABEL,
+    DESKTOP_LABEL,
+];
+const HOUSEKEEPING_INTERVAL: Duration = Duration::from_secs(5);
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const ENVIRONMENT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const SLEEP_GAP: Duration = Duration::from_secs(2 * 60);
+const LOCK_SCREEN_TITLE: &str = "Windows Default Lock Screen";
+const LOCK_SCREEN_EXES: &[&str] = &["lockapp.exe", "logonui.exe"];
+
+struct Segment {
+    title: String,
+    exe: Option<String>,
+    start: DateTime<Local>,
+}
+
+pub struct Tracker {
+    provider: Box<dyn ActiveWindowProvider>,
+    store: Arc<Mutex<Box<dyn Store>>>,
+    control: Arc<Control>,
+    off_hours: Option<OffHours>,
+    debounce: Duration,
+    poll_interval: Duration,
+    window: String,
+    window_exe: Option<String>,
+    segment: Option<Segment>,
+    idle_label: Option<&'static str>,
+    is_off_hours: bool,
+    is_paused: bool,
+    pending_switch: Option<(String, DateTime<Local>)>,
+    network: Option<NetworkState>,
+    last_environment_check: Option<Instant>,
+    last_heartbeat: Option<Instant>,
+    last_tick: Option<DateTime<Local>>,
+    slept_until: Option<DateTime<Local>>,
+    rollover: Option<Rollover>,
+    is_locked: bool,
+    unlock_grace: Duration,
+    unlocked_at: Option<DateTime<Local>>,
+    locations: Vec<LocationProfile>,
+    location: Option<String>,
+    only: Option<Regex>,
+    ignore: Vec<String>,
+    title_rules: Vec<TitleRule>,
+    remote_tools: Vec<String>,
+    remote: Option<String>,
+    screenshots: Option<Screenshots>,
+    timer: Option<Timer>,
+    tag: Option<String>,
+    task: Option<String>,
+    bindings: BTreeMap<String, String>,
+    suggested: HashMap<String, String>,
+    hinted: HashSet<String>,
+    quiet: bool,
+}
+
+fn print_event(event: &Event) {
+    let time = event.time.format("%H:%M:%S");
+    let stayed = event
+        .duration
+        .map(|duration| format!(" (머문 시간: {})", format_duration(&duration)))
+        .unwrap_or_default();
+    match event.kind {
+        EventKind::Switch => {
+            println!(
+                "[{}] -> {}",
+                time,
+                truncate_or_pad(&event.title, title_width())
+            )
+        }
+        EventKind::IdleStart => println!(
+            "[{}] {}",
+            time,
+            truncate_or_pad(
+                &format!("{}비활성 상태 진입", icon(Icon::Warning)),
+                title_width()
+            )
+        ),
+        EventKind::IdleChange => {
+            println!(
+                "[{}] {}",
+                time,
+                truncate_or_pad(&event.title, title_width())
+            )
+        }
+        EventKind::Active => println!(
+            "[{}] {}{}",
+            time,
+            truncate_or_pad(
+                &format!("{}다시 활성화됨", icon(Icon::Active)),
+                title_width()
+            ),
+            stayed
+        ),
+        EventKind::OffHoursStart => println!(
+            "[{}] {}",
+            time,
+            truncate_or_pad(
+                &format!("{}업무 외 시간 시작", icon(Icon::OffHoursStart)),
+                title_width()
+            )
+        ),
+        EventKind::OffHoursEnd => println!(
+            "[{}] {}{}",
+            time,
+            truncate_or_pad(
+                &format!("{}업무 외 시간 종료", icon(Icon::OffHoursEnd)),
+                title_width()
+            ),
+            stayed
+        ),
+        EventKind::Paused => println!(
+            "[{}] {}",
+            time,
+            truncate_or_pad(
+                &format!("{}추적 일시 중지", icon(Icon::Paused)),
+                title_width()
+            )
+        ),
+        EventKind::Resumed => println!(
+            "[{}] {}",
+            time,
+            truncate_or_pad(&format!("{}추적 재개", icon(Icon::Resumed)), title_width())
+        ),
+        EventKind::Annotation => {
+            println!(
+                "[{}] {}",
+                time,
+                truncate_or_pad(&event.title, title_width())
+            )
+        }
+    }
+}
+
+impl Tracker {
+    pub fn new(
+        config: &Config,
+        store: Arc<Mutex<Box<dyn Store>>>,
+        control: Arc<Control>,
+    ) -> Result<Tracker> {
+        let provider = platform::detect()?;
+        let suggested = binding::suggestions(store.lock().unwrap().as_ref())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|suggestion| (suggestion.key, suggestion.tag))
+            .collect();
+        Ok(Tracker {
+            provider,
+            store,
+            control,
+            off_hours: config.off_hours,
+            debounce: Duration::from_millis(config.switch_debounce_ms),
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            window: String::new(),
+            window_exe: None,
+            segment: None,
+            idle_label: None,
+            is_off_hours: false,
+            is_paused: false,
+            pending_switch: None,
+            network: None,
+            last_environment_check: None,
+            last_heartbeat: None,
+            last_tick: None,
+            slept_until: None,
+            rollover: Rollover::new(config),
+            is_locked: false,
+            unlock_grace: Duration::from_secs(config.unlock_grace_secs),
+            unlocked_at: None,
+            locations: config.locations.clone(),
+            location: None,
+            only: None,
+            ignore: config.ignore.clone(),
+            title_rules: normalize::parse_all(&config.title_rules),
+            remote_tools: config.remote_tools.clone(),
+            remote: None,
+            screenshots: Screenshots::new(&config.screenshots),
+            timer: None,
+            tag: None,
+            task: None,
+            bindings: binding::load().unwrap_or_default(),
+            suggested,
+            hinted: HashSet::new(),
+            quiet: false,
+        })
+    }
+
+    pub fn with_only(mut self, only: Option<Regex>) -> Tracker {
+        self.only = only;
+        self
+    }
+
+    pub fn with_tag(mut self, tag: Option<String>) -> Tracker {
+        self.tag = tag;
+        self
+    }
+
+    pub fn with_provider(mut self, provider: Box<dyn ActiveWindowProvider>) -> Tracker {
+        self.provider = provider;
+        self
+    }
+
+    pub fn with_quiet(mut self, quiet: bool) -> Tracker {
+        self.quiet = quiet;
+        self
+    }
+
+    pub fn subscribe(&self) -> Receiver<Event> {
+        self.control.subscribe()
+    }
+
+    pub fn subscribe_intervals(&self) -> Receiver<Interval> {
+        self.control.subscribe_intervals()
+    }
+
+    pub fn with_timer(mut self, timer: Option<Timer>) -> Tracker {
+        if let Some(timer) = timer.as_ref().filter(|_| !self.quiet) {
+            println!("{}{}", icon(Icon::Timer), timer.start_text());
+        }
+        self.timer = timer;
+        self
+    }
+
+    pub fn run(&mut self) -> ! {
+        let watcher = self
+            .provider
+            .notifies_switches()
+            .then(Watcher::install)
+            .flatten();
+        let Some(mut watcher) = watcher else {
+            if self.provider.notifies_switches() {
+                eprintln!(
+                    "창 전환 이벤트를 받을 수 없어 {}ms마다 확인합니다",
+                    self.poll_interval.as_millis()
+                );
+            }
+            loop {
+                self.tick();
+                thread::sleep(self.poll_interval);
+            }
+        };
+        loop {
+            self.tick();
+            watcher.wait(self.next_wake(Local::now()));
+        }
+    }
+
+    fn next_wake(&self, now: DateTime<Local>) -> Duration {
+        let mut wake = HOUSEKEEPING_INTERVAL;
+        if self.is_paused || self.is_off_hours || self.remote.is_some() {
+            return wake;
+        }
+        if let Some((_, since)) = &self.pending_switch {
+            let waited = (now - *since).to_std().unwrap_or_default();
+            wake = wake.min(self.debounce.saturating_sub(waited));
+        }
+        if let Some(timer) = &self.timer {
+            wake = wake.min((timer.ends_at() - now).to_std().unwrap_or_default());
+        }
+        match self.idle_label {
+            Some(_) => wake.min(IDLE_POLL_INTERVAL),
+            None => wake.min(idle_threshold().saturating_sub(self.provider.idle_duration())),
+        }
+    }
+
+    fn emit(&self, kind: EventKind, title: &str, duration: Option<Duration>) {
+        let event = Event {
+            time: Local::now(),
+            kind,
+            title: title.to_string(),
+            exe: self
+                .segment
+                .as_ref()
+                .and_then(|segment| segment.exe.clone()),
+            duration,
+        };
+        if !self.quiet {
+            print_event(&event);
+        }
+        self.control.publish(&event);
+    }
+
+    fn record(&self, kind: &str, text: &str) {
+        let annotation = Annotation {
+            time: Local::now(),
+            kind: kind.to_string(),
+            text: text.to_string(),
+        };
+        if let Err(e) = self.store.lock().unwrap().append_annotation(&annotation) {
+            eprintln!("사용 기록 저장 실패: {}", e);
+        }
+    }
+
+    pub fn annotate(&self, kind: &str, text: &str) {
+        self.record(kind, text);
+        self.emit(EventKind::Annotation, text, None);
+    }
+
+    fn place_markers(&self) {
+        for name in self.control.take_markers() {
+            self.record(MARKER_KIND, &name);
+            self.emit(
+                EventKind::Annotation,
+                &format!("{}{}", icon(Icon::Marker), name),
+                None,
+            );
+        }
+    }
+
+    fn check_timer(&mut self, now: DateTime<Local>) {
+        let Some(ends_at) = self.timer.as_ref().map(Timer::ends_at) else {
+            return;
+        };
+        if now < ends_at {
+            return;
+        }
+        let title = self.segment.as_ref().map(|segment| segment.title.clone());
+        self.switch_to(title, ends_at);
+        let Some(timer) = self.timer.take() else {
+            return;
+        };
+        let summary = timer.summary(self.store.lock().unwrap().as_ref());
+        match summary {
+            Ok(text) => {
+                self.record(TIMER_KIND, &text);
+                notify::send(&format!("{}타이머 종료", icon(Icon::Timer)), &text);
+            }
+            Err(e) => eprintln!("타이머 결과 계산 실패: {}", e),
+        }
+    }
+
+    fn heartbeat(&mut self) {
+        match self.last_heartbeat {
+            None => {
+                if let Some(boot) = uptime::boot_time() {
+                    self.record(BOOT_KIND, &boot.to_rfc3339());
+                }
+                if let Some(logon) = uptime::logon_time() {
+                    self.record(LOGON_KIND, &logon.to_rfc3339());
+                }
+            }
+            Some(beat) if beat.elapsed() >= HEARTBEAT_INTERVAL => self.record(HEARTBEAT_KIND, ""),
+            Some(_) => return,
+        }
+        self.last_heartbeat = Some(Instant::now());
+    }
+
+    fn check_sleep(&mut self, now: DateTime<Local>) {
+        let mut sleeps = uptime::take_sleeps();
+        let gap = SLEEP_GAP.max(self.poll_interval * 3);
+        match self.last_tick.replace(now) {
+            Some(last_tick)
+                if sleeps.is_empty() && (now - last_tick).to_std().unwrap_or_default() >= gap =>
+            {
+                sleeps.push((last_tick, now));
+            }
+            _ => {}
+        }
+        for (start, end) in sleeps {
+            if self.slept_until.is_some_and(|until| end <= until) {
+                continue;
+            }
+            self.slept_until = Some(end);
+            if let Some(title) = self.segment.as_ref().map(|segment| segment.title.clone()) {
+                self.pending_switch = None;
+                self.switch_to(Some(SLEEP_LABEL.to_string()), start);
+                self.switch_to(Some(title), end);
+            }
+            self.record(WAKE_KIND, &end.to_rfc3339());
+            let slept = (end - start).to_std().unwrap_or_default();
+            self.emit(
+                EventKind::Annotation,
+                &format!(
+                    "{}절전에서 복귀 ({})",
+                    icon(Icon::Sleep),
+                    format_duration(&slept)
+                ),
+                None,
+            );
+        }
+    }
+
+    fn check_rollover(&mut self, now: DateTime<Local>) {
+        let Some((day, boundary)) = self
+            .rollover
+            .as_mut()
+            .and_then(|rollover| rollover.due(now))
+        else {
+            return;
+        };
+        let title = self.segment.as_ref().map(|segment| segment.title.clone());
+        self.switch_to(title, boundary);
+        let from = self.control.session_start();
+        self.control.start_session(boundary);
+        let Some(rollover) = &self.rollover else {
+            return;
+        };
+        let written = rollover.write(self.store.lock().unwrap().as_ref(), from, boundary, day);
+        match written {
+            Ok(filename) => self.emit(
+                EventKind::Annotation,
+                &format!("하루 마감, 요약 저장됨: {}", filename),
+                None,
+            ),
+            Err(e) => eprintln!("하루 요약 저장 실패: {}", e),
+        }
+    }
+
+    fn check_task(&mut self, now: DateTime<Local>) {
+        let task = tasks::top();
+        if task == self.task {
+            return;
+        }
+        let title = self.segment.as_ref().map(|segment| segment.title.clone());
+        self.switch_to(title, now);
+        let text = match &task {
+            Some(task) => format!("작업: {}", task),
+            None => "작업 없음".to_string(),
+        };
+        self.task = task;
+        self.emit(EventKind::Annotation, &text, None);
+    }
+
+    fn hint_binding(&mut self) {
+        if self.timer.is_some() || self.task.is_some() || self.tag.is_some() {
+            return;
+        }
+        let key = binding::key(&self.window, self.window_exe.as_deref());
+        let Some(tag) = self.suggested.get(&key).cloned() else {
+            return;
+        };
+        if self.bindings.contains_key(&key) || !self.hinted.insert(key) {
+            return;
+        }
+        self.emit(
+            EventKind::Annotation,
+            &format!(
+                "이 창은 보통 '{}' 태그와 함께 쓰였습니다 (bindings suggest로 자동 태그 설정)",
+                tag
+            ),
+            None,
+        );
+    }
+
+    fn check_lock(&mut self, now: DateTime<Local>) {
+        let locked = uptime::is_locked();
+        if self.is_locked && !locked {
+            self.record(UNLOCK_KIND, &now.to_rfc3339());
+            self.unlocked_at = (!self.unlock_grace.is_zero()).then_some(now);
+        }
+        self.is_locked = locked;
+    }
+
+    fn take_screenshot(&mut self, now: DateTime<Local>) {
+        let Some(screenshots) = &mut self.screenshots else {
+            return;
+        };
+        match screenshots.capture(now) {
+            Ok(Some(path)) => self.record(SCREENSHOT_KIND, &path.display().to_string()),
+            Ok(None) => {}
+            Err(e) => eprintln!("스크린샷 저장 실패: {}", e),
+        }
+    }
+
+    fn check_network(&mut self) {
+        let Some(state) = network::current_state() else {
+            return;
+        };
+        let Some(previous) = self.network.replace(state.clone()) else {
+            return;
+        };
+        if previous.online != state.online {
+            let text = if state.online {
+                "네트워크 연결됨"
+            } else {
+                "네트워크 끊김"
+            };
+            self.annotate("network", &format!("{}{}", icon(Icon::Network), text));
+        }
+        if previous.vpn != state.vpn {
+            match &state.vpn {
+                Some(name) => self.annotate(
+                    "vpn",
+                    &format!("{}VPN 연결됨 ({})", icon(Icon::VpnOn), name),
+                ),
+                None => self.annotate("vpn", &format!("{}VPN 연결 해제", icon(Icon::VpnOff))),
+            }
+        }
+    }
+
+    fn check_location(&mut self, now: DateTime<Local>) {
+        if self.locations.is_empty() {
+            return;
+        }
+        let location = LocationInfo::detect()
+            .profile(&self.locations)
+            .map(|profile| profile.name.clone());
+        if location == self.location {
+            return;
+        }
+        let title = self.segment.as_ref().map(|segment| segment.title.clone());
+        self.switch_to(title, now);
+        self.location = location;
+        let text = match &self.location {
+            Some(name) => format!("{}위치: {}", icon(Icon::Location), name),
+            None => format!("{}위치: 알 수 없음", icon(Icon::Location)),
+        };
+        self.annotate("location", &text);
+    }
+
+    fn in_unlock_grace(&mut self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let unlocked_at = self.unlocked_at?;
+        if (now - unlocked_at).to_std().unwrap_or_default() < self.unlock_grace {
+            return Some(unlocked_at);
+        }
+        self.unlocked_at = None;
+        None
+    }
+
+    fn is_lock_screen(title: &str, exe: Option<&str>) -> bool {
+        title == LOCK_SCREEN_TITLE
+            || exe.is_some_and(|exe| LOCK_SCREEN_EXES.contains(&exe.to_lowercase().as_str()))
+    }
+
+    fn check_remote(&mut self, now: DateTime<Local>) {
+        let remote = remote::controlling_tool(&self.remote_tools);
+        let changed = remote != self.remote;
+        match &remote {
+            Some(tool) => {
+                if self
+                    .segment
+                    .as_ref()
+                    .is_none_or(|segment| segment.title != REMOTE_LABEL)
+                {
+                    self.idle_label = None;
+                    self.pending_switch = None;
+                    self.switch_to(Some(REMOTE_LABEL.to_string()), now);
+                }
+                if changed {
+                    self.annotate(
+                        REMOTE_KIND,
+                        &format!("{}원격 제어 시작 ({})", icon(Icon::Remote), tool),
+                    );
+                }
+            }
+            None if changed => {
+                let remote_time = self.switch_to(self.current_window(), now);
+                let stayed = remote_time
+                    .map(|duration| format!(" ({})", format_duration(&duration)))
+                    .unwrap_or_default();
+                self.annotate(
+                    REMOTE_KIND,
+                    &format!("{}원격 제어 종료{}", icon(Icon::Remote), stayed),
+                );
+            }
+            None => {}
+        }
+        self.remote = remote;
+    }
+
+    fn is_ignored(&self, title: &str, exe: Option<&str>) -> bool {
+        let title = title.to_lowercase();
+        let exe = exe.map(str::to_lowercase);
+        self.ignore
+            .iter()
+            .map(|pattern| pattern.to_lowercase())
+            .any(|pattern| title.contains(&pattern) || exe.as_deref() == Some(pattern.as_str()))
+    }
+
+    fn filter_title(&self, title: String) -> String {
+        match &self.only {
+            Some(only) if !only.is_match(&title) => OTHER_LABEL.to_string(),
+            _ => normalize::normalize(&self.title_rules, title),
+        }
+    }
+
+    fn current_window(&self) -> Option<String> {
+        (!self.window.is_empty()).then(|| self.window.clone())
+    }
+
+    fn segment_interval(&self, segment: &Segment, end: DateTime<Local>) -> Interval {
+        let mut interval = Interval::new(
+            segment.start,
+            end,
+            segment.title.clone(),
+            self.location.clone(),
+        );
+        interval.tag = self
+            .timer
+            .as_ref()
+            .map(|timer| timer.label.clone())
+            .or_else(|| self.task.clone())
+            .or_else(|| self.tag.clone())
+            .or_else(|| {
+                self.bindings
+                    .get(&binding::key(&segment.title, segment.exe.as_deref()))
+                    .cloned()
+            });
+        interval.exe = segment.exe.clone();
+        interval
+    }
+
+    fn switch_to(&mut self, title: Option<String>, at: DateTime<Local>) -> Option<Duration> {
+        let store = self.store.clone();
+        let mut store = store.lock().unwrap();
+        if self.control.is_stopped() {
+            self.segment = None;
+            return None;
+        }
+        let at = self
+            .segment
+            .as_ref()
+            .map_or(at, |segment| at.max(segment.start));
+        let closed = self.segment.take().map(|segment| {
+            let interval = self.segment_interval(&segment, at);
+            if let Err(e) = store.append_interval(&interval) {
+                eprintln!("사용 기록 저장 실패: {}", e);
+            }
+            self.control.publish_interval(&interval);
+            interval.duration()
+        });
+        self.control
+            .set_status(title.clone(), title.as_ref().map(|_| at));
+        self.segment = title.map(|title| Segment {
+            exe: (title == self.window && title != OTHER_LABEL)
+                .then(|| self.window_exe.clone())
+                .flatten(),
+            title,
+            start: at,
+        });
+        self.control.set_open(
+            self.segment
+                .as_ref()
+                .map(|segment| self.segment_interval(segment, at)),
+        );
+        closed
+    }
+
+    fn follow(&mut self, capture: Option<&Capture>, now: DateTime<Local>) -> String {
+        let current_title = match capture.map(|capture| capture.title.clone()) {
+            None => return "무시: 전경 창 없음".to_string(),
+            Some(Err(reason)) => return format!("무시: {}", reason),
+            Some(Ok(title))
+                if self.is_ignored(&title, capture.and_then(|capture| capture.exe.as_deref())) =>
+            {
+                return "무시: 설정의 ignore 목록".to_string();
+            }
+            Some(Ok(title))
+                if self.in_unlock_grace(now).is_some()
+                    && Self::is_lock_screen(
+                        &title,
+                        capture.and_then(|capture| capture.exe.as_deref()),
+                    ) =>
+            {
+                return "무시: 잠금 해제 직후 잠금 화면".to_string();
+            }
+            Some(Ok(title)) => self.filter_title(title),
+        };
+        let seen = capture.map_or(now, |capture| capture.seen);
+        let exe = capture.and_then(|capture| capture.exe.clone());
+        let unlocked_at = match self.in_unlock_grace(now) {
+            Some(unlocked_at) if self.idle_label.is_none() && self.window != current_title => {
+                self.unlocked_at = None;
+                Some(unlocked_at)
+            }
+            _ => None,
+        };
+        let filtered = current_title == OTHER_LABEL;
+        let action = if self.window == current_title {
+            self.pending_switch = None;
+            "유지"
+        } else if self.idle_label.is_some() || self.window.is_empty() {
+            self.pending_switch = None;
+            self.window = current_title;
+            self.window_exe = exe;
+            if self.idle_label.is_none() {
+                self.switch_to(self.current_window(), seen);
+                self.hint_binding();
+                "시작"
+            } else {
+                "창 기억"
+            }
+        } else {
+            let since = match self.pending_switch.take() {
+                Some((title, since)) if title == current_title => since,
+                _ => seen,
+            };
+
+            if let Some(unlocked_at) = unlocked_at {
+                if self
+                    .segment
+                    .as_ref()
+                    .is_some_and(|segment| segment.start >= unlocked_at)
+                {
+                    self.segment = None;
+                }
+                self.window = current_title.clone();
+                self.window_exe = exe;
+                self.switch_to(Some(current_title.clone()), unlocked_at);
+                self.emit(EventKind::Switch, &current_title, None);
+                self.hint_binding();
+                "잠금 해제 후 전환"
+            } else if (now - since).to_std().unwrap_or_default() >= self.debounce {
+                self.window = current_title.clone();
+                self.window_exe = exe;
+                self.switch_to(Some(current_title.clone()), since);
+                self.emit(EventKind::Switch, &current_title, None);
+                self.hint_binding();
+                if self
+                    .screenshots
+                    .as_ref()
+                    .is_some_and(|screenshots| screenshots.on_switch())
+                {
+                    self.take_screenshot(now);
+                }
+                "전환"
+            } else {
+                self.pending_switch = Some((current_title, since));
+                "전환 대기 (디바운스)"
+            }
+        };
+        let mut decision = action.to_string();
+        if filtered {
+            decision = format!("{}로 묶어 {} (--only 불일치)", OTHER_LABEL, decision);
+        }
+        match self.idle_label {
+            Some(label) => format!("{} 중 {}", label, decision),
+            None => decision,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        if self.control.is_stopped() {
+            return;
+        }
+        let now = Local::now();
+        self.check_sleep(now);
+        self.check_rollover(now);
+        self.heartbeat();
+        self.place_markers();
+        self.check_timer(now);
+        self.check_lock(now);
+        self.check_task(now);
+        if self
+            .last_environment_check
+            .is_none_or(|checked| checked.elapsed() >= ENVIRONMENT_CHECK_INTERVAL)
+        {
+            self.last_environment_check = Some(Instant::now());
+            self.check_network();
+            self.check_location(now);
+            if let Ok(bindings) = binding::load() {
+                self.bindings = bindings;
+            }
+        }
+
+        let paused = self.control.is_paused();
+        if paused && !self.is_paused {
+            self.is_paused = true;
+            self.is_off_hours = false;
+            self.idle_label = None;
+            self.pending_switch = None;
+            self.switch_to(None, now);
+            self.emit(EventKind::Paused, "", None);
+        } else if !paused && self.is_paused {
+            self.is_paused = false;
+            self.switch_to(self.current_window(), now);
+            self.emit(EventKind::Resumed, "", None);
+        }
+
+        if self.is_paused {
+            debug::record(None, "추적 일시 중지");
+            return;
+        }
+
+        let in_off_hours = self
+            .off_hours
+            .is_some_and(|hours| hours.contains(now.time()));
+        if in_off_hours && !self.is_off_hours {
+            self.is_off_hours = true;
+            self.idle_label = None;
+            self.pending_switch = None;
+            self.switch_to(Some(OFF_HOURS_LABEL.to_string()), now);
+            self.emit(EventKind::OffHoursStart, OFF_HOURS_LABEL, None);
+        } else if !in_off_hours && self.is_off_hours {
+            self.is_off_hours = false;
+            let off_time = self.switch_to(self.current_window(), now);
+            self.emit(EventKind::OffHoursEnd, OFF_HOURS_LABEL, off_time);
+        }
+
+        if self.is_off_hours {
+            debug::record(None, "업무 외 시간");
+            return;
+        }
+
+        self.check_remote(now);
+        if self.remote.is_some() {
+            debug::record(None, "원격 제어 중");
+            return;
+        }
+
+        if self.is_locked {
+            if self.idle_label != Some(LOCKED_LABEL) {
+                self.idle_label = Some(LOCKED_LABEL);
+                self.pending_switch = None;
+                let locked_at = uptime::locked_since().unwrap_or(now);
+                self.switch_to(Some(LOCKED_LABEL.to_string()), locked_at);
+                self.emit(EventKind::IdleChange, LOCKED_LABEL, None);
+            }
+            debug::record(None, "화면 잠금");
+            return;
+        }
+
+        let idle_duration = self.provider.idle_duration();
+        if idle_duration >= idle_threshold() {
+            let label = self.provider.idle_label();
+            if self.idle_label.is_none() {
+                self.idle_label = Some(label);
+                self.switch_to(Some(label.to_string()), now);
+                self.emit(EventKind::IdleStart, IDLE_LABEL, None);
+                if label != IDLE_LABEL {
+                    self.emit(EventKind::IdleChange, label, None);
+                }
+            } else if self.idle_label != Some(label) {
+                self.idle_label = Some(label);
+                self.switch_to(Some(label.to_string()), now);
+                self.emit(EventKind::IdleChange, label, None);
+            }
+        } else if self.idle_label.take().is_some() {
+            let resumed = self.in_unlock_grace(now).unwrap_or(now);
+            let idle_time = self.switch_to(self.current_window(), resumed);
+            self.emit(EventKind::Active, &self.window, idle_time);
+        }
+
+        let capture = self.provider.capture();
+        let decision = self.follow(capture.as_ref(), now);
+        debug::record(capture.as_ref(), &decision);
+
+        if self.idle_label.is_none()
+            && self
+                .screenshots
+                .as_ref()
+                .is_some_and(|screenshots| screenshots.is_due())
+        {
+            self.take_screenshot(now);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, POINT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::Shell::{
+    NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, NIM_MODIFY, NOTIFYICONDATAW,
+    Shell_NotifyIconW,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    AppendMenuW, CreatePopupMenu, CreateWindowExW, DefWindowProcW, DestroyMenu, DispatchMessageW,
+    GetCursorPos, GetMessageW, IDI_APPLICATION, LoadIconW, MB_ICONINFORMATION, MB_OK, MF_GRAYED,
+    MF_SEPARATOR, MF_STRING, MSG, MessageBoxW, RegisterClassW, SetForegroundWindow, TPM_NONOTIFY,
+    TPM_RETURNCMD, TPM_RIGHTBUTTON, TrackPopupMenu, TranslateMessage, WINDOW_EX_STYLE, WM_APP,
+    WM_LBUTTONUP, WM_RBUTTONUP, WNDCLASSW, WS_OVERLAPPED,
+};
+use windows::core::{HSTRING, PCWSTR, w};
+
+use crate::config::Goal;
+use crate::control::Control;
+use crate::format::format_duration;
+use crate::report::View;
+use crate::store::{Query, Result, Store};
+
+const CALLBACK_MESSAGE: u32 = WM_APP + 1;
+const SUMMARY_LIMIT: usize = 10;
+const PAUSE_ID: usize = 1;
+const RESUME_ID: usize = 2;
+const SUMMARY_ID: usize = 3;
+const EXIT_ID: usize = 4;
+
+type ExitHandler = Box<dyn Fn() -> Result<String> + Send + Sync>;
+
+struct Tray {
+    control: Arc<Control>,
+    store: Arc<Mutex<Box<dyn Store>>>,
+    goals: Vec<Goal>,
+    exit: ExitHandler,
+}
+
+static TRAY: OnceLock<Tray> = OnceLock::new();
+
+fn icon_data(hwnd: HWND, paused: bool) -> NOTIFYICONDATAW {
+    let mut data = NOTIFYICONDATAW {
+        cbSize: size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: hwnd,
+        uID: 1,
+        uFlags: NIF_ICON | NIF_MESSAGE | NIF_TIP,
+        uCallbackMessage: CALLBACK_MESSAGE,
+        hIcon: unsafe { LoadIconW(None, IDI_APPLICATION) }.unwrap_or_default(),
+        ..Default::default()
+    };
+    let tip = if paused {
+        "window-change-detector (일시 중지)"
+    } else {
+        "window-change-detector"
+    };
+    for (slot, unit) in data.szTip.iter_mut().take(127).zip(tip.encode_utf16()) {
+        *slot = unit;
+    }
+    data
+}
+
+impl Tray {
+    fn summary(&self) -> String {
+        let status = self.control.status();
+        let mut lines = vec![match &status.title {
+            _ if status.paused => "추적 일시 중지".to_string(),
+            Some(title) => format!("현재 창: {}", title),
+            None => "현재 창: -".to_string(),
+        }];
+        lines.push(String::new());
+        let query = Query {
+            from: Some(self.control.session_start()),
+            to: None,
+        };
+        let store = self.store.lock().unwrap();
+        match View::default().summarize(store.as_ref(), &query, &self.goals) {
+            Ok(entries) if entries.is_empty() => {
+                lines.push("아직 기록된 창이 없습니다".to_string())
+            }
+            Ok(entries) => lines.extend(
+                entries
+                    .iter()
+                    .take(SUMMARY_LIMIT)
+                    .map(|(title, spent)| format!("{}  {}", format_duration(spent), title)),
+            ),
+            Err(e) => lines.push(format!("요약 실패: {}", e)),
+        }
+        lines.join("\n")
+    }
+
+    fn menu(&self, hwnd: HWND) -> windows::core::Result<usize> {
+        let paused = self.control.is_paused();
+        let enabled = |on: bool| if on { MF_STRING } else { MF_STRING | MF_GRAYED };
+        unsafe {
+            let menu = CreatePopupMenu()?;
+            AppendMenuW(menu, enabled(!paused), PAUSE_ID, w!("추적 일시 중지"))?;
+            AppendMenuW(menu, enabled(paused), RESUME_ID, w!("추적 재개"))?;
+            AppendMenuW(menu, MF_STRING, SUMMARY_ID, w!("요약 보기"))?;
+            AppendMenuW(menu, MF_SEPARATOR, 0, PCWSTR::null())?;
+            AppendMenuW(menu, MF_STRING, EXIT_ID, w!("저장 후 종료"))?;
+            let mut point = POINT::default();
+            GetCursorPos(&mut point)?;
+            let _ = SetForegroundWindow(hwnd);
+            let command = TrackPopupMenu(
+                menu,
+                TPM_RETURNCMD | TPM_NONOTIFY | TPM_RIGHTBUTTON,
+                point.x,
+                point.y,
+                None,
+                hwnd,
+                None,
+            );
+            DestroyMenu(menu)?;
+            Ok(command.0 as usize)
+        }
+    }
+
+    fn handle(&self, hwnd: HWND, command: usize) {
+        match command {
+            PAUSE_ID => self.control.pause(),
+            RESUME_ID => self.control.resume(),
+            SUMMARY_ID => message(hwnd, &self.summary()),
+            EXIT_ID => {
+                let text = match (self.exit)() {
+                    Ok(filename) => format!("로그 파일로 저장됨: {}", filename),
+                    Err(e) => format!("로그 파일 생성 실패: {}", e),
+                };
+                unsafe {
+                    let _ = Shell_NotifyIconW(NIM_DELETE, &icon_data(hwnd, false));
+                }
+                message(hwnd, &text);
+                std::process::exit(0);
+            }
+            _ => return,
+        }
+        unsafe {
+            let _ = Shell_NotifyIconW(NIM_MODIFY, &icon_data(hwnd, self.control.is_paused()));
+        }
+    }
+}
+
+fn message(hwnd: HWND, text: &str) {
+    unsafe {
+        MessageBoxW(
+            Some(hwnd),
+            &HSTRING::from(text),
+            w!("window-change-detector"),
+            MB_OK | MB_ICONINFORMATION,
+        );
+    }
+}
+
+extern "system" fn window_proc(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if message == CALLBACK_MESSAGE && matches!(lparam.0 as u32, WM_LBUTTONUP | WM_RBUTTONUP) {
+        if let Some(tray) = TRAY.get() {
+            match tray.menu(hwnd) {
+                Ok(command) => tray.handle(hwnd, command),
+                Err(e) => eprintln!("트레이 메뉴 표시 실패: {}", e),
+            }
+        }
+        return LRESULT(0);
+    }
+    unsafe { DefWindowProcW(hwnd, message, wparam, lparam) }
+}
+
+fn run() -> windows::core::Result<()> {
+    unsafe {
+        let instance = HINSTANCE::from(GetModuleHandleW(None)?);
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance,
+            lpszClassName: w!("WindowChangeDetectorTray"),
+            ..Default::default()
+        };
+        RegisterClassW(&class);
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            w!("WindowChangeDetectorTray"),
+            w!("window-change-detector"),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            Some(instance),
+            None,
+        )?;
+        Shell_NotifyIconW(NIM_ADD, &icon_data(hwnd, false)).ok()?;
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn(
+    control: Arc<Control>,
+    store: Arc<Mutex<Box<dyn Store>>>,
+    goals: Vec<Goal>,
+    exit: impl Fn() -> Result<String> + Send + Sync + 'static,
+) {
+    let _ = TRAY.set(Tray {
+        control,
+        store,
+        goals,
+        exit: Box::new(exit),
+    });
+    thread::spawn(|| {
+        if let Err(e) = run() {
+            eprintln!("트레이 아이콘을 만들 수 없습니다: {}", e);
+        }
+    });
+}
@@ -0,0 +1,660 @@
+use std::collections::HashMap;
+use std::io::{Stdout, stdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Local, TimeDelta, Timelike};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::crossterm::cursor::{Hide, Show};
+use ratatui::crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+    MouseEvent, MouseEventKind,
+};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Row, Table};
+
+use crate::amend::{self, Correction};
+use crate::categories;
+use crate::config::{Goal, Theme};
+use crate::control::{Control, Status};
+use crate::format::format_duration;
+use crate::idle::IDLE_LABELS;
+use crate::report::category;
+use crate::store::{Interval, Query, Store};
+
+const INPUT_POLL: Duration = Duration::from_millis(200);
+const SCALES: [i64; 7] = [1, 2, 5, 10, 15, 30, 60];
+const SCROLL_COLUMNS: i32 = 10;
+
+struct Palette {
+    base: Style,
+    categories: [Color; 8],
+    other: Color,
+    idle: Color,
+    accent: Color,
+    muted: Color,
+}
+
+const DARK: Palette = Palette {
+    base: Style::new(),
+    categories: [
+        Color::Rgb(86, 180, 233),
+        Color::Rgb(230, 159, 0),
+        Color::Rgb(0, 158, 115),
+        Color::Rgb(240, 228, 66),
+        Color::Rgb(204, 121, 167),
+        Color::Rgb(0, 114, 178),
+        Color::Rgb(213, 94, 0),
+        Color::Rgb(255, 255, 255),
+    ],
+    other: Color::Gray,
+    idle: Color::DarkGray,
+    accent: Color::Yellow,
+    muted: Color::DarkGray,
+};
+
+const LIGHT: Palette = Palette {
+    base: Style::new().fg(Color::Black).bg(Color::White),
+    categories: [
+        Color::Rgb(0, 114, 178),
+        Color::Rgb(213, 94, 0),
+        Color::Rgb(0, 158, 115),
+        Color::Rgb(204, 121, 167),
+        Color::Rgb(230, 159, 0),
+        Color::Rgb(86, 180, 233),
+        Color::Rgb(0, 0, 0),
+        Color::Rgb(240, 228, 66),
+    ],
+    other: Color::DarkGray,
+    idle: Color::Gray,
+    accent: Color::Rgb(0, 114, 178),
+    muted: Color::DarkGray,
+};
+
+const HIGH_CONTRAST: Palette = Palette {
+    base: Style::new().fg(Color::White).bg(Color::Black),
+    categories: [
+        Color::LightYellow,
+        Color::LightCyan,
+        Color::LightMagenta,
+        Color::LightGreen,
+        Color::White,
+        Color::LightRed,
+        Color::LightBlue,
+        Color::Yellow,
+    ],
+    other: Color::Gray,
+    idle: Color::DarkGray,
+    accent: Color::LightYellow,
+    muted: Color::Gray,
+};
+
+fn palette(theme: Theme) -> &'static Palette {
+    match theme {
+        Theme::Dark => &DARK,
+        Theme::Light => &LIGHT,
+        Theme::HighContrast => &HIGH_CONTRAST,
+    }
+}
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+pub type Finish = Arc<dyn Fn() + Send + Sync>;
+
+fn floor(time: DateTime<Local>, minutes: i64) -> DateTime<Local> {
+    let seconds = time.num_seconds_from_midnight() as i64 % (minutes * 60);
+    time.with_nanosecond(0).unwrap_or(time) - TimeDelta::seconds(seconds)
+}
+
+struct Timeline {
+    intervals: Vec<Interval>,
+    scale: Option<usize>,
+    offset: Option<DateTime<Local>>,
+    cursor: DateTime<Local>,
+    follow: bool,
+    bar: Rect,
+}
+
+impl Timeline {
+    fn new(intervals: Vec<Interval>) -> Timeline {
+        Timeline {
+            intervals,
+            scale: None,
+            offset: None,
+            cursor: Local::now(),
+            follow: true,
+            bar: Rect::default(),
+        }
+    }
+
+    fn minutes(&self) -> i64 {
+        SCALES[self.scale.unwrap_or(0)]
+    }
+
+    fn step(&self) -> TimeDelta {
+        TimeDelta::minutes(self.minutes())
+    }
+
+    fn first_start(&self, now: DateTime<Local>) -> DateTime<Local> {
+        self.intervals
+            .iter()
+            .map(|interval| interval.start)
+            .min()
+            .unwrap_or(now)
+    }
+
+    fn fit(&mut self, width: u16, now: DateTime<Local>) -> DateTime<Local> {
+        let width = i64::from(width.max(1));
+        if self.follow {
+            self.cursor = now;
+        }
+        if self.scale.is_none() {
+            let span = (now - self.first_start(now)).num_minutes();
+            self.scale = Some(
+                SCALES
+                    .iter()
+                    .position(|minutes| minutes * (width - 1) >= span)
+                    .unwrap_or(SCALES.len() - 1),
+            );
+        }
+        let step = self.step();
+        let minutes = self.minutes();
+        let first = floor(self.first_start(now), minutes);
+        let offset = self.offset.get_or_insert(first);
+        if self.cursor < *offset {
+            *offset = floor(self.cursor, minutes);
+        } else if self.cursor >= *offset + step * width as i32 {
+            *offset = floor(self.cursor, minutes) - step * (width as i32 - 1);
+        }
+        *offset
+    }
+
+    fn dominant<'a>(
+        &'a self,
+        current: Option<&'a Interval>,
+        from: DateTime<Local>,
+        to: DateTime<Local>,
+    ) -> Option<&'a Interval> {
+        self.intervals
+            .iter()
+            .chain(current)
+            .filter_map(|interval| {
+                let overlap = interval.end.min(to) - interval.start.max(from);
+                (overlap > TimeDelta::zero()).then_some((overlap, interval))
+            })
+            .max_by_key(|(overlap, _)| *overlap)
+            .map(|(_, interval)| interval)
+    }
+
+    fn selected(&self) -> Option<Interval> {
+        let start = floor(self.cursor, self.minutes());
+        self.dominant(None, start, start + self.step()).cloned()
+    }
+
+    fn move_cursor(&mut self, columns: i32) {
+        self.cursor += self.step() * columns;
+        self.follow = self.cursor >= Local::now();
+    }
+
+    fn zoom(&mut self, delta: isize) {
+        let scale = self.scale.unwrap_or(0).saturating_add_signed(delta);
+        self.scale = Some(scale.min(SCALES.len() - 1));
+        self.offset = None;
+    }
+
+    fn key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(-1),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(1),
+            KeyCode::PageUp => self.move_cursor(-SCROLL_COLUMNS),
+            KeyCode::PageDown => self.move_cursor(SCROLL_COLUMNS),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom(-1),
+            KeyCode::Char('-') => self.zoom(1),
+            KeyCode::Home => {
+                self.cursor = self.first_start(Local::now());
+                self.follow = false;
+            }
+            KeyCode::End => self.follow = true,
+            _ => {}
+        }
+    }
+
+    fn mouse(&mut self, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Moved | MouseEventKind::Down(_) => {
+                let inside = mouse.column >= self.bar.x
+                    && mouse.column < self.bar.right()
+                    && mouse.row >= self.bar.y
+                    && mouse.row < self.bar.bottom();
+                if let (true, Some(offset)) = (inside, self.offset) {
+                    self.cursor = offset + self.step() * i32::from(mouse.column - self.bar.x);
+                    self.follow = false;
+                }
+            }
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollLeft => {
+                self.move_cursor(-SCROLL_COLUMNS)
+            }
+            MouseEventKind::ScrollDown | MouseEventKind::ScrollRight => {
+                self.move_cursor(SCROLL_COLUMNS)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn block_style(interval: &Interval, goals: &[Goal], palette: &Palette) -> (&'static str, Color) {
+    if IDLE_LABELS.contains(&interval.title.as_str()) {
+        return ("░", palette.idle);
+    }
+    let name = category(&interval.title, interval.exe.as_deref(), goals);
+    let position = goals
+        .iter()
+        .map(|goal| goal.name.as_str())
+        .chain(categories::names())
+        .position(|known| known == name);
+    match position {
+        Some(index) => ("█", palette.categories[index % palette.categories.len()]),
+        None => ("█", palette.other),
+    }
+}
+
+struct Dashboard {
+    session_start: DateTime<Local>,
+    totals: HashMap<String, Duration>,
+    goals: Vec<Goal>,
+    palette: &'static Palette,
+    timeline: Timeline,
+    store: Arc<Mutex<Box<dyn Store>>>,
+    input: Option<String>,
+    message: Option<String>,
+}
+
+impl Dashboard {
+    fn add(&mut self, interval: &Interval) {
+        if interval.end > self.session_start {
+            *self.totals.entry(interval.title.clone()).or_default() += interval.duration();
+        }
+        self.timeline.intervals.push(interval.clone());
+    }
+
+    fn roll_over(&mut self, session_start: DateTime<Local>) {
+        if session_start != self.session_start {
+            self.session_start = session_start;
+            self.totals.clear();
+        }
+    }
+
+    fn amend(&mut self, correction: Correction) {
+        let Some(target) = self.timeline.selected() else {
+            self.message = Some("선택한 위치에 수정할 구간이 없습니다".to_string());
+            return;
+        };
+        let result = amend::amend(self.store.lock().unwrap().as_mut(), &target, &correction);
+        let amended = match result {
+            Ok(amended) => amended,
+            Err(e) => {
+                self.message = Some(format!("수정 실패: {}", e));
+                return;
+            }
+        };
+        if target.start >= self.session_start {
+            if let Some(spent) = self.totals.get_mut(&target.title) {
+                *spent = spent.saturating_sub(target.duration());
+                if spent.is_zero() {
+                    self.totals.remove(&target.title);
+                }
+            }
+            *self.totals.entry(amended.title.clone()).or_default() += amended.duration();
+        }
+        for interval in &mut self.timeline.intervals {
+            if interval.id == target.id {
+                *interval = amended.clone();
+            }
+        }
+        self.message = Some(format!(
+            "수정됨: {}~{} {}{}",
+            amended.start.format("%H:%M"),
+            amended.end.format("%H:%M"),
+            amended.title,
+            amended
+                .tag
+                .as_ref()
+                .map(|tag| format!(" #{}", tag))
+                .unwrap_or_default()
+        ));
+    }
+
+    fn key(&mut self, code: KeyCode) {
+        self.message = None;
+        let Some(input) = &mut self.input else {
+            match code {
+                KeyCode::Char('t') => {
+                    self.input = Some(
+                        self.timeline
+                            .selected()
+                            .and_then(|interval| interval.tag)
+                            .unwrap_or_default(),
+                    )
+                }
+                KeyCode::Char('p') => self.amend(Correction::Private),
+                _ => self.timeline.key(code),
+            }
+            return;
+        };
+        match code {
+            KeyCode::Enter => {
+                let tag = input.trim().to_string();
+                self.input = None;
+                self.amend(Correction::Tag((!tag.is_empty()).then_some(tag)));
+            }
+            KeyCode::Esc => self.input = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(ch) => input.push(ch),
+            _ => {}
+        }
+    }
+
+    fn prompt(&self) -> Line<'static> {
+        match (&self.input, &self.message) {
+            (Some(input), _) => Line::from(format!(
+                "태그 입력: {}_  (Enter 적용, 빈 값이면 태그 제거, Esc 취소)",
+                input
+            ))
+            .style(Style::new().fg(self.palette.accent)),
+            (None, Some(message)) => {
+                Line::from(message.clone()).style(Style::new().fg(self.palette.accent))
+            }
+            (None, None) => Line::from(
+                "←/→ 이동  +/- 확대/축소  Home/End 처음/지금  t 태그  p 비공개  q/Ctrl+C 종료하고 요약 저장",
+            ),
+        }
+    }
+
+    fn ranked(&self, status: &Status, now: DateTime<Local>) -> Vec<(String, Duration)> {
+        let mut totals = self.totals.clone();
+        if let (Some(title), Some(since)) = (&status.title, status.since) {
+            *totals.entry(title.clone()).or_default() += (now - since).to_std().unwrap_or_default();
+        }
+        let mut ranked: Vec<_> = totals.into_iter().collect();
+        ranked.sort_by_key(|(_, spent)| std::cmp::Reverse(*spent));
+        ranked
+    }
+
+    fn timeline_lines(
+        &mut self,
+        width: u16,
+        current: Option<&Interval>,
+        now: DateTime<Local>,
+    ) -> Vec<Line<'static>> {
+        let offset = self.timeline.fit(width, now);
+        let timeline = &self.timeline;
+        let step = timeline.step();
+        let cursor = ((timeline.cursor - offset).num_seconds() / step.num_seconds().max(1))
+            .clamp(0, i64::from(width.max(1)) - 1) as usize;
+
+        let mut ruler = vec![' '; width as usize];
+        let mut free = 0;
+        let mut bar = Vec::new();
+        let mut legend: Vec<(String, Color)> = Vec::new();
+        for column in 0..width as usize {
+            let start = offset + step * column as i32;
+            if start.minute() == 0 && column >= free && column + 2 <= ruler.len() {
+                for (slot, ch) in ruler[column..]
+                    .iter_mut()
+                    .zip(start.format("%H").to_string().chars())
+                {
+                    *slot = ch;
+                }
+                free = column + 3;
+            }
+            bar.push(match timeline.dominant(current, start, start + step) {
+                Some(interval) => {
+                    let (symbol, color) = block_style(interval, &self.goals, self.palette);
+                    let name = category(&interval.title, interval.exe.as_deref(), &self.goals);
+                    if symbol == "█" && !legend.iter().any(|(known, _)| *known == name) {
+                        legend.push((name, color));
+                    }
+                    let style = Style::new().fg(color);
+                    Span::styled(
+                        symbol,
+                        if column == cursor {
+                            style.add_modifier(Modifier::REVERSED)
+                        } else {
+                            style
+                        },
+                    )
+                }
+                None if column == cursor => Span::raw("│"),
+                None => Span::raw(" "),
+            });
+        }
+
+        let mut marker = " ".repeat(cursor);
+        marker.push('▲');
+        let start = offset + step * cursor as i32;
+        let inspect = match timeline.dominant(current, start, start + step) {
+            Some(interval) => format!(
+                "{}~{} ({}) [{}] {}",
+                interval.start.format("%H:%M"),
+                interval.end.format("%H:%M"),
+                format_duration(&interval.duration()),
+                category(&interval.title, interval.exe.as_deref(), &self.goals),
+                interval.title
+            ),
+            None => format!("{} 기록 없음", start.format("%H:%M")),
+        };
+        let legend = legend
+            .into_iter()
+            .flat_map(|(name, color)| {
+                [
+                    Span::styled("█ ", Style::new().fg(color)),
+                    Span::raw(format!("{}  ", name)),
+                ]
+            })
+            .chain([Span::styled(
+                format!("{}분 단위", timeline.minutes()),
+                Style::new().fg(self.palette.muted),
+            )])
+            .collect::<Vec<_>>();
+
+        vec![
+            Line::from(ruler.into_iter().collect::<String>()),
+            Line::from(bar.clone()),
+            Line::from(bar),
+            Line::from(marker),
+            Line::from(inspect).style(Style::new().add_modifier(Modifier::BOLD)),
+            Line::from(legend),
+        ]
+    }
+
+    fn draw(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>, status: &Status) {
+        let now = Local::now();
+        let ranked = self.ranked(status, now);
+        let total: Duration = ranked.iter().map(|(_, spent)| *spent).sum();
+        let current = match (&status.title, status.since) {
+            (Some(title), Some(since)) if !status.paused => {
+                Some(Interval::new(since, now, title.clone(), None))
+            }
+            _ => None,
+        };
+        let Ok(area) = terminal.size() else {
+            return;
+        };
+        let [header, table, timeline] = Layout::vertical([
+            Constraint::Length(5),
+            Constraint::Min(3),
+            Constraint::Length(8),
+        ])
+        .areas(Rect::new(0, 0, area.width, area.height));
+        let inner = Block::new().borders(Borders::ALL).inner(timeline);
+        self.timeline.bar = Rect::new(inner.x, inner.y + 1, inner.width, 2);
+        let timeline_lines = self.timeline_lines(inner.width, current.as_ref(), now);
+        let prompt = self.prompt();
+
+        let _ = terminal.draw(|frame| {
+            frame.render_widget(Block::new().style(self.palette.base), frame.area());
+            let current = match (&status.title, status.since) {
+                _ if status.paused => "추적 일시 중지".to_string(),
+                (Some(title), Some(since)) => format!(
+                    "{}  ({})",
+                    title,
+                    format_duration(&(now - since).to_std().unwrap_or_default())
+                ),
+                _ => "-".to_string(),
+            };
+            let session = (now - self.session_start).to_std().unwrap_or_default();
+            frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(current).style(Style::new().add_modifier(Modifier::BOLD)),
+                    Line::from(format!(
+                        "세션 {} / 기록 {}",
+                        format_duration(&session),
+                        format_duration(&total)
+                    )),
+                    prompt,
+                ])
+                .block(Block::new().borders(Borders::ALL).title(" 현재 창 ")),
+                header,
+            );
+
+            let visible = table.height.saturating_sub(3) as usize;
+            let rows = ranked
+                .iter()
+                .take(visible)
+                .enumerate()
+                .map(|(rank, (title, spent))| {
+                    let share = spent.as_secs_f64() / total.as_secs_f64().max(1.0) * 100.0;
+                    Row::new(vec![
+                        format!("{}", rank + 1),
+                        title.clone(),
+                        format_duration(spent),
+                        format!("{:.0}%", share),
+                    ])
+                });
+            frame.render_widget(
+                Table::new(
+                    rows,
+                    [
+                        Constraint::Length(3),
+                        Constraint::Fill(1),
+                        Constraint::Length(12),
+                        Constraint::Length(5),
+                    ],
+                )
+                .header(
+                    Row::new(vec!["#", "창", "시간", "비율"])
+                        .style(Style::new().add_modifier(Modifier::BOLD)),
+                )
+                .block(Block::new().borders(Borders::ALL).title(" 누적 시간 ")),
+                table,
+            );
+
+            frame.render_widget(
+                Paragraph::new(timeline_lines)
+                    .block(Block::new().borders(Borders::ALL).title(" 오늘 타임라인 ")),
+                timeline,
+            );
+        });
+    }
+}
+
+pub fn restore() {
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+        let _ = disable_raw_mode();
+        let _ = execute!(stdout(), DisableMouseCapture, Show, LeaveAlternateScreen);
+    }
+}
+
+fn run(
+    control: Arc<Control>,
+    intervals: Receiver<Interval>,
+    mut dashboard: Dashboard,
+    finish: Finish,
+) -> std::io::Result<()> {
+    enable_raw_mode()?;
+    ACTIVE.store(true, Ordering::SeqCst);
+    execute!(stdout(), EnterAlternateScreen, EnableMouseCapture, Hide)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    terminal.clear()?;
+    while ACTIVE.load(Ordering::SeqCst) {
+        dashboard.roll_over(control.session_start());
+        dashboard.draw(&mut terminal, &control.status());
+        if event::poll(INPUT_POLL)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let interrupt = key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL);
+                    let quit = key.code == KeyCode::Char('q') && dashboard.input.is_none();
+                    if interrupt || quit {
+                        restore();
+                        finish();
+                        return Ok(());
+                    }
+                    dashboard.key(key.code);
+                }
+                Event::Mouse(mouse) => dashboard.timeline.mouse(mouse),
+                _ => {}
+            }
+        }
+        loop {
+            match intervals.try_recv() {
+                Ok(interval) => dashboard.add(&interval),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    restore();
+                    return Ok(());
+                }
+            }
+        }
+    }
+    restore();
+    Ok(())
+}
+
+pub fn spawn(
+    control: Arc<Control>,
+    store: Arc<Mutex<Box<dyn Store>>>,
+    goals: Vec<Goal>,
+    theme: Theme,
+    finish: Finish,
+) {
+    let session_start = control.session_start();
+    let intervals = control.subscribe_intervals();
+    let today = Query::between(Some(session_start.date_naive()), None);
+    let history = store.lock().unwrap().query(&today).unwrap_or_default();
+    let mut totals = HashMap::<String, Duration>::new();
+    for interval in history
+        .iter()
+        .filter(|interval| interval.end > session_start)
+    {
+        *totals.entry(interval.title.clone()).or_default() += interval.duration();
+    }
+    let dashboard = Dashboard {
+        session_start,
+        totals,
+        goals,
+        palette: palette(theme),
+        timeline: Timeline::new(history),
+        store,
+        input: None,
+        message: None,
+    };
+    thread::spawn(move || {
+        if let Err(e) = run(control, intervals, dashboard, finish) {
+            restore();
+            eprintln!("대시보드를 표시할 수 없습니다: {}", e);
+        }
+    });
+}
@@ -0,0 +1,255 @@
+#[cfg(windows)]
+use std::sync::Mutex;
+#[cfg(windows)]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(windows)]
+use std::thread;
+
+use chrono::{DateTime, Local, TimeDelta};
+#[cfg(windows)]
+use windows::Win32::Foundation::{HANDLE, HINSTANCE, HWND, LPARAM, LRESULT, WPARAM};
+#[cfg(windows)]
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+#[cfg(windows)]
+use windows::Win32::System::Power::RegisterSuspendResumeNotification;
+#[cfg(windows)]
+use windows::Win32::System::RemoteDesktop::{
+    NOTIFY_FOR_THIS_SESSION, WTS_CURRENT_SERVER_HANDLE, WTS_CURRENT_SESSION, WTS_SESSIONSTATE_LOCK,
+    WTSFreeMemory, WTSINFOEXW, WTSINFOW, WTSQuerySessionInformationW,
+    WTSRegisterSessionNotification, WTSSessionInfo, WTSSessionInfoEx,
+};
+#[cfg(windows)]
+use windows::Win32::System::SystemInformation::GetTickCount64;
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DEVICE_NOTIFY_WINDOW_HANDLE, DefWindowProcW, DispatchMessageW, GetMessageW,
+    HWND_MESSAGE, MSG, PBT_APMRESUMEAUTOMATIC, PBT_APMRESUMESUSPEND, PBT_APMSUSPEND,
+    RegisterClassW, WINDOW_EX_STYLE, WINDOW_STYLE, WM_POWERBROADCAST, WM_WTSSESSION_CHANGE,
+    WNDCLASSW, WTS_SESSION_LOCK, WTS_SESSION_UNLOCK,
+};
+#[cfg(windows)]
+use windows::core::{PCWSTR, PWSTR, w};
+
+#[cfg(windows)]
+const FILETIME_UNIX_OFFSET_SECS: i64 = 11_644_473_600;
+
+#[cfg(windows)]
+static WATCHING_LOCK: AtomicBool = AtomicBool::new(false);
+#[cfg(windows)]
+static LOCKED: AtomicBool = AtomicBool::new(false);
+#[cfg(windows)]
+static LOCKED_AT: Mutex<Option<DateTime<Local>>> = Mutex::new(None);
+#[cfg(windows)]
+static SUSPENDED_AT: Mutex<Option<DateTime<Local>>> = Mutex::new(None);
+#[cfg(windows)]
+static SLEEPS: Mutex<Vec<(DateTime<Local>, DateTime<Local>)>> = Mutex::new(Vec::new());
+
+#[cfg(windows)]
+pub fn boot_time() -> Option<DateTime<Local>> {
+    let uptime_ms = unsafe { GetTickCount64() };
+    Some(Local::now() - TimeDelta::milliseconds(uptime_ms as i64))
+}
+
+#[cfg(not(windows))]
+pub fn boot_time() -> Option<DateTime<Local>> {
+    let uptime = std::fs::read_to_string("/proc/uptime").ok()?;
+    let secs: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    Some(Local::now() - TimeDelta::milliseconds((secs * 1000.0) as i64))
+}
+
+#[cfg(windows)]
+fn from_filetime(filetime: i64) -> Option<DateTime<Local>> {
+    if filetime <= 0 {
+        return None;
+    }
+    let secs = filetime / 10_000_000 - FILETIME_UNIX_OFFSET_SECS;
+    let nanos = (filetime % 10_000_000) as u32 * 100;
+    DateTime::from_timestamp(secs, nanos).map(|time| time.with_timezone(&Local))
+}
+
+#[cfg(windows)]
+pub fn logon_time() -> Option<DateTime<Local>> {
+    unsafe {
+        let mut buffer = PWSTR::null();
+        let mut bytes = 0;
+        WTSQuerySessionInformationW(
+            Some(WTS_CURRENT_SERVER_HANDLE),
+            WTS_CURRENT_SESSION,
+            WTSSessionInfo,
+            &mut buffer,
+            &mut bytes,
+        )
+        .ok()?;
+        let logon = (bytes as usize >= size_of::<WTSINFOW>())
+            .then(|| (*(buffer.0 as *const WTSINFOW)).LogonTime);
+        WTSFreeMemory(buffer.0 as *mut std::ffi::c_void);
+        from_filetime(logon?)
+    }
+}
+
+#[cfg(windows)]
+pub fn is_locked() -> bool {
+    if WATCHING_LOCK.load(Ordering::SeqCst) {
+        return LOCKED.load(Ordering::SeqCst);
+    }
+    query_locked()
+}
+
+#[cfg(windows)]
+pub fn locked_since() -> Option<DateTime<Local>> {
+    let locked_at = *LOCKED_AT.lock().unwrap();
+    locked_at.filter(|_| is_locked())
+}
+
+#[cfg(windows)]
+pub fn take_sleeps() -> Vec<(DateTime<Local>, DateTime<Local>)> {
+    std::mem::take(&mut *SLEEPS.lock().unwrap())
+}
+
+#[cfg(windows)]
+fn on_power(event: u32) {
+    match event {
+        PBT_APMSUSPEND => *SUSPENDED_AT.lock().unwrap() = Some(Local::now()),
+        PBT_APMRESUMEAUTOMATIC | PBT_APMRESUMESUSPEND => {
+            if let Some(suspended_at) = SUSPENDED_AT.lock().unwrap().take() {
+                SLEEPS.lock().unwrap().push((suspended_at, Local::now()));
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(windows)]
+extern "system" fn session_proc(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if message == WM_WTSSESSION_CHANGE {
+        match wparam.0 as u32 {
+            WTS_SESSION_LOCK => {
+                *LOCKED_AT.lock().unwrap() = Some(Local::now());
+                LOCKED.store(true, Ordering::SeqCst);
+            }
+            WTS_SESSION_UNLOCK => LOCKED.store(false, Ordering::SeqCst),
+            _ => {}
+        }
+        return LRESULT(0);
+    }
+    if message == WM_POWERBROADCAST {
+        on_power(wparam.0 as u32);
+        return LRESULT(1);
+    }
+    unsafe { DefWindowProcW(hwnd, message, wparam, lparam) }
+}
+
+#[cfg(windows)]
+fn listen_session() -> windows::core::Result<()> {
+    unsafe {
+        let instance = HINSTANCE::from(GetModuleHandleW(None)?);
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(session_proc),
+            hInstance: instance,
+            lpszClassName: w!("WindowChangeDetectorSession"),
+            ..Default::default()
+        };
+        RegisterClassW(&class);
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            w!("WindowChangeDetectorSession"),
+            PCWSTR::null(),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            Some(HWND_MESSAGE),
+            None,
+            Some(instance),
+            None,
+        )?;
+        if let Err(e) =
+            RegisterSuspendResumeNotification(HANDLE(hwnd.0), DEVICE_NOTIFY_WINDOW_HANDLE)
+        {
+            eprintln!(
+                "절전 알림을 등록할 수 없어 시각 차이로 절전을 감지합니다: {}",
+                e
+            );
+        }
+        WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION)?;
+        if query_locked() {
+            *LOCKED_AT.lock().unwrap() = Some(Local::now());
+            LOCKED.store(true, Ordering::SeqCst);
+        }
+        WATCHING_LOCK.store(true, Ordering::SeqCst);
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            DispatchMessageW(&msg);
+        }
+    }
+    WATCHING_LOCK.store(false, Ordering::SeqCst);
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn watch_session() {
+    thread::spawn(|| {
+        if let Err(e) = listen_session() {
+            eprintln!(
+                "세션 잠금 알림을 등록할 수 없어 주기적으로 확인합니다: {}",
+                e
+            );
+        }
+    });
+}
+
+#[cfg(windows)]
+fn query_locked() -> bool {
+    unsafe {
+        let mut buffer = PWSTR::null();
+        let mut bytes = 0;
+        if WTSQuerySessionInformationW(
+            Some(WTS_CURRENT_SERVER_HANDLE),
+            WTS_CURRENT_SESSION,
+            WTSSessionInfoEx,
+            &mut buffer,
+            &mut bytes,
+        )
+        .is_err()
+        {
+            return false;
+        }
+        let locked = bytes as usize >= size_of::<WTSINFOEXW>()
+            && (*(buffer.0 as *const WTSINFOEXW))
+                .Data
+                .WTSInfoExLevel1
+                .SessionFlags
+                == WTS_SESSIONSTATE_LOCK as i32;
+        WTSFreeMemory(buffer.0 as *mut std::ffi::c_void);
+        locked
+    }
+}
+
+#[cfg(not(windows))]
+pub fn logon_time() -> Option<DateTime<Local>> {
+    None
+}
+
+#[cfg(not(windows))]
+pub fn is_locked() -> bool {
+    false
+}
+
+#[cfg(not(windows))]
+pub fn locked_since() -> Option<DateTime<Local>> {
+    None
+}
+
+#[cfg(not(windows))]
+pub fn take_sleeps() -> Vec<(DateTime<Local>, DateTime<Local>)> {
+    Vec::new()
+}
+
+#[cfg(not(windows))]
+pub fn watch_session() {}
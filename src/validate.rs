@@ -0,0 +1,213 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+use serde::Deserialize;
+use toml::Spanned;
+
+use crate::config::{Config, parse_limit};
+use crate::hotkey::parse_hotkey;
+use crate::normalize;
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Spans {
+    goals: Vec<Named>,
+    categories: Vec<Named>,
+    limits: BTreeMap<String, Spanned<String>>,
+    billing: BillingSpans,
+    markers: Vec<HotkeySpan>,
+    title_rules: Vec<Spanned<String>>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct BillingSpans {
+    clients: Vec<Named>,
+}
+
+#[derive(Deserialize)]
+struct Named {
+    name: Spanned<String>,
+}
+
+#[derive(Deserialize)]
+struct HotkeySpan {
+    hotkey: Spanned<String>,
+}
+
+fn line_of(text: &str, offset: usize) -> usize {
+    text.get(..offset).unwrap_or(text).matches('\n').count() + 1
+}
+
+fn at(text: &str, span: Range<usize>, message: String) -> String {
+    format!("{}번째 줄: {}", line_of(text, span.start), message)
+}
+
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = (previous + usize::from(ca != *cb))
+                .min(row[j] + 1)
+                .min(current + 1);
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+fn unknown_key(message: &str) -> Option<String> {
+    let (key, rest) = message.strip_prefix("unknown field `")?.split_once('`')?;
+    let expected: Vec<&str> = rest.split('`').skip(1).step_by(2).collect();
+    let closest = expected
+        .iter()
+        .min_by_key(|candidate| distance(key, candidate))
+        .filter(|candidate| distance(key, candidate) <= (key.chars().count() / 4).max(1));
+    Some(match closest {
+        Some(candidate) => format!("알 수 없는 키 '{}' ('{}'의 오타인가요?)", key, candidate),
+        None if expected.is_empty() => format!("알 수 없는 키 '{}'", key),
+        None => format!(
+            "알 수 없는 키 '{}' (사용할 수 있는 키: {})",
+            key,
+            expected.join(", ")
+        ),
+    })
+}
+
+pub fn parse_error(text: &str, error: &toml::de::Error) -> String {
+    let message = unknown_key(error.message()).unwrap_or_else(|| error.message().to_string());
+    match error.span() {
+        Some(span) => at(text, span, message),
+        None => message,
+    }
+}
+
+pub fn check(text: &str, config: &Config) -> Vec<String> {
+    let spans: Spans = toml::from_str(text).unwrap_or_default();
+    let mut problems = Vec::new();
+
+    let mut goal_names = BTreeSet::new();
+    for (goal, span) in config.goals.iter().zip(&spans.goals) {
+        let span = span.name.span();
+        if goal.max_hours.is_some() == goal.min_hours.is_some() {
+            problems.push(at(
+                text,
+                span.clone(),
+                format!(
+                    "목표 '{}'에는 max_hours와 min_hours 중 하나만 지정해야 합니다",
+                    goal.name
+                ),
+            ));
+        }
+        if goal.titles.iter().all(|title| title.trim().is_empty()) {
+            problems.push(at(
+                text,
+                span.clone(),
+                format!(
+                    "목표 '{}'의 titles가 비어 있어 어떤 창과도 일치하지 않습니다",
+                    goal.name
+                ),
+            ));
+        }
+        if !goal_names.insert(goal.name.to_lowercase()) {
+            problems.push(at(
+                text,
+                span,
+                format!("목표 이름 '{}'이(가) 중복됩니다", goal.name),
+            ));
+        }
+    }
+    let mut category_names = BTreeSet::new();
+    for (rule, span) in config.categories.iter().zip(&spans.categories) {
+        let span = span.name.span();
+        let patterns = rule.titles.iter().chain(&rule.exes);
+        if patterns.clone().all(|pattern| pattern.trim().is_empty()) {
+            problems.push(at(
+                text,
+                span.clone(),
+                format!(
+                    "분류 '{}'의 titles와 exes가 비어 있어 어떤 창과도 일치하지 않습니다",
+                    rule.name
+                ),
+            ));
+        }
+        if !category_names.insert(rule.name.to_lowercase()) {
+            problems.push(at(
+                text,
+                span,
+                format!("분류 이름 '{}'이(가) 중복됩니다", rule.name),
+            ));
+        }
+    }
+    for rule in &spans.title_rules {
+        if let Err(e) = normalize::parse(rule.get_ref()) {
+            problems.push(at(text, rule.span(), format!("title_rules: {}", e)));
+        }
+    }
+    for (name, limit) in &spans.limits {
+        if let Err(e) = parse_limit(limit.get_ref()) {
+            problems.push(at(text, limit.span(), format!("limits.{}: {}", name, e)));
+        }
+        if !goal_names.insert(name.to_lowercase()) {
+            problems.push(at(
+                text,
+                limit.span(),
+                format!("limits.{}이(가) 같은 이름의 목표와 충돌합니다", name),
+            ));
+        }
+    }
+
+    let mut client_names = BTreeSet::new();
+    let mut client_titles: BTreeMap<String, &str> = BTreeMap::new();
+    for (client, span) in config.billing.clients.iter().zip(&spans.billing.clients) {
+        let span = span.name.span();
+        if !client_names.insert(client.name.to_lowercase()) {
+            problems.push(at(
+                text,
+                span.clone(),
+                format!("청구 대상 이름 '{}'이(가) 중복됩니다", client.name),
+            ));
+        }
+        for title in &client.titles {
+            match client_titles.get(&title.to_lowercase()) {
+                Some(other) if *other != client.name => problems.push(at(
+                    text,
+                    span.clone(),
+                    format!(
+                        "청구 대상 '{}'와(과) '{}'가 같은 제목 패턴 '{}'을(를) 씁니다",
+                        other, client.name, title
+                    ),
+                )),
+                _ => {
+                    client_titles.insert(title.to_lowercase(), &client.name);
+                }
+            }
+        }
+    }
+
+    let mut hotkeys = Vec::new();
+    for (marker, span) in config.markers.iter().zip(&spans.markers) {
+        let span = span.hotkey.span();
+        match parse_hotkey(&marker.hotkey) {
+            Err(e) => problems.push(at(
+                text,
+                span,
+                format!("마커 '{}'의 단축키: {}", marker.name, e),
+            )),
+            Ok(hotkey) if hotkeys.contains(&hotkey) => problems.push(at(
+                text,
+                span,
+                format!(
+                    "마커 '{}'의 단축키 {}가 다른 마커와 겹칩니다",
+                    marker.name, marker.hotkey
+                ),
+            )),
+            Ok(hotkey) => hotkeys.push(hotkey),
+        }
+    }
+    problems
+}
@@ -0,0 +1,409 @@
+#[cfg(windows)]
+use std::sync::Mutex;
+#[cfg(windows)]
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicU32};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+#[cfg(windows)]
+use std::time::Instant;
+
+use chrono::{DateTime, Local};
+#[cfg(windows)]
+use windows::Win32::Foundation::{CloseHandle, HWND};
+#[cfg(windows)]
+use windows::Win32::Graphics::Dwm::{DWMWA_CLOAKED, DwmGetWindowAttribute};
+#[cfg(windows)]
+use windows::Win32::System::SystemInformation::GetTickCount;
+#[cfg(windows)]
+use windows::Win32::System::Threading::{
+    OpenProcess, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION, QueryFullProcessImageNameW,
+};
+#[cfg(windows)]
+use windows::Win32::UI::Accessibility::{HWINEVENTHOOK, SetWinEventHook, UnhookWinEvent};
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::*;
+#[cfg(windows)]
+use windows::core::PWSTR;
+
+pub const DESKTOP_LABEL: &str = "[바탕 화면]";
+pub const UNTITLED_LABEL: &str = "[Untitled]";
+#[cfg(windows)]
+const DESKTOP_WINDOW_CLASSES: &[&str] = &[
+    "Progman",
+    "WorkerW",
+    "Shell_TrayWnd",
+    "Shell_SecondaryTrayWnd",
+];
+#[cfg(windows)]
+const EXCLUDED_WINDOW_CLASSES: &[&str] = &[
+    "MultitaskingViewFrame",
+    "TaskSwitcherWnd",
+    "TaskSwitcherOverlayWnd",
+    "XamlExplorerHostIslandWindow",
+    "ForegroundStaging",
+];
+#[cfg(windows)]
+const CORE_WINDOW_CLASS: &str = "Windows.UI.Core.CoreWindow";
+#[cfg(windows)]
+const APP_FRAME_CLASS: &str = "ApplicationFrameWindow";
+
+#[cfg(windows)]
+fn get_window_class(hwnd: HWND) -> String {
+    unsafe {
+        let mut class: [u16; 256] = [0; 256];
+        let len = GetClassNameW(hwnd, &mut class);
+        String::from_utf16_lossy(&class[..len.max(0) as usize])
+    }
+}
+
+#[cfg(windows)]
+fn is_cloaked(hwnd: HWND) -> bool {
+    let mut cloaked: u32 = 0;
+    unsafe {
+        DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_CLOAKED,
+            &mut cloaked as *mut _ as *mut std::ffi::c_void,
+            size_of::<u32>() as u32,
+        )
+        .is_ok()
+            && cloaked != 0
+    }
+}
+
+#[cfg(windows)]
+fn is_app_core_window(hwnd: HWND) -> bool {
+    if is_cloaked(hwnd) {
+        return false;
+    }
+    let parent = unsafe { GetAncestor(hwnd, GA_PARENT) };
+    let owner = unsafe { GetWindow(hwnd, GW_OWNER) }.ok();
+    [Some(parent), owner]
+        .into_iter()
+        .flatten()
+        .any(|frame| !frame.is_invalid() && get_window_class(frame) == APP_FRAME_CLASS)
+}
+
+#[cfg(windows)]
+static LAST_EXE: Mutex<Option<(u32, Option<String>)>> = Mutex::new(None);
+
+#[cfg(windows)]
+fn get_process_exe(pid: u32) -> Option<String> {
+    let mut last = LAST_EXE.lock().unwrap();
+    if let Some((_, exe)) = last.as_ref().filter(|(last_pid, _)| *last_pid == pid) {
+        return exe.clone();
+    }
+    let exe = query_process_exe(pid);
+    *last = Some((pid, exe.clone()));
+    exe
+}
+
+#[cfg(windows)]
+fn query_process_exe(pid: u32) -> Option<String> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut path: [u16; 1024] = [0; 1024];
+        let mut len = path.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(path.as_mut_ptr()),
+            &mut len,
+        );
+        let _ = CloseHandle(process);
+        result.ok()?;
+        let path = String::from_utf16_lossy(&path[..len as usize]);
+        std::path::Path::new(&path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForegroundSource {
+    Foreground,
+    GuiActive,
+    GuiFocus,
+    Missing,
+}
+
+impl ForegroundSource {
+    pub const ALL: [ForegroundSource; 4] = [
+        ForegroundSource::Foreground,
+        ForegroundSource::GuiActive,
+        ForegroundSource::GuiFocus,
+        ForegroundSource::Missing,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ForegroundSource::Foreground => "GetForegroundWindow",
+            ForegroundSource::GuiActive => "GetGUIThreadInfo (활성 창)",
+            ForegroundSource::GuiFocus => "GetGUIThreadInfo (포커스 창)",
+            ForegroundSource::Missing => "찾지 못함",
+        }
+    }
+}
+
+static SOURCE_COUNTS: [AtomicU64; 4] = [const { AtomicU64::new(0) }; 4];
+
+pub fn source_counts() -> Vec<(ForegroundSource, u64)> {
+    ForegroundSource::ALL
+        .iter()
+        .map(|&source| {
+            (
+                source,
+                SOURCE_COUNTS[source as usize].load(Ordering::SeqCst),
+            )
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+fn find_foreground() -> (Option<HWND>, ForegroundSource) {
+    unsafe {
+        let hwnd = GetForegroundWindow();
+        if !hwnd.is_invalid() {
+            return (Some(hwnd), ForegroundSource::Foreground);
+        }
+        let mut info = GUITHREADINFO {
+            cbSize: size_of::<GUITHREADINFO>() as u32,
+            ..Default::default()
+        };
+        if GetGUIThreadInfo(0, &mut info).is_ok() {
+            if !info.hwndActive.is_invalid() {
+                return (Some(info.hwndActive), ForegroundSource::GuiActive);
+            }
+            if !info.hwndFocus.is_invalid() {
+                let root = GetAncestor(info.hwndFocus, GA_ROOT);
+                let hwnd = if root.is_invalid() {
+                    info.hwndFocus
+                } else {
+                    root
+                };
+                return (Some(hwnd), ForegroundSource::GuiFocus);
+            }
+        }
+        (None, ForegroundSource::Missing)
+    }
+}
+
+#[cfg(windows)]
+fn foreground_window() -> Option<HWND> {
+    let (hwnd, source) = find_foreground();
+    SOURCE_COUNTS[source as usize].fetch_add(1, Ordering::SeqCst);
+    hwnd
+}
+
+pub fn untitled(exe: Option<&str>) -> String {
+    match exe {
+        Some(exe) => format!("{} ({})", UNTITLED_LABEL, exe),
+        None => UNTITLED_LABEL.to_string(),
+    }
+}
+
+pub struct Capture {
+    pub hwnd: isize,
+    pub pid: u32,
+    pub exe: Option<String>,
+    pub class: String,
+    pub raw_title: String,
+    pub title: Result<String, &'static str>,
+    pub seen: DateTime<Local>,
+}
+
+#[cfg(windows)]
+pub fn capture() -> Option<Capture> {
+    let hwnd = foreground_window()?;
+    unsafe {
+        let mut pid = 0;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid));
+        let class = get_window_class(hwnd);
+        let mut buffer: [u16; 512] = [0; 512];
+        let len = GetWindowTextW(hwnd, &mut buffer);
+        let raw_title = String::from_utf16_lossy(&buffer[..len.max(0) as usize]);
+        let exe = get_process_exe(pid);
+        let title = if DESKTOP_WINDOW_CLASSES.contains(&class.as_str()) {
+            Ok(DESKTOP_LABEL.to_string())
+        } else if EXCLUDED_WINDOW_CLASSES.contains(&class.as_str())
+            || (class == CORE_WINDOW_CLASS && !is_app_core_window(hwnd))
+        {
+            Err("제외된 창 클래스")
+        } else if raw_title.is_empty() {
+            Ok(untitled(exe.as_deref()))
+        } else {
+            Ok(raw_title.clone())
+        };
+        Some(Capture {
+            hwnd: hwnd.0 as isize,
+            pid,
+            exe,
+            class,
+            raw_title,
+            title,
+            seen: seen_at(hwnd),
+        })
+    }
+}
+
+#[cfg(windows)]
+pub fn get_active_window_title() -> Option<String> {
+    capture().and_then(|capture| capture.title.ok())
+}
+
+#[cfg(not(windows))]
+pub fn get_active_window_title() -> Option<String> {
+    None
+}
+
+#[cfg(windows)]
+static FOREGROUND_CHANGED: AtomicBool = AtomicBool::new(false);
+#[cfg(windows)]
+static TITLE_CHANGED: AtomicBool = AtomicBool::new(false);
+#[cfg(windows)]
+static EVENT_HWND: AtomicIsize = AtomicIsize::new(0);
+#[cfg(windows)]
+static EVENT_TICK: AtomicU32 = AtomicU32::new(0);
+#[cfg(windows)]
+static CAPTURE_TICK: AtomicU32 = AtomicU32::new(0);
+
+#[cfg(windows)]
+fn seen_at(hwnd: HWND) -> DateTime<Local> {
+    let now = Local::now();
+    let tick = unsafe { GetTickCount() };
+    let previous = CAPTURE_TICK.swap(tick, Ordering::SeqCst);
+    if EVENT_HWND.load(Ordering::SeqCst) != hwnd.0 as isize {
+        return now;
+    }
+    let since_event = tick.wrapping_sub(EVENT_TICK.load(Ordering::SeqCst));
+    if since_event > tick.wrapping_sub(previous) {
+        return now;
+    }
+    now - chrono::Duration::milliseconds(since_event as i64)
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn on_win_event(
+    _hook: HWINEVENTHOOK,
+    event: u32,
+    hwnd: HWND,
+    id_object: i32,
+    _id_child: i32,
+    _thread: u32,
+    time: u32,
+) {
+    let changed = if event == EVENT_SYSTEM_FOREGROUND {
+        &FOREGROUND_CHANGED
+    } else if id_object == OBJID_WINDOW.0 && hwnd == unsafe { GetForegroundWindow() } {
+        &TITLE_CHANGED
+    } else {
+        return;
+    };
+    EVENT_HWND.store(hwnd.0 as isize, Ordering::SeqCst);
+    EVENT_TICK.store(time, Ordering::SeqCst);
+    changed.store(true, Ordering::SeqCst);
+}
+
+#[cfg(windows)]
+pub struct Watcher {
+    foreground: HWINEVENTHOOK,
+    title: Option<HWINEVENTHOOK>,
+}
+
+#[cfg(windows)]
+impl Watcher {
+    pub fn install() -> Option<Watcher> {
+        let foreground = unsafe {
+            SetWinEventHook(
+                EVENT_SYSTEM_FOREGROUND,
+                EVENT_SYSTEM_FOREGROUND,
+                None,
+                Some(on_win_event),
+                0,
+                0,
+                WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS,
+            )
+        };
+        if foreground.is_invalid() {
+            return None;
+        }
+        let mut watcher = Watcher {
+            foreground,
+            title: None,
+        };
+        watcher.watch_title();
+        Some(watcher)
+    }
+
+    fn watch_title(&mut self) {
+        unsafe {
+            if let Some(hook) = self.title.take() {
+                let _ = UnhookWinEvent(hook);
+            }
+            let Some(hwnd) = find_foreground().0 else {
+                return;
+            };
+            let mut pid = 0;
+            GetWindowThreadProcessId(hwnd, Some(&mut pid));
+            let hook = SetWinEventHook(
+                EVENT_OBJECT_NAMECHANGE,
+                EVENT_OBJECT_NAMECHANGE,
+                None,
+                Some(on_win_event),
+                pid,
+                0,
+                WINEVENT_OUTOFCONTEXT,
+            );
+            self.title = (!hook.is_invalid()).then_some(hook);
+        }
+    }
+
+    pub fn wait(&mut self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            unsafe {
+                MsgWaitForMultipleObjects(None, false, remaining.as_millis() as u32, QS_ALLINPUT);
+                let mut msg = MSG::default();
+                while PeekMessageW(&mut msg, None, 0, 0, PM_REMOVE).as_bool() {
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+            }
+            if FOREGROUND_CHANGED.swap(false, Ordering::SeqCst) {
+                TITLE_CHANGED.store(false, Ordering::SeqCst);
+                self.watch_title();
+                return;
+            }
+            if TITLE_CHANGED.swap(false, Ordering::SeqCst) || remaining.is_zero() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        unsafe {
+            if let Some(hook) = self.title.take() {
+                let _ = UnhookWinEvent(hook);
+            }
+            let _ = UnhookWinEvent(self.foreground);
+        }
+    }
+}
+
+#[cfg(not(windows))]
+pub struct Watcher;
+
+#[cfg(not(windows))]
+impl Watcher {
+    pub fn install() -> Option<Watcher> {
+        None
+    }
+
+    pub fn wait(&mut self, timeout: Duration) {
+        std::thread::sleep(timeout);
+    }
+}